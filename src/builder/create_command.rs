@@ -314,6 +314,10 @@ pub struct CreateCommand {
     #[serde(rename = "type")]
     kind: Option<CommandType>,
     nsfw: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    integration_types: Option<Vec<InstallationContext>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    contexts: Option<Vec<InteractionContext>>,
 }
 
 impl CreateCommand {
@@ -331,6 +335,8 @@ impl CreateCommand {
 
             options: Vec::new(),
             nsfw: false,
+            integration_types: None,
+            contexts: None,
         }
     }
 
@@ -423,6 +429,39 @@ impl CreateCommand {
         self.nsfw = nsfw;
         self
     }
+
+    /// Sets the installation contexts where the command is available, replacing the current
+    /// ones. Only for globally-scoped commands.
+    ///
+    /// For example, [`InstallationContext::User`] makes the command available to users who
+    /// installed the application to their account, in any guild or DM.
+    pub fn integration_types(mut self, integration_types: Vec<InstallationContext>) -> Self {
+        self.integration_types = Some(integration_types);
+        self
+    }
+
+    /// Adds an installation context where the command is available. Only for globally-scoped
+    /// commands.
+    pub fn add_integration_type(mut self, integration_type: InstallationContext) -> Self {
+        self.integration_types.get_or_insert_with(Vec::new).push(integration_type);
+        self
+    }
+
+    /// Sets the interaction contexts where the command can be used, replacing the current ones.
+    /// Only for globally-scoped commands.
+    ///
+    /// This supersedes [`Self::dm_permission`].
+    pub fn contexts(mut self, contexts: Vec<InteractionContext>) -> Self {
+        self.contexts = Some(contexts);
+        self
+    }
+
+    /// Adds an interaction context where the command can be used. Only for globally-scoped
+    /// commands.
+    pub fn add_context(mut self, context: InteractionContext) -> Self {
+        self.contexts.get_or_insert_with(Vec::new).push(context);
+        self
+    }
 }
 
 #[cfg(feature = "http")]
@@ -5,6 +5,7 @@ use super::{
     CreateAllowedMentions,
     CreateAttachment,
     CreateEmbed,
+    CreatePoll,
     EditAttachments,
 };
 #[cfg(feature = "http")]
@@ -69,6 +70,8 @@ pub struct CreateMessage {
     flags: Option<MessageFlags>,
     pub(crate) attachments: EditAttachments,
     enforce_nonce: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    poll: Option<CreatePoll>,
 
     // The following fields are handled separately.
     #[serde(skip)]
@@ -288,6 +291,15 @@ impl CreateMessage {
         self.enforce_nonce = enforce_nonce;
         self
     }
+
+    /// Attaches a poll to the message.
+    ///
+    /// **Note**: A message with a poll can not be edited, and the poll can only be ended early by
+    /// its author, through [`Message::end_poll`].
+    pub fn poll(mut self, poll: CreatePoll) -> Self {
+        self.poll = Some(poll);
+        self
+    }
 }
 
 #[cfg(feature = "http")]
@@ -0,0 +1,142 @@
+use std::time::Duration;
+
+use crate::model::prelude::*;
+
+#[derive(Clone, Debug, Default, Serialize)]
+struct CreatePollMedia {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    emoji: Option<CreatePollEmoji>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(untagged)]
+enum CreatePollEmoji {
+    Custom { id: EmojiId },
+    Unicode { name: String },
+}
+
+/// A builder for creating a [`Poll`], sent through [`CreateMessage::poll`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+///
+/// use serenity::builder::{CreateMessage, CreatePoll, CreatePollAnswer};
+///
+/// let poll = CreatePoll::new("Best fruit?")
+///     .answers(vec![
+///         CreatePollAnswer::new("Apple").emoji('\u{1f34e}'),
+///         CreatePollAnswer::new("Banana").emoji('\u{1f34c}'),
+///     ])
+///     .duration(Duration::from_secs(60 * 60 * 24));
+/// let builder = CreateMessage::new().poll(poll);
+/// ```
+///
+/// [`CreateMessage::poll`]: super::CreateMessage::poll
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/poll#poll-create-request-object)
+#[derive(Clone, Debug, Serialize)]
+#[must_use]
+pub struct CreatePoll {
+    question: CreatePollMedia,
+    answers: Vec<CreatePollAnswer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration: Option<u64>,
+    allow_multiselect: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    layout_type: Option<PollLayoutType>,
+}
+
+impl CreatePoll {
+    /// Creates a poll with the given question and no answer.
+    ///
+    /// **Note**: The question must be at most 300 characters.
+    pub fn new(question: impl Into<String>) -> Self {
+        Self {
+            question: CreatePollMedia {
+                text: Some(question.into()),
+                emoji: None,
+            },
+            answers: Vec::new(),
+            duration: None,
+            allow_multiselect: false,
+            layout_type: None,
+        }
+    }
+
+    /// Sets the answers of the poll, replacing the current ones.
+    ///
+    /// **Note**: A poll can have at most 10 answers.
+    pub fn answers(mut self, answers: Vec<CreatePollAnswer>) -> Self {
+        self.answers = answers;
+        self
+    }
+
+    /// Adds an answer to the poll.
+    pub fn answer(mut self, answer: CreatePollAnswer) -> Self {
+        self.answers.push(answer);
+        self
+    }
+
+    /// Sets how long the poll stays open. Discord only supports a precision of hours, so the
+    /// duration is rounded up to the next hour.
+    ///
+    /// Defaults to 24 hours, and can be at most 32 days.
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration.as_secs().div_ceil(60 * 60));
+        self
+    }
+
+    /// Sets whether users may vote for more than one answer. Defaults to `false`.
+    pub fn allow_multiselect(mut self, allow_multiselect: bool) -> Self {
+        self.allow_multiselect = allow_multiselect;
+        self
+    }
+
+    /// Sets the layout of the poll.
+    pub fn layout_type(mut self, layout_type: PollLayoutType) -> Self {
+        self.layout_type = Some(layout_type);
+        self
+    }
+}
+
+/// A builder for an answer of a [`CreatePoll`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/poll#poll-answer-object)
+#[derive(Clone, Debug, Serialize)]
+#[must_use]
+pub struct CreatePollAnswer {
+    poll_media: CreatePollMedia,
+}
+
+impl CreatePollAnswer {
+    /// Creates an answer with the given text.
+    ///
+    /// **Note**: The text must be at most 55 characters.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            poll_media: CreatePollMedia {
+                text: Some(text.into()),
+                emoji: None,
+            },
+        }
+    }
+
+    /// Sets the emoji shown next to the answer.
+    pub fn emoji(mut self, emoji: impl Into<ReactionType>) -> Self {
+        self.poll_media.emoji = Some(match emoji.into() {
+            ReactionType::Custom {
+                id, ..
+            } => CreatePollEmoji::Custom {
+                id,
+            },
+            ReactionType::Unicode(name) => CreatePollEmoji::Unicode {
+                name,
+            },
+        });
+        self
+    }
+}
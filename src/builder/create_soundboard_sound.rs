@@ -0,0 +1,128 @@
+#[cfg(feature = "http")]
+use super::Builder;
+use super::CreateAttachment;
+#[cfg(feature = "http")]
+use crate::http::CacheHttp;
+#[cfg(feature = "http")]
+use crate::internal::prelude::*;
+use crate::model::prelude::*;
+
+/// A builder to create a guild soundboard sound.
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/soundboard#create-guild-soundboard-sound)
+#[derive(Clone, Debug, Serialize)]
+#[must_use]
+pub struct CreateSoundboardSound<'a> {
+    name: String,
+    sound: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    volume: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    emoji_id: Option<EmojiId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    emoji_name: Option<String>,
+
+    #[serde(skip)]
+    audit_log_reason: Option<&'a str>,
+}
+
+impl<'a> CreateSoundboardSound<'a> {
+    /// Creates a new builder with the given name and sound file.
+    ///
+    /// **Note**: The name must be between 2 and 32 characters long. The sound must be an MP3 or
+    /// OGG file, of at most 512 KB and 5.2 seconds.
+    pub fn new(name: impl Into<String>, sound: &CreateAttachment) -> Self {
+        Self {
+            name: name.into(),
+            sound: sound_data_uri(sound),
+            volume: None,
+            emoji_id: None,
+            emoji_name: None,
+            audit_log_reason: None,
+        }
+    }
+
+    /// Set the name of the sound, replacing the current value as set in [`Self::new`].
+    ///
+    /// **Note**: Must be between 2 and 32 characters long.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Set the sound file, replacing the current value as set in [`Self::new`].
+    pub fn sound(mut self, sound: &CreateAttachment) -> Self {
+        self.sound = sound_data_uri(sound);
+        self
+    }
+
+    /// Set the volume of the sound, from 0 to 1. Defaults to 1.
+    pub fn volume(mut self, volume: f64) -> Self {
+        self.volume = Some(volume);
+        self
+    }
+
+    /// Set the emoji of the sound.
+    pub fn emoji(mut self, emoji: impl Into<ReactionType>) -> Self {
+        (self.emoji_id, self.emoji_name) = split_emoji(emoji.into());
+        self
+    }
+
+    /// Sets the request's audit log reason.
+    pub fn audit_log_reason(mut self, reason: &'a str) -> Self {
+        self.audit_log_reason = Some(reason);
+        self
+    }
+}
+
+/// Encodes a sound file as a data URI, guessing its MIME type from the file extension.
+fn sound_data_uri(sound: &CreateAttachment) -> String {
+    use base64::Engine;
+
+    let mime =
+        if sound.filename.to_lowercase().ends_with(".mp3") { "audio/mpeg" } else { "audio/ogg" };
+    let data = base64::prelude::BASE64_STANDARD.encode(&sound.data);
+    format!("data:{mime};base64,{data}")
+}
+
+/// Splits an emoji into the `emoji_id` and `emoji_name` fields of a soundboard sound.
+pub(super) fn split_emoji(emoji: ReactionType) -> (Option<EmojiId>, Option<String>) {
+    match emoji {
+        ReactionType::Custom {
+            id, ..
+        } => (Some(id), None),
+        ReactionType::Unicode(name) => (None, Some(name)),
+    }
+}
+
+#[cfg(feature = "http")]
+#[async_trait::async_trait]
+impl<'a> Builder for CreateSoundboardSound<'a> {
+    type Context<'ctx> = GuildId;
+    type Built = SoundboardSound;
+
+    /// Creates a new soundboard sound in the guild with the data set, if any.
+    ///
+    /// **Note**: Requires the [Create Guild Expressions] permission.
+    ///
+    /// # Errors
+    ///
+    /// If the `cache` is enabled, returns a [`ModelError::InvalidPermissions`] if the current user
+    /// lacks permission. Otherwise returns [`Error::Http`], as well as if invalid data is given.
+    ///
+    /// [Create Guild Expressions]: Permissions::CREATE_GUILD_EXPRESSIONS
+    async fn execute(
+        self,
+        cache_http: impl CacheHttp,
+        ctx: Self::Context<'_>,
+    ) -> Result<Self::Built> {
+        #[cfg(feature = "cache")]
+        crate::utils::user_has_guild_perms(
+            &cache_http,
+            ctx,
+            Permissions::CREATE_GUILD_EXPRESSIONS,
+        )?;
+
+        cache_http.http().create_guild_soundboard_sound(ctx, &self, self.audit_log_reason).await
+    }
+}
@@ -0,0 +1,443 @@
+#[cfg(feature = "http")]
+use super::Builder;
+#[cfg(feature = "http")]
+use crate::http::CacheHttp;
+#[cfg(feature = "http")]
+use crate::internal::prelude::*;
+use crate::model::prelude::*;
+
+/// A builder to edit the onboarding flow of a guild.
+///
+/// Discord replaces the whole list of prompts on every edit, so prompts which should be kept must
+/// be passed again. [`CreateOnboardingPrompt`] can be built from an existing [`OnboardingPrompt`]
+/// for that purpose.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use serenity::http::Http;
+/// # use serenity::model::prelude::*;
+/// use serenity::builder::{
+///     CreateOnboardingPrompt,
+///     CreateOnboardingPromptOption,
+///     EditGuildOnboarding,
+/// };
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// # let http: Http = unimplemented!();
+/// let guild_id = GuildId::new(1);
+/// let onboarding = guild_id.onboarding(&http).await?;
+///
+/// let mut prompts: Vec<_> = onboarding.prompts.into_iter().map(Into::into).collect();
+/// prompts.push(
+///     CreateOnboardingPrompt::new(OnboardingPromptType::MultipleChoice, "Pick your language")
+///         .add_option(
+///             CreateOnboardingPromptOption::new("Rust")
+///                 .add_role_id(RoleId::new(2))
+///                 .emoji('\u{1f980}'),
+///         ),
+/// );
+///
+/// let builder = EditGuildOnboarding::new().prompts(prompts).enabled(true);
+/// guild_id.edit_onboarding(&http, builder).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/guild#modify-guild-onboarding)
+#[derive(Clone, Debug, Default, Serialize)]
+#[must_use]
+pub struct EditGuildOnboarding<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prompts: Option<Vec<CreateOnboardingPrompt>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_channel_ids: Option<Vec<ChannelId>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mode: Option<OnboardingMode>,
+
+    #[serde(skip)]
+    audit_log_reason: Option<&'a str>,
+}
+
+impl<'a> EditGuildOnboarding<'a> {
+    /// Equivalent to [`Self::default`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the prompts shown during onboarding and in customize community, replacing the
+    /// current ones.
+    pub fn prompts(mut self, prompts: Vec<CreateOnboardingPrompt>) -> Self {
+        self.prompts = Some(prompts);
+        self
+    }
+
+    /// Sets the channels that members get opted into automatically.
+    pub fn default_channel_ids(mut self, channel_ids: Vec<ChannelId>) -> Self {
+        self.default_channel_ids = Some(channel_ids);
+        self
+    }
+
+    /// Whether onboarding is enabled in the guild.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = Some(enabled);
+        self
+    }
+
+    /// Sets the criteria used to check whether onboarding can be enabled.
+    pub fn mode(mut self, mode: OnboardingMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Sets the request's audit log reason.
+    pub fn audit_log_reason(mut self, reason: &'a str) -> Self {
+        self.audit_log_reason = Some(reason);
+        self
+    }
+}
+
+#[cfg(feature = "http")]
+#[async_trait::async_trait]
+impl<'a> Builder for EditGuildOnboarding<'a> {
+    type Context<'ctx> = GuildId;
+    type Built = Onboarding;
+
+    /// Edits the guild's onboarding flow.
+    ///
+    /// **Note**: Requires the [Manage Guild] and [Manage Roles] permissions.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the current user lacks permission, or if the new configuration
+    /// does not meet the requirements of onboarding.
+    ///
+    /// [Manage Guild]: Permissions::MANAGE_GUILD
+    /// [Manage Roles]: Permissions::MANAGE_ROLES
+    async fn execute(
+        self,
+        cache_http: impl CacheHttp,
+        ctx: Self::Context<'_>,
+    ) -> Result<Self::Built> {
+        cache_http.http().edit_guild_onboarding(ctx, &self, self.audit_log_reason).await
+    }
+}
+
+/// A builder for a prompt of an [`EditGuildOnboarding`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/guild#guild-onboarding-object-onboarding-prompt-structure)
+#[derive(Clone, Debug, Serialize)]
+#[must_use]
+pub struct CreateOnboardingPrompt {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<GenericId>,
+    #[serde(rename = "type")]
+    kind: OnboardingPromptType,
+    options: Vec<CreateOnboardingPromptOption>,
+    title: String,
+    single_select: bool,
+    required: bool,
+    in_onboarding: bool,
+}
+
+impl CreateOnboardingPrompt {
+    /// Creates a prompt of the given kind with a title and no option. The prompt is shown during
+    /// onboarding by default.
+    pub fn new(kind: OnboardingPromptType, title: impl Into<String>) -> Self {
+        Self {
+            id: None,
+            kind,
+            options: Vec::new(),
+            title: title.into(),
+            single_select: false,
+            required: false,
+            in_onboarding: true,
+        }
+    }
+
+    /// Sets the Id of the prompt. Setting this to the Id of an existing prompt updates that
+    /// prompt.
+    pub fn id(mut self, id: impl Into<GenericId>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Sets the options of the prompt, replacing the current ones.
+    pub fn options(mut self, options: Vec<CreateOnboardingPromptOption>) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Adds an option to the prompt.
+    pub fn add_option(mut self, option: CreateOnboardingPromptOption) -> Self {
+        self.options.push(option);
+        self
+    }
+
+    /// Whether users are limited to selecting a single option. Defaults to `false`.
+    pub fn single_select(mut self, single_select: bool) -> Self {
+        self.single_select = single_select;
+        self
+    }
+
+    /// Whether the prompt must be answered during onboarding. Defaults to `false`.
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Whether the prompt is shown during onboarding, or only in customize community. Defaults to
+    /// `true`.
+    pub fn in_onboarding(mut self, in_onboarding: bool) -> Self {
+        self.in_onboarding = in_onboarding;
+        self
+    }
+}
+
+impl From<OnboardingPrompt> for CreateOnboardingPrompt {
+    fn from(prompt: OnboardingPrompt) -> Self {
+        Self {
+            id: Some(prompt.id),
+            kind: prompt.kind,
+            options: prompt.options.into_iter().map(Into::into).collect(),
+            title: prompt.title,
+            single_select: prompt.single_select,
+            required: prompt.required,
+            in_onboarding: prompt.in_onboarding,
+        }
+    }
+}
+
+/// A builder for an option of a [`CreateOnboardingPrompt`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/guild#guild-onboarding-object-prompt-option-structure)
+#[derive(Clone, Debug, Serialize)]
+#[must_use]
+pub struct CreateOnboardingPromptOption {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<GenericId>,
+    channel_ids: Vec<ChannelId>,
+    role_ids: Vec<RoleId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    emoji_id: Option<EmojiId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    emoji_name: Option<String>,
+    emoji_animated: bool,
+    title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+}
+
+impl CreateOnboardingPromptOption {
+    /// Creates an option with the given title, which adds no channel or role.
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            id: None,
+            channel_ids: Vec::new(),
+            role_ids: Vec::new(),
+            emoji_id: None,
+            emoji_name: None,
+            emoji_animated: false,
+            title: title.into(),
+            description: None,
+        }
+    }
+
+    /// Sets the Id of the option. Setting this to the Id of an existing option updates that
+    /// option.
+    pub fn id(mut self, id: impl Into<GenericId>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Sets the channels a member is added to when selecting the option, replacing the current
+    /// ones.
+    pub fn channel_ids(mut self, channel_ids: Vec<ChannelId>) -> Self {
+        self.channel_ids = channel_ids;
+        self
+    }
+
+    /// Adds a channel a member is added to when selecting the option.
+    pub fn add_channel_id(mut self, channel_id: impl Into<ChannelId>) -> Self {
+        self.channel_ids.push(channel_id.into());
+        self
+    }
+
+    /// Sets the roles assigned to a member when selecting the option, replacing the current ones.
+    pub fn role_ids(mut self, role_ids: Vec<RoleId>) -> Self {
+        self.role_ids = role_ids;
+        self
+    }
+
+    /// Adds a role assigned to a member when selecting the option.
+    pub fn add_role_id(mut self, role_id: impl Into<RoleId>) -> Self {
+        self.role_ids.push(role_id.into());
+        self
+    }
+
+    /// Sets the emoji shown next to the option.
+    pub fn emoji(mut self, emoji: impl Into<ReactionType>) -> Self {
+        match emoji.into() {
+            ReactionType::Custom {
+                animated,
+                id,
+                name,
+            } => {
+                self.emoji_id = Some(id);
+                self.emoji_name = name;
+                self.emoji_animated = animated;
+            },
+            ReactionType::Unicode(name) => {
+                self.emoji_id = None;
+                self.emoji_name = Some(name);
+                self.emoji_animated = false;
+            },
+        }
+        self
+    }
+
+    /// Sets the description of the option.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+impl From<OnboardingPromptOption> for CreateOnboardingPromptOption {
+    fn from(option: OnboardingPromptOption) -> Self {
+        let mut builder = Self::new(option.title).id(option.id);
+        builder.channel_ids = option.channel_ids;
+        builder.role_ids = option.role_ids;
+        builder.description = option.description;
+        if let Some(emoji) = option.emoji {
+            builder = builder.emoji(emoji);
+        }
+        builder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::{from_value, json, to_value};
+
+    #[test]
+    fn edit_guild_onboarding() {
+        let builder = EditGuildOnboarding::new()
+            .prompts(vec![CreateOnboardingPrompt::new(OnboardingPromptType::Dropdown, "Language")
+                .required(true)
+                .add_option(
+                    CreateOnboardingPromptOption::new("Rust")
+                        .add_role_id(RoleId::new(2))
+                        .emoji('\u{1f980}'),
+                )
+                .add_option(
+                    CreateOnboardingPromptOption::new("Ferris")
+                        .add_channel_id(ChannelId::new(3))
+                        .emoji(ReactionType::Custom {
+                            animated: true,
+                            id: EmojiId::new(4),
+                            name: Some("ferris".into()),
+                        })
+                        .description("Crab"),
+                )])
+            .enabled(true)
+            .audit_log_reason("reason");
+
+        assert_eq!(
+            to_value(&builder).unwrap(),
+            json!({
+                "prompts": [{
+                    "type": 1,
+                    "options": [
+                        {
+                            "channel_ids": [],
+                            "role_ids": ["2"],
+                            "emoji_name": "\u{1f980}",
+                            "emoji_animated": false,
+                            "title": "Rust",
+                        },
+                        {
+                            "channel_ids": ["3"],
+                            "role_ids": [],
+                            "emoji_id": "4",
+                            "emoji_name": "ferris",
+                            "emoji_animated": true,
+                            "title": "Ferris",
+                            "description": "Crab",
+                        },
+                    ],
+                    "title": "Language",
+                    "single_select": false,
+                    "required": true,
+                    "in_onboarding": true,
+                }],
+                "enabled": true,
+            })
+        );
+    }
+
+    #[test]
+    fn prompt_from_onboarding_prompt() {
+        let prompt = json!({
+            "id": "1",
+            "type": 0,
+            "options": [
+                {
+                    "id": "2",
+                    "channel_ids": ["3"],
+                    "role_ids": [],
+                    "emoji": {"id": "4", "name": "chat", "animated": false},
+                    "title": "Chat",
+                    "description": "Talk with friends",
+                },
+                {
+                    "id": "5",
+                    "channel_ids": [],
+                    "role_ids": ["6"],
+                    "emoji": {"id": null, "name": null, "animated": false},
+                    "title": "Lurk",
+                    "description": null,
+                },
+            ],
+            "title": "What brings you here?",
+            "single_select": true,
+            "required": false,
+            "in_onboarding": false,
+        });
+        let builder = CreateOnboardingPrompt::from(from_value::<OnboardingPrompt>(prompt).unwrap());
+
+        assert_eq!(
+            to_value(&builder).unwrap(),
+            json!({
+                "id": "1",
+                "type": 0,
+                "options": [
+                    {
+                        "id": "2",
+                        "channel_ids": ["3"],
+                        "role_ids": [],
+                        "emoji_id": "4",
+                        "emoji_name": "chat",
+                        "emoji_animated": false,
+                        "title": "Chat",
+                        "description": "Talk with friends",
+                    },
+                    {
+                        "id": "5",
+                        "channel_ids": [],
+                        "role_ids": ["6"],
+                        "emoji_animated": false,
+                        "title": "Lurk",
+                    },
+                ],
+                "title": "What brings you here?",
+                "single_select": true,
+                "required": false,
+                "in_onboarding": false,
+            })
+        );
+    }
+}
@@ -51,7 +51,9 @@ mod create_interaction_response;
 mod create_interaction_response_followup;
 mod create_invite;
 mod create_message;
+mod create_poll;
 mod create_scheduled_event;
+mod create_soundboard_sound;
 mod create_stage_instance;
 mod create_sticker;
 mod create_thread;
@@ -59,6 +61,7 @@ mod create_webhook;
 mod edit_automod_rule;
 mod edit_channel;
 mod edit_guild;
+mod edit_guild_onboarding;
 mod edit_guild_welcome_screen;
 mod edit_guild_widget;
 mod edit_interaction_response;
@@ -67,6 +70,7 @@ mod edit_message;
 mod edit_profile;
 mod edit_role;
 mod edit_scheduled_event;
+mod edit_soundboard_sound;
 mod edit_stage_instance;
 mod edit_sticker;
 mod edit_thread;
@@ -91,7 +95,9 @@ pub use create_interaction_response::*;
 pub use create_interaction_response_followup::*;
 pub use create_invite::*;
 pub use create_message::*;
+pub use create_poll::*;
 pub use create_scheduled_event::*;
+pub use create_soundboard_sound::*;
 pub use create_stage_instance::*;
 pub use create_sticker::*;
 pub use create_thread::*;
@@ -99,6 +105,7 @@ pub use create_webhook::*;
 pub use edit_automod_rule::*;
 pub use edit_channel::*;
 pub use edit_guild::*;
+pub use edit_guild_onboarding::*;
 pub use edit_guild_welcome_screen::*;
 pub use edit_guild_widget::*;
 pub use edit_interaction_response::*;
@@ -107,6 +114,7 @@ pub use edit_message::*;
 pub use edit_profile::*;
 pub use edit_role::*;
 pub use edit_scheduled_event::*;
+pub use edit_soundboard_sound::*;
 pub use edit_stage_instance::*;
 pub use edit_sticker::*;
 pub use edit_thread::*;
@@ -0,0 +1,380 @@
+//! Storage backends for the maps held by the [`Cache`].
+//!
+//! [`Cache`]: super::Cache
+
+use std::fmt;
+use std::hash::Hash;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+use dashmap::mapref::multiple::RefMulti;
+use dashmap::mapref::one::{MappedRef, Ref, RefMut};
+use dashmap::DashMap;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::wrappers::BuildHasher;
+
+/// Creates the storage for each of the maps held by the [`Cache`], such as its guilds, users,
+/// channels and messages.
+///
+/// The cache only ever accesses its data through the [`CacheMap`]s returned by this trait, so
+/// event handling stays the same regardless of where the data is stored.
+///
+/// The default backend is [`DashMapBackend`], which keeps everything in memory.
+/// [`FileBackend`] stores the data on disk instead, so that it survives restarts and may be read
+/// by other processes.
+///
+/// # Examples
+///
+/// Create a cache storing its data in the `cache` directory:
+///
+/// ```rust,no_run
+/// use serenity::cache::{Cache, FileBackend, Settings};
+///
+/// # fn run() -> std::io::Result<()> {
+/// let backend = FileBackend::new("cache")?;
+/// let cache = Cache::new_with_backend(Settings::default(), &backend);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Cache`]: super::Cache
+/// [`FileBackend`]: super::FileBackend
+pub trait CacheBackend {
+    /// Creates the storage for the cache's map with the given name, such as `guilds` or `users`.
+    fn map<K: CacheKey, V: CacheValue>(&self, name: &'static str) -> Box<dyn CacheMap<K, V>>;
+}
+
+/// The storage for one of the maps held by the [`Cache`].
+///
+/// [`Cache`]: super::Cache
+pub trait CacheMap<K, V>: fmt::Debug + Send + Sync {
+    /// Retrieves the value for the given key.
+    fn get(&self, key: &K) -> Option<StorageRef<'_, K, V>>;
+
+    /// Retrieves the value for the given key, to be modified in place.
+    fn get_mut(&self, key: &K) -> Option<StorageRefMut<'_, K, V>>;
+
+    /// Retrieves the value for the given key to be modified in place, inserting the result of
+    /// `default` first if there is none.
+    fn get_mut_or_insert_with(&self, key: K, default: &dyn Fn() -> V) -> StorageRefMut<'_, K, V>;
+
+    /// Inserts a value for the given key, returning the previous value.
+    fn insert(&self, key: K, value: V) -> Option<V>;
+
+    /// Removes the value for the given key, returning it.
+    fn remove(&self, key: &K) -> Option<V>;
+
+    /// Returns the keys of all stored values.
+    fn keys(&self) -> Vec<K>;
+
+    /// Iterates over all stored values.
+    ///
+    /// By default, this looks up each of the [`Self::keys`] one after the other, skipping values
+    /// removed in the meantime. Backends able to iterate over their values directly should
+    /// override it.
+    fn iter(&self) -> Box<dyn Iterator<Item = StorageRef<'_, K, V>> + '_> {
+        Box::new(self.keys().into_iter().filter_map(|key| self.get(&key)))
+    }
+
+    /// Returns the number of stored values.
+    fn len(&self) -> usize;
+
+    /// Whether there are no stored values.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether there is a value for the given key.
+    fn contains(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Shrinks the capacity of the storage, if it is kept in memory.
+    fn shrink_to_fit(&self) {}
+
+    /// The memory used by the stored values, if they are kept in memory.
+    #[cfg(feature = "typesize")]
+    fn extra_size(&self) -> usize {
+        0
+    }
+}
+
+/// The requirements on the keys of the cache's maps, which are all Discord IDs.
+pub trait CacheKey:
+    Copy + Eq + Hash + fmt::Debug + fmt::Display + FromStr + Send + Sync + MaybeTypeSize + 'static
+{
+}
+
+impl<T> CacheKey for T where
+    T: Copy
+        + Eq
+        + Hash
+        + fmt::Debug
+        + fmt::Display
+        + FromStr
+        + Send
+        + Sync
+        + MaybeTypeSize
+        + 'static
+{
+}
+
+/// The requirements on the values of the cache's maps.
+pub trait CacheValue:
+    Clone + fmt::Debug + Serialize + DeserializeOwned + Send + Sync + MaybeTypeSize + 'static
+{
+}
+
+impl<T> CacheValue for T where
+    T: Clone + fmt::Debug + Serialize + DeserializeOwned + Send + Sync + MaybeTypeSize + 'static
+{
+}
+
+/// [`typesize::TypeSize`] if the `typesize` feature is enabled, or any type otherwise.
+#[cfg(feature = "typesize")]
+pub trait MaybeTypeSize: typesize::TypeSize {}
+#[cfg(feature = "typesize")]
+impl<T: typesize::TypeSize> MaybeTypeSize for T {}
+
+/// [`typesize::TypeSize`] if the `typesize` feature is enabled, or any type otherwise.
+#[cfg(not(feature = "typesize"))]
+pub trait MaybeTypeSize {}
+#[cfg(not(feature = "typesize"))]
+impl<T> MaybeTypeSize for T {}
+
+/// A reference to a value returned by a [`CacheMap`], along with its key.
+///
+/// Like the references returned by a [`DashMap`], this dereferences to the value, and gives
+/// access to the key through [`Self::key`].
+pub struct StorageRef<'a, K, V>(StorageRefInner<'a, K, V>);
+
+enum StorageRefInner<'a, K, V> {
+    Dash(Ref<'a, K, V, BuildHasher>),
+    DashMulti(RefMulti<'a, K, V, BuildHasher>),
+    Owned(K, V),
+}
+
+impl<'a, K: Eq + Hash, V> StorageRef<'a, K, V> {
+    /// Creates a reference to a value which was loaded from the storage.
+    pub fn owned(key: K, value: V) -> Self {
+        Self(StorageRefInner::Owned(key, value))
+    }
+
+    /// The key of the value.
+    #[must_use]
+    pub fn key(&self) -> &K {
+        match &self.0 {
+            StorageRefInner::Dash(inner) => inner.key(),
+            StorageRefInner::DashMulti(inner) => inner.key(),
+            StorageRefInner::Owned(key, _) => key,
+        }
+    }
+
+    /// The value itself.
+    #[must_use]
+    pub fn value(&self) -> &V {
+        match &self.0 {
+            StorageRefInner::Dash(inner) => inner.value(),
+            StorageRefInner::DashMulti(inner) => inner.value(),
+            StorageRefInner::Owned(_, value) => value,
+        }
+    }
+
+    /// The key and the value.
+    #[must_use]
+    pub fn pair(&self) -> (&K, &V) {
+        (self.key(), self.value())
+    }
+
+    pub(crate) fn map<T: Clone>(self, f: impl FnOnce(&V) -> &T) -> MappedStorageRef<'a, K, V, T> {
+        match self.0 {
+            StorageRefInner::Dash(inner) => MappedStorageRef::Dash(inner.map(f)),
+            StorageRefInner::DashMulti(inner) => MappedStorageRef::Owned(f(inner.value()).clone()),
+            StorageRefInner::Owned(_, value) => MappedStorageRef::Owned(f(&value).clone()),
+        }
+    }
+
+    pub(crate) fn try_map<T: Clone>(
+        self,
+        f: impl FnOnce(&V) -> Option<&T>,
+    ) -> Result<MappedStorageRef<'a, K, V, T>, Self> {
+        match self.0 {
+            StorageRefInner::Dash(inner) => {
+                inner.try_map(f).map(MappedStorageRef::Dash).map_err(Self::from)
+            },
+            StorageRefInner::DashMulti(inner) => match f(inner.value()).cloned() {
+                Some(mapped) => Ok(MappedStorageRef::Owned(mapped)),
+                None => Err(Self::from(inner)),
+            },
+            StorageRefInner::Owned(key, value) => match f(&value).cloned() {
+                Some(mapped) => Ok(MappedStorageRef::Owned(mapped)),
+                None => Err(Self::owned(key, value)),
+            },
+        }
+    }
+}
+
+impl<'a, K, V> From<Ref<'a, K, V, BuildHasher>> for StorageRef<'a, K, V> {
+    fn from(inner: Ref<'a, K, V, BuildHasher>) -> Self {
+        Self(StorageRefInner::Dash(inner))
+    }
+}
+
+impl<'a, K, V> From<RefMulti<'a, K, V, BuildHasher>> for StorageRef<'a, K, V> {
+    fn from(inner: RefMulti<'a, K, V, BuildHasher>) -> Self {
+        Self(StorageRefInner::DashMulti(inner))
+    }
+}
+
+impl<K: Eq + Hash, V> Deref for StorageRef<'_, K, V> {
+    type Target = V;
+
+    fn deref(&self) -> &Self::Target {
+        self.value()
+    }
+}
+
+impl<K: Eq + Hash, V: Serialize> Serialize for StorageRef<'_, K, V> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        V::serialize(self, serializer)
+    }
+}
+
+/// A reference to a part of a value returned by a [`CacheMap`].
+pub(crate) enum MappedStorageRef<'a, K, V, T> {
+    Dash(MappedRef<'a, K, V, T, BuildHasher>),
+    Owned(T),
+}
+
+impl<K: Eq + Hash, V, T> Deref for MappedStorageRef<'_, K, V, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Dash(inner) => inner.value(),
+            Self::Owned(value) => value,
+        }
+    }
+}
+
+/// A mutable reference to a value returned by a [`CacheMap`].
+pub struct StorageRefMut<'a, K, V>(StorageRefMutInner<'a, K, V>);
+
+enum StorageRefMutInner<'a, K, V> {
+    Dash(RefMut<'a, K, V, BuildHasher>),
+    WriteBack { value: Option<V>, commit: Option<Box<dyn FnOnce(V) + 'a>> },
+}
+
+impl<'a, K, V> StorageRefMut<'a, K, V> {
+    /// Creates a reference to a value which was loaded from the storage, calling `commit` with
+    /// the modified value to store it once the reference is dropped.
+    pub fn write_back(value: V, commit: impl FnOnce(V) + 'a) -> Self {
+        Self(StorageRefMutInner::WriteBack {
+            value: Some(value),
+            commit: Some(Box::new(commit)),
+        })
+    }
+}
+
+impl<'a, K, V> From<RefMut<'a, K, V, BuildHasher>> for StorageRefMut<'a, K, V> {
+    fn from(inner: RefMut<'a, K, V, BuildHasher>) -> Self {
+        Self(StorageRefMutInner::Dash(inner))
+    }
+}
+
+impl<K: Eq + Hash, V> Deref for StorageRefMut<'_, K, V> {
+    type Target = V;
+
+    fn deref(&self) -> &Self::Target {
+        match &self.0 {
+            StorageRefMutInner::Dash(inner) => inner.value(),
+            StorageRefMutInner::WriteBack {
+                value, ..
+            } => value.as_ref().expect("value is only taken on drop"),
+        }
+    }
+}
+
+impl<K: Eq + Hash, V> DerefMut for StorageRefMut<'_, K, V> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match &mut self.0 {
+            StorageRefMutInner::Dash(inner) => inner.value_mut(),
+            StorageRefMutInner::WriteBack {
+                value, ..
+            } => value.as_mut().expect("value is only taken on drop"),
+        }
+    }
+}
+
+impl<K, V> Drop for StorageRefMut<'_, K, V> {
+    fn drop(&mut self) {
+        if let StorageRefMutInner::WriteBack {
+            value,
+            commit,
+        } = &mut self.0
+        {
+            if let (Some(value), Some(commit)) = (value.take(), commit.take()) {
+                commit(value);
+            }
+        }
+    }
+}
+
+/// The default [`CacheBackend`], keeping all data in memory in [`DashMap`]s.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DashMapBackend;
+
+impl CacheBackend for DashMapBackend {
+    fn map<K: CacheKey, V: CacheValue>(&self, _: &'static str) -> Box<dyn CacheMap<K, V>> {
+        Box::new(DashMap::<K, V, BuildHasher>::default())
+    }
+}
+
+impl<K: CacheKey, V: CacheValue> CacheMap<K, V> for DashMap<K, V, BuildHasher> {
+    fn get(&self, key: &K) -> Option<StorageRef<'_, K, V>> {
+        DashMap::get(self, key).map(StorageRef::from)
+    }
+
+    fn get_mut(&self, key: &K) -> Option<StorageRefMut<'_, K, V>> {
+        DashMap::get_mut(self, key).map(StorageRefMut::from)
+    }
+
+    fn get_mut_or_insert_with(&self, key: K, default: &dyn Fn() -> V) -> StorageRefMut<'_, K, V> {
+        self.entry(key).or_insert_with(default).into()
+    }
+
+    fn insert(&self, key: K, value: V) -> Option<V> {
+        DashMap::insert(self, key, value)
+    }
+
+    fn remove(&self, key: &K) -> Option<V> {
+        DashMap::remove(self, key).map(|(_, value)| value)
+    }
+
+    fn keys(&self) -> Vec<K> {
+        DashMap::iter(self).map(|entry| *entry.key()).collect()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = StorageRef<'_, K, V>> + '_> {
+        Box::new(DashMap::iter(self).map(StorageRef::from))
+    }
+
+    fn len(&self) -> usize {
+        DashMap::len(self)
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        self.contains_key(key)
+    }
+
+    fn shrink_to_fit(&self) {
+        DashMap::shrink_to_fit(self);
+    }
+
+    #[cfg(feature = "typesize")]
+    fn extra_size(&self) -> usize {
+        typesize::TypeSize::extra_size(self)
+    }
+}
@@ -0,0 +1,479 @@
+//! Typed before/after diffs of the data updated in the [`Cache`].
+//!
+//! [`Cache`]: super::Cache
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::model::prelude::*;
+
+/// Compares two versions of a value, field by field.
+trait Diff<T> {
+    fn new(old: &T, new: &T) -> Self;
+    fn is_empty(&self) -> bool;
+}
+
+macro_rules! diff {
+    ( $(
+        $( #[doc = $doc:literal] )*
+        $name:ident: $model:ty { $( $field:ident: $ty:ty ),* $(,)? }
+    )* ) => { $(
+        $( #[doc = $doc] )*
+        ///
+        /// Each field holds the old and new value, if it changed.
+        #[derive(Clone, Debug, Default)]
+        #[non_exhaustive]
+        pub struct $name {
+            $( pub $field: Option<($ty, $ty)>, )*
+        }
+
+        impl $name {
+            #[doc = concat!("Compares two versions of a [`", stringify!($model), "`].")]
+            #[must_use]
+            #[allow(clippy::float_cmp)] // Any change to a float field is reported.
+            pub fn new(old: &$model, new: &$model) -> Self {
+                Self {
+                    $( $field: (old.$field != new.$field)
+                        .then(|| (old.$field.clone(), new.$field.clone())), )*
+                }
+            }
+
+            /// Whether none of the compared fields changed.
+            #[must_use]
+            pub fn is_empty(&self) -> bool {
+                true $( && self.$field.is_none() )*
+            }
+        }
+
+        impl Diff<$model> for $name {
+            fn new(old: &$model, new: &$model) -> Self {
+                Self::new(old, new)
+            }
+
+            fn is_empty(&self) -> bool {
+                self.is_empty()
+            }
+        }
+    )* };
+}
+
+diff! {
+    /// The changes to a [`Guild`]'s settings.
+    GuildDiff: Guild {
+        name: String,
+        icon: Option<ImageHash>,
+        banner: Option<String>,
+        splash: Option<ImageHash>,
+        discovery_splash: Option<ImageHash>,
+        owner_id: UserId,
+        features: Vec<String>,
+        vanity_url_code: Option<String>,
+        verification_level: VerificationLevel,
+        default_message_notifications: DefaultMessageNotificationLevel,
+        mfa_level: MfaLevel,
+        nsfw_level: NsfwLevel,
+        premium_tier: PremiumTier,
+        premium_subscription_count: Option<u64>,
+        system_channel_id: Option<ChannelId>,
+        system_channel_flags: SystemChannelFlags,
+        rules_channel_id: Option<ChannelId>,
+        public_updates_channel_id: Option<ChannelId>,
+        widget_enabled: Option<bool>,
+        widget_channel_id: Option<ChannelId>,
+        max_members: Option<u64>,
+        max_presences: Option<u64>,
+        max_video_channel_users: Option<u64>,
+    }
+
+    /// The changes to a [`GuildChannel`], which may be a thread.
+    ChannelDiff: GuildChannel {
+        name: String,
+        kind: ChannelType,
+        position: u16,
+        topic: Option<String>,
+        nsfw: bool,
+        parent_id: Option<ChannelId>,
+        permission_overwrites: Vec<PermissionOverwrite>,
+        rate_limit_per_user: Option<u16>,
+        bitrate: Option<u32>,
+        user_limit: Option<u32>,
+        rtc_region: Option<String>,
+        video_quality_mode: Option<VideoQualityMode>,
+        default_auto_archive_duration: Option<AutoArchiveDuration>,
+        flags: ChannelFlags,
+        applied_tags: Vec<ForumTagId>,
+        default_thread_rate_limit_per_user: Option<u16>,
+        status: Option<String>,
+        default_sort_order: Option<SortOrder>,
+        default_forum_layout: Option<ForumLayoutType>,
+    }
+
+    /// The changes to a [`Role`].
+    RoleDiff: Role {
+        name: String,
+        colour: Colour,
+        hoist: bool,
+        mentionable: bool,
+        permissions: Permissions,
+        position: u16,
+        icon: Option<ImageHash>,
+        unicode_emoji: Option<String>,
+    }
+
+    /// The changes to a [`Member`].
+    MemberDiff: Member {
+        nick: Option<String>,
+        avatar: Option<ImageHash>,
+        roles: Vec<RoleId>,
+        premium_since: Option<Timestamp>,
+        deaf: bool,
+        mute: bool,
+        flags: GuildMemberFlags,
+        pending: bool,
+        communication_disabled_until: Option<Timestamp>,
+        unusual_dm_activity_until: Option<Timestamp>,
+    }
+
+    /// The changes to an [`Emoji`].
+    EmojiDiff: Emoji {
+        name: String,
+        animated: bool,
+        available: bool,
+        roles: Vec<RoleId>,
+    }
+
+    /// The changes to a [`Sticker`].
+    StickerDiff: Sticker {
+        name: String,
+        description: Option<String>,
+        tags: Vec<String>,
+        available: bool,
+    }
+
+    /// The changes to a [`VoiceState`].
+    VoiceStateDiff: VoiceState {
+        channel_id: Option<ChannelId>,
+        deaf: bool,
+        mute: bool,
+        self_deaf: bool,
+        self_mute: bool,
+        self_stream: Option<bool>,
+        self_video: bool,
+        suppress: bool,
+        request_to_speak_timestamp: Option<Timestamp>,
+    }
+
+    /// The changes to a [`Presence`].
+    PresenceDiff: Presence {
+        status: OnlineStatus,
+        client_status: Option<ClientStatus>,
+    }
+
+    /// The changes to a [`Message`].
+    MessageDiff: Message {
+        content: String,
+        edited_timestamp: Option<Timestamp>,
+        mention_everyone: bool,
+        mention_roles: Vec<RoleId>,
+        pinned: bool,
+        flags: Option<MessageFlags>,
+    }
+
+    /// The changes to a [`ScheduledEvent`].
+    ScheduledEventDiff: ScheduledEvent {
+        name: String,
+        description: Option<String>,
+        channel_id: Option<ChannelId>,
+        start_time: Timestamp,
+        end_time: Option<Timestamp>,
+        privacy_level: ScheduledEventPrivacyLevel,
+        status: ScheduledEventStatus,
+        image: Option<ImageHash>,
+    }
+
+    /// The changes to a [`StageInstance`].
+    StageInstanceDiff: StageInstance {
+        topic: String,
+        privacy_level: StageInstancePrivacyLevel,
+        discoverable_disabled: bool,
+        guild_scheduled_event_id: Option<ScheduledEventId>,
+    }
+
+    /// The changes to a [`SoundboardSound`].
+    SoundboardSoundDiff: SoundboardSound {
+        name: String,
+        volume: f64,
+        emoji_id: Option<EmojiId>,
+        emoji_name: Option<String>,
+        available: bool,
+    }
+}
+
+/// The changes to a collection of values, such as the emojis of a guild.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct CollectionDiff<K, T, D> {
+    /// The values which were added.
+    pub added: Vec<T>,
+    /// The values which were removed.
+    pub removed: Vec<T>,
+    /// The changes to the values which were updated.
+    pub updated: HashMap<K, D>,
+}
+
+/// The changes to the emojis of a guild.
+pub type EmojisDiff = CollectionDiff<EmojiId, Emoji, EmojiDiff>;
+/// The changes to the stickers of a guild.
+pub type StickersDiff = CollectionDiff<StickerId, Sticker, StickerDiff>;
+
+impl<K, T, D> CollectionDiff<K, T, D> {
+    /// Whether nothing was added, removed or updated.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.updated.is_empty()
+    }
+}
+
+fn collection_diff<K: Copy + Eq + Hash, T: Clone, D: Diff<T>>(
+    old: &HashMap<K, T>,
+    new: &HashMap<K, T>,
+) -> CollectionDiff<K, T, D> {
+    let added = new.iter().filter(|(k, _)| !old.contains_key(k)).map(|(_, v)| v.clone());
+    let removed = old.iter().filter(|(k, _)| !new.contains_key(k)).map(|(_, v)| v.clone());
+    let updated = new.iter().filter_map(|(k, new)| {
+        let diff = D::new(old.get(k)?, new);
+        (!diff.is_empty()).then_some((*k, diff))
+    });
+
+    CollectionDiff {
+        added: added.collect(),
+        removed: removed.collect(),
+        updated: updated.collect(),
+    }
+}
+
+/// A change applied to the [`Cache`] by an update, delivered through [`EventHandler::cache_diff`].
+///
+/// Diffs are only produced if the old data was cached, and some of the compared fields changed.
+///
+/// [`Cache`]: super::Cache
+/// [`EventHandler::cache_diff`]: crate::client::EventHandler::cache_diff
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum CacheDiff {
+    Guild { guild_id: GuildId, diff: Box<GuildDiff> },
+    Channel { guild_id: GuildId, channel_id: ChannelId, diff: ChannelDiff },
+    Thread { guild_id: GuildId, thread_id: ChannelId, diff: ChannelDiff },
+    Role { guild_id: GuildId, role_id: RoleId, diff: RoleDiff },
+    Member { guild_id: GuildId, user_id: UserId, diff: MemberDiff },
+    Emojis { guild_id: GuildId, diff: EmojisDiff },
+    Stickers { guild_id: GuildId, diff: StickersDiff },
+    VoiceState { guild_id: Option<GuildId>, user_id: UserId, diff: VoiceStateDiff },
+    Presence { guild_id: Option<GuildId>, user_id: UserId, diff: PresenceDiff },
+    Message { channel_id: ChannelId, message_id: MessageId, diff: MessageDiff },
+    ScheduledEvent { guild_id: GuildId, event_id: ScheduledEventId, diff: ScheduledEventDiff },
+    StageInstance { guild_id: GuildId, stage_instance_id: StageInstanceId, diff: StageInstanceDiff },
+    SoundboardSound { guild_id: GuildId, sound_id: SoundId, diff: SoundboardSoundDiff },
+    SoundboardSounds { guild_id: GuildId, diff: HashMap<SoundId, SoundboardSoundDiff> },
+}
+
+/// Builds the diff variant from the old and new value, if the old value was cached and anything
+/// changed.
+macro_rules! diff_variant {
+    ($variant:ident { $( $id:ident: $id_value:expr ),* } $diff:ident($old:expr, $new:expr)) => {{
+        let diff = $diff::new($old?, $new);
+        (!diff.is_empty()).then(|| Self::$variant {
+            $( $id: $id_value, )*
+            diff: diff.into(),
+        })
+    }};
+}
+
+impl CacheDiff {
+    pub(crate) fn guild(old: Option<&Guild>, new: &Guild) -> Option<Self> {
+        diff_variant!(Guild { guild_id: new.id } GuildDiff(old, new))
+    }
+
+    pub(crate) fn channel(old: Option<&GuildChannel>, new: &GuildChannel) -> Option<Self> {
+        diff_variant!(Channel { guild_id: new.guild_id, channel_id: new.id } ChannelDiff(old, new))
+    }
+
+    pub(crate) fn thread(old: Option<&GuildChannel>, new: &GuildChannel) -> Option<Self> {
+        diff_variant!(Thread { guild_id: new.guild_id, thread_id: new.id } ChannelDiff(old, new))
+    }
+
+    pub(crate) fn role(old: Option<&Role>, new: &Role) -> Option<Self> {
+        diff_variant!(Role { guild_id: new.guild_id, role_id: new.id } RoleDiff(old, new))
+    }
+
+    pub(crate) fn member(old: Option<&Member>, new: &Member) -> Option<Self> {
+        diff_variant!(Member { guild_id: new.guild_id, user_id: new.user.id } MemberDiff(old, new))
+    }
+
+    pub(crate) fn emojis(
+        guild_id: GuildId,
+        old: Option<&HashMap<EmojiId, Emoji>>,
+        new: &HashMap<EmojiId, Emoji>,
+    ) -> Option<Self> {
+        let diff = collection_diff(old?, new);
+        (!diff.is_empty()).then_some(Self::Emojis {
+            guild_id,
+            diff,
+        })
+    }
+
+    pub(crate) fn stickers(
+        guild_id: GuildId,
+        old: Option<&HashMap<StickerId, Sticker>>,
+        new: &HashMap<StickerId, Sticker>,
+    ) -> Option<Self> {
+        let diff = collection_diff(old?, new);
+        (!diff.is_empty()).then_some(Self::Stickers {
+            guild_id,
+            diff,
+        })
+    }
+
+    pub(crate) fn voice_state(old: Option<&VoiceState>, new: &VoiceState) -> Option<Self> {
+        diff_variant!(VoiceState {
+            guild_id: new.guild_id,
+            user_id: new.user_id
+        } VoiceStateDiff(old, new))
+    }
+
+    pub(crate) fn presence(old: Option<&Presence>, new: &Presence) -> Option<Self> {
+        diff_variant!(Presence {
+            guild_id: new.guild_id,
+            user_id: new.user.id
+        } PresenceDiff(old, new))
+    }
+
+    pub(crate) fn message(old: Option<&Message>, new: &Message) -> Option<Self> {
+        diff_variant!(Message {
+            channel_id: new.channel_id,
+            message_id: new.id
+        } MessageDiff(old, new))
+    }
+
+    pub(crate) fn scheduled_event(
+        old: Option<&ScheduledEvent>,
+        new: &ScheduledEvent,
+    ) -> Option<Self> {
+        diff_variant!(ScheduledEvent {
+            guild_id: new.guild_id,
+            event_id: new.id
+        } ScheduledEventDiff(old, new))
+    }
+
+    pub(crate) fn stage_instance(old: Option<&StageInstance>, new: &StageInstance) -> Option<Self> {
+        diff_variant!(StageInstance {
+            guild_id: new.guild_id,
+            stage_instance_id: new.id
+        } StageInstanceDiff(old, new))
+    }
+
+    pub(crate) fn soundboard_sound(
+        old: Option<&SoundboardSound>,
+        new: &SoundboardSound,
+    ) -> Option<Self> {
+        let guild_id = new.guild_id?;
+        diff_variant!(SoundboardSound {
+            guild_id: guild_id,
+            sound_id: new.sound_id
+        } SoundboardSoundDiff(old, new))
+    }
+
+    pub(crate) fn soundboard_sounds(
+        guild_id: GuildId,
+        old: Option<&[SoundboardSound]>,
+        new: &HashMap<SoundId, SoundboardSound>,
+    ) -> Option<Self> {
+        let diff: HashMap<_, _> = old?
+            .iter()
+            .filter_map(|old| {
+                let diff = SoundboardSoundDiff::new(old, new.get(&old.sound_id)?);
+                (!diff.is_empty()).then_some((old.sound_id, diff))
+            })
+            .collect();
+
+        (!diff.is_empty()).then_some(Self::SoundboardSounds {
+            guild_id,
+            diff,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::{from_value, json};
+
+    #[test]
+    fn channel_diff() {
+        let old = GuildChannel {
+            name: "old".into(),
+            topic: Some("topic".into()),
+            ..Default::default()
+        };
+        let mut new = old.clone();
+        new.name = "new".into();
+
+        let diff = ChannelDiff::new(&old, &new);
+        assert_eq!(diff.name, Some(("old".into(), "new".into())));
+        assert_eq!(diff.topic, None);
+        assert!(ChannelDiff::new(&old, &old).is_empty());
+        assert!(CacheDiff::channel(None, &new).is_none());
+    }
+
+    #[test]
+    fn emojis_diff() {
+        let emoji = |id: u64, name: &str| -> Emoji {
+            from_value(json!({"id": id.to_string(), "name": name})).unwrap()
+        };
+        let map = |emojis: Vec<Emoji>| emojis.into_iter().map(|e| (e.id, e)).collect();
+        let old = map(vec![emoji(1, "removed"), emoji(2, "old"), emoji(3, "same")]);
+        let new = map(vec![emoji(2, "new"), emoji(3, "same"), emoji(4, "added")]);
+
+        let Some(CacheDiff::Emojis {
+            diff, ..
+        }) = CacheDiff::emojis(GuildId::new(1), Some(&old), &new)
+        else {
+            panic!("Expected an emojis diff");
+        };
+
+        assert_eq!(diff.added.iter().map(|e| e.id.get()).collect::<Vec<_>>(), [4]);
+        assert_eq!(diff.removed.iter().map(|e| e.id.get()).collect::<Vec<_>>(), [1]);
+        assert_eq!(diff.updated.len(), 1);
+        assert_eq!(diff.updated[&EmojiId::new(2)].name, Some(("old".into(), "new".into())));
+    }
+
+    #[test]
+    fn soundboard_sounds_diff() {
+        let sound = |id: u64, name: &str| -> SoundboardSound {
+            from_value(json!({
+                "sound_id": id.to_string(),
+                "name": name,
+                "volume": 1.0,
+                "emoji_id": null,
+                "emoji_name": null,
+                "guild_id": "1",
+                "available": true,
+            }))
+            .unwrap()
+        };
+        let old = [sound(1, "old"), sound(2, "same")];
+        let new =
+            [sound(1, "new"), sound(2, "same")].into_iter().map(|s| (s.sound_id, s)).collect();
+
+        let Some(CacheDiff::SoundboardSounds {
+            diff, ..
+        }) = CacheDiff::soundboard_sounds(GuildId::new(1), Some(&old), &new)
+        else {
+            panic!("Expected a soundboard sounds diff");
+        };
+
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff[&SoundId::new(1)].name, Some(("old".into(), "new".into())));
+        assert!(CacheDiff::soundboard_sound(Some(&old[1]), &new[&SoundId::new(2)]).is_none());
+    }
+}
@@ -1,7 +1,16 @@
-use std::collections::HashSet;
-
-use super::{Cache, CacheUpdate};
-use crate::model::channel::{GuildChannel, Message};
+use std::collections::{HashMap, HashSet};
+
+use super::index::{index_guild, index_member, unindex_guild};
+use super::messages::insert_message;
+use super::{Cache, CacheUpdate, MemberEviction};
+use crate::model::channel::{
+    GuildChannel,
+    Message,
+    MessageReaction,
+    Reaction,
+    ReactionType,
+    StageInstance,
+};
 use crate::model::event::{
     ChannelCreateEvent,
     ChannelDeleteEvent,
@@ -17,23 +26,45 @@ use crate::model::event::{
     GuildRoleCreateEvent,
     GuildRoleDeleteEvent,
     GuildRoleUpdateEvent,
+    GuildScheduledEventCreateEvent,
+    GuildScheduledEventDeleteEvent,
+    GuildScheduledEventUpdateEvent,
+    GuildScheduledEventUserAddEvent,
+    GuildScheduledEventUserRemoveEvent,
+    GuildSoundboardSoundCreateEvent,
+    GuildSoundboardSoundDeleteEvent,
+    GuildSoundboardSoundUpdateEvent,
+    GuildSoundboardSoundsUpdateEvent,
     GuildStickersUpdateEvent,
     GuildUpdateEvent,
     MessageCreateEvent,
+    MessageDeleteBulkEvent,
+    MessageDeleteEvent,
     MessageUpdateEvent,
     PresenceUpdateEvent,
     PresencesReplaceEvent,
+    ReactionAddEvent,
+    ReactionRemoveAllEvent,
+    ReactionRemoveEmojiEvent,
+    ReactionRemoveEvent,
     ReadyEvent,
+    StageInstanceCreateEvent,
+    StageInstanceDeleteEvent,
+    StageInstanceUpdateEvent,
     ThreadCreateEvent,
     ThreadDeleteEvent,
+    ThreadListSyncEvent,
+    ThreadMembersUpdateEvent,
     ThreadUpdateEvent,
     UserUpdateEvent,
     VoiceChannelStatusUpdateEvent,
     VoiceStateUpdateEvent,
 };
-use crate::model::gateway::ShardInfo;
-use crate::model::guild::{Guild, GuildMemberFlags, Member, Role};
-use crate::model::id::ShardId;
+use crate::model::gateway::{Presence, ShardInfo};
+use crate::model::guild::{Emoji, Guild, GuildMemberFlags, Member, Role, ScheduledEvent};
+use crate::model::id::{ChannelId, EmojiId, MessageId, ShardId, StickerId, UserId};
+use crate::model::soundboard::SoundboardSound;
+use crate::model::sticker::Sticker;
 use crate::model::user::{CurrentUser, OnlineStatus};
 use crate::model::voice::VoiceState;
 
@@ -58,10 +89,25 @@ impl CacheUpdate for ChannelDeleteEvent {
         let (channel_id, guild_id) = (self.channel.id, self.channel.guild_id);
 
         cache.channels.remove(&channel_id);
-        cache.guilds.get_mut(&guild_id).map(|mut g| g.channels.remove(&channel_id));
+        if let Some(mut guild) = cache.guilds.get_mut(&guild_id) {
+            guild.channels.remove(&channel_id);
+
+            // Threads are deleted along with their parent channel, without any ThreadDelete.
+            guild.threads.retain(|thread| {
+                let is_child = thread.parent_id == Some(channel_id);
+                if is_child {
+                    cache.threads.remove(&thread.id);
+                }
+                !is_child
+            });
+        }
 
         // Remove the cached messages for the channel.
-        cache.messages.remove(&channel_id).map(|(_, messages)| messages.into_values().collect())
+        let mut lru = cache.message_lru.lock();
+        cache.message_queue.remove(&channel_id);
+        let messages = cache.messages.remove(&channel_id)?;
+        lru.remove_channel(channel_id, messages.len());
+        Some(messages.into_values().collect())
     }
 }
 
@@ -100,6 +146,7 @@ impl CacheUpdate for GuildCreateEvent {
     fn update(&mut self, cache: &Cache) -> Option<()> {
         cache.unavailable_guilds.remove(&self.guild.id);
         let mut guild = self.guild.clone();
+        filter_guild(cache, &mut guild);
 
         for (user_id, member) in &mut guild.members {
             cache.update_user_entry(&member.user);
@@ -108,10 +155,18 @@ impl CacheUpdate for GuildCreateEvent {
             }
         }
 
+        if let Some(old) = cache.guilds.get(&self.guild.id) {
+            unindex_guild(cache, &old);
+        }
+        index_guild(cache, &guild);
+
         cache.guilds.insert(self.guild.id, guild);
         for channel_id in self.guild.channels.keys() {
             cache.channels.insert(*channel_id, self.guild.id);
         }
+        for thread in &self.guild.threads {
+            cache.threads.insert(thread.id, self.guild.id);
+        }
 
         None
     }
@@ -123,22 +178,34 @@ impl CacheUpdate for GuildDeleteEvent {
     fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
         if self.guild.unavailable {
             cache.unavailable_guilds.insert(self.guild.id, ());
-            cache.guilds.remove(&self.guild.id);
+            if let Some(guild) = cache.guilds.remove(&self.guild.id) {
+                unindex_guild(cache, &guild);
+            }
 
             return None;
         }
 
         match cache.guilds.remove(&self.guild.id) {
             Some(guild) => {
-                for channel_id in guild.1.channels.keys() {
+                unindex_guild(cache, &guild);
+
+                let mut lru = cache.message_lru.lock();
+                for channel_id in guild.channels.keys() {
                     // Remove the channel from the cache.
                     cache.channels.remove(channel_id);
 
                     // Remove the channel's cached messages.
-                    cache.messages.remove(channel_id);
+                    cache.message_queue.remove(channel_id);
+                    let removed = cache.messages.remove(channel_id).map_or(0, |m| m.len());
+                    lru.remove_channel(*channel_id, removed);
                 }
+                drop(lru);
 
-                Some(guild.1)
+                for thread in &guild.threads {
+                    cache.threads.remove(&thread.id);
+                }
+
+                Some(guild)
             },
             None => None,
         }
@@ -146,14 +213,16 @@ impl CacheUpdate for GuildDeleteEvent {
 }
 
 impl CacheUpdate for GuildEmojisUpdateEvent {
-    type Output = ();
+    /// The guild's previous emojis.
+    type Output = HashMap<EmojiId, Emoji>;
 
-    fn update(&mut self, cache: &Cache) -> Option<()> {
-        if let Some(mut guild) = cache.guilds.get_mut(&self.guild_id) {
-            guild.emojis.clone_from(&self.emojis);
+    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        if !cache.settings().cache_emojis {
+            return None;
         }
 
-        None
+        let mut guild = cache.guilds.get_mut(&self.guild_id)?;
+        Some(std::mem::replace(&mut guild.emojis, self.emojis.clone()))
     }
 }
 
@@ -169,7 +238,7 @@ impl CacheUpdate for GuildMemberAddEvent {
 
         if let Some(mut guild) = cache.guilds.get_mut(&self.member.guild_id) {
             guild.member_count += 1;
-            guild.members.insert(user_id, self.member.clone());
+            insert_member(cache, &mut guild, self.member.clone());
         }
 
         None
@@ -182,7 +251,10 @@ impl CacheUpdate for GuildMemberRemoveEvent {
     fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
         if let Some(mut guild) = cache.guilds.get_mut(&self.guild_id) {
             guild.member_count -= 1;
-            return guild.members.remove(&self.user.id);
+
+            let member = guild.members.remove(&self.user.id);
+            index_member(cache, self.guild_id, member.as_ref(), None);
+            return member;
         }
 
         None
@@ -195,50 +267,30 @@ impl CacheUpdate for GuildMemberUpdateEvent {
     fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
         cache.update_user_entry(&self.user);
 
-        if let Some(mut guild) = cache.guilds.get_mut(&self.guild_id) {
-            let item = if let Some(member) = guild.members.get_mut(&self.user.id) {
-                let item = Some(member.clone());
-
-                member.joined_at.clone_from(&Some(self.joined_at));
-                member.nick.clone_from(&self.nick);
-                member.roles.clone_from(&self.roles);
-                member.user.clone_from(&self.user);
-                member.pending.clone_from(&self.pending);
-                member.premium_since.clone_from(&self.premium_since);
-                member.deaf.clone_from(&self.deaf);
-                member.mute.clone_from(&self.mute);
-                member.avatar.clone_from(&self.avatar);
-                member.communication_disabled_until.clone_from(&self.communication_disabled_until);
-                member.unusual_dm_activity_until.clone_from(&self.unusual_dm_activity_until);
-
-                item
-            } else {
-                None
-            };
-
-            if item.is_none() {
-                guild.members.insert(self.user.id, Member {
-                    deaf: false,
-                    guild_id: self.guild_id,
-                    joined_at: Some(self.joined_at),
-                    mute: false,
-                    nick: self.nick.clone(),
-                    roles: self.roles.clone(),
-                    user: self.user.clone(),
-                    pending: self.pending,
-                    premium_since: self.premium_since,
-                    permissions: None,
-                    avatar: self.avatar,
-                    communication_disabled_until: self.communication_disabled_until,
-                    flags: GuildMemberFlags::default(),
-                    unusual_dm_activity_until: self.unusual_dm_activity_until,
-                });
-            }
-
-            item
-        } else {
-            None
-        }
+        let mut guild = cache.guilds.get_mut(&self.guild_id)?;
+        let item = guild.members.get(&self.user.id).cloned();
+
+        let mut member = item.clone().unwrap_or_else(|| Member {
+            guild_id: self.guild_id,
+            ..Default::default()
+        });
+        member.joined_at.clone_from(&Some(self.joined_at));
+        member.nick.clone_from(&self.nick);
+        member.roles.clone_from(&self.roles);
+        member.user.clone_from(&self.user);
+        member.pending.clone_from(&self.pending);
+        member.premium_since.clone_from(&self.premium_since);
+        member.deaf.clone_from(&self.deaf);
+        member.mute.clone_from(&self.mute);
+        member.avatar.clone_from(&self.avatar);
+        member.communication_disabled_until.clone_from(&self.communication_disabled_until);
+        member.unusual_dm_activity_until.clone_from(&self.unusual_dm_activity_until);
+
+        // The member may not pass the member filters anymore, for example if they lost their
+        // last role, in which case they're removed.
+        insert_member(cache, &mut guild, member);
+
+        item
     }
 }
 
@@ -251,13 +303,113 @@ impl CacheUpdate for GuildMembersChunkEvent {
         }
 
         if let Some(mut g) = cache.guilds.get_mut(&self.guild_id) {
-            g.members.extend(self.members.clone());
+            insert_members(cache, &mut g, self.members.values().cloned());
         }
 
         None
     }
 }
 
+/// Removes the data which should not be cached according to the cache's settings from a guild
+/// about to be cached.
+pub(super) fn filter_guild(cache: &Cache, guild: &mut Guild) {
+    let settings = cache.settings();
+    if !settings.cache_presences {
+        guild.presences.clear();
+    }
+    if !settings.cache_emojis {
+        guild.emojis.clear();
+    }
+    if !settings.cache_stickers {
+        guild.stickers.clear();
+    }
+    if !settings.cache_soundboard_sounds {
+        guild.soundboard_sounds.clear();
+    }
+
+    let current_user_id = cache.current_user().id;
+    guild.members.retain(|_, m| m.user.id == current_user_id || settings.caches_member(m));
+    if let Some(max) = settings.max_members_per_guild {
+        evict_members(cache, guild, max, settings.member_eviction, current_user_id);
+    }
+}
+
+fn insert_member(cache: &Cache, guild: &mut Guild, member: Member) {
+    insert_members(cache, guild, std::iter::once(member));
+}
+
+/// Caches the members of a guild which pass the member filters, respecting the limit of members
+/// per guild. Cached members which don't pass the filters anymore are removed.
+fn insert_members(cache: &Cache, guild: &mut Guild, members: impl IntoIterator<Item = Member>) {
+    let settings = cache.settings();
+    let current_user_id = cache.current_user().id;
+
+    for member in members {
+        let user_id = member.user.id;
+        let is_current_user = user_id == current_user_id;
+
+        if !is_current_user && !settings.caches_member(&member) {
+            let old = guild.members.remove(&user_id);
+            index_member(cache, guild.id, old.as_ref(), None);
+            continue;
+        }
+
+        let has_room = settings.member_eviction != MemberEviction::KeepExisting
+            || settings.max_members_per_guild.map_or(true, |max| guild.members.len() < max);
+        if has_room || is_current_user || guild.members.contains_key(&user_id) {
+            let old = guild.members.insert(user_id, member);
+            let new = guild.members.get(&user_id);
+            index_member(cache, guild.id, old.as_ref(), new);
+        }
+    }
+
+    if let Some(max) = settings.max_members_per_guild {
+        evict_members(cache, guild, max, settings.member_eviction, current_user_id);
+    }
+}
+
+/// Evicts members from a guild once it has more than `max` members, other than the current user.
+///
+/// Picking the members to evict goes through all of them, so a tenth of the limit is evicted at
+/// once rather than doing so on every insert.
+fn evict_members(
+    cache: &Cache,
+    guild: &mut Guild,
+    max: usize,
+    eviction: MemberEviction,
+    current_user_id: UserId,
+) {
+    if guild.members.len() <= max {
+        return;
+    }
+
+    let excess = guild.members.len() - (max - max / 10);
+    let candidates = guild
+        .members
+        .values()
+        .filter(|m| m.user.id != current_user_id)
+        .map(|m| (m.joined_at, m.user.id));
+
+    let evicted: Vec<_> = if eviction == MemberEviction::OldestJoined {
+        let mut candidates: Vec<_> = candidates.collect();
+        let excess = excess.min(candidates.len());
+        if excess == 0 {
+            return;
+        }
+
+        candidates.select_nth_unstable(excess - 1);
+        candidates.truncate(excess);
+        candidates
+    } else {
+        candidates.take(excess).collect()
+    };
+
+    for (_, user_id) in evicted {
+        let old = guild.members.remove(&user_id);
+        index_member(cache, guild.id, old.as_ref(), None);
+    }
+}
+
 impl CacheUpdate for GuildRoleCreateEvent {
     type Output = ();
 
@@ -293,18 +445,140 @@ impl CacheUpdate for GuildRoleUpdateEvent {
     }
 }
 
-impl CacheUpdate for GuildStickersUpdateEvent {
+impl CacheUpdate for GuildScheduledEventCreateEvent {
     type Output = ();
 
     fn update(&mut self, cache: &Cache) -> Option<()> {
-        if let Some(mut guild) = cache.guilds.get_mut(&self.guild_id) {
-            guild.stickers.clone_from(&self.stickers);
+        let mut guild = cache.guilds.get_mut(&self.event.guild_id)?;
+        upsert(&mut guild.scheduled_events, self.event.clone(), |e| e.id == self.event.id);
+
+        None
+    }
+}
+
+impl CacheUpdate for GuildScheduledEventDeleteEvent {
+    type Output = ScheduledEvent;
+
+    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        let mut guild = cache.guilds.get_mut(&self.event.guild_id)?;
+        let index = guild.scheduled_events.iter().position(|e| e.id == self.event.id)?;
+        Some(guild.scheduled_events.remove(index))
+    }
+}
+
+impl CacheUpdate for GuildScheduledEventUpdateEvent {
+    type Output = ScheduledEvent;
+
+    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        let mut guild = cache.guilds.get_mut(&self.event.guild_id)?;
+        upsert(&mut guild.scheduled_events, self.event.clone(), |e| e.id == self.event.id)
+    }
+}
+
+impl CacheUpdate for GuildScheduledEventUserAddEvent {
+    type Output = ();
+
+    fn update(&mut self, cache: &Cache) -> Option<()> {
+        let mut guild = cache.guilds.get_mut(&self.guild_id)?;
+        let event = guild.scheduled_events.iter_mut().find(|e| e.id == self.scheduled_event_id)?;
+
+        // The user count is only known if it was requested, or for events received afterwards.
+        if let Some(count) = &mut event.user_count {
+            *count += 1;
+        }
+
+        None
+    }
+}
+
+impl CacheUpdate for GuildScheduledEventUserRemoveEvent {
+    type Output = ();
+
+    fn update(&mut self, cache: &Cache) -> Option<()> {
+        let mut guild = cache.guilds.get_mut(&self.guild_id)?;
+        let event = guild.scheduled_events.iter_mut().find(|e| e.id == self.scheduled_event_id)?;
+
+        if let Some(count) = &mut event.user_count {
+            *count = count.saturating_sub(1);
         }
 
         None
     }
 }
 
+impl CacheUpdate for GuildSoundboardSoundCreateEvent {
+    type Output = ();
+
+    fn update(&mut self, cache: &Cache) -> Option<()> {
+        if !cache.settings().cache_soundboard_sounds {
+            return None;
+        }
+
+        let mut guild = cache.guilds.get_mut(&self.sound.guild_id?)?;
+        guild.soundboard_sounds.insert(self.sound.sound_id, self.sound.clone());
+
+        None
+    }
+}
+
+impl CacheUpdate for GuildSoundboardSoundDeleteEvent {
+    /// The deleted sound.
+    type Output = SoundboardSound;
+
+    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        let mut guild = cache.guilds.get_mut(&self.guild_id)?;
+        guild.soundboard_sounds.remove(&self.sound_id)
+    }
+}
+
+impl CacheUpdate for GuildSoundboardSoundUpdateEvent {
+    /// The sound's previous data.
+    type Output = SoundboardSound;
+
+    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        if !cache.settings().cache_soundboard_sounds {
+            return None;
+        }
+
+        let mut guild = cache.guilds.get_mut(&self.sound.guild_id?)?;
+        guild.soundboard_sounds.insert(self.sound.sound_id, self.sound.clone())
+    }
+}
+
+impl CacheUpdate for GuildSoundboardSoundsUpdateEvent {
+    /// The previous data of the updated sounds which were cached.
+    type Output = Vec<SoundboardSound>;
+
+    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        if !cache.settings().cache_soundboard_sounds {
+            return None;
+        }
+
+        let mut guild = cache.guilds.get_mut(&self.guild_id)?;
+        let old = self
+            .soundboard_sounds
+            .values()
+            .filter_map(|sound| guild.soundboard_sounds.insert(sound.sound_id, sound.clone()))
+            .collect();
+
+        Some(old)
+    }
+}
+
+impl CacheUpdate for GuildStickersUpdateEvent {
+    /// The guild's previous stickers.
+    type Output = HashMap<StickerId, Sticker>;
+
+    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        if !cache.settings().cache_stickers {
+            return None;
+        }
+
+        let mut guild = cache.guilds.get_mut(&self.guild_id)?;
+        Some(std::mem::replace(&mut guild.stickers, self.stickers.clone()))
+    }
+}
+
 impl CacheUpdate for GuildUpdateEvent {
     type Output = ();
 
@@ -363,28 +637,8 @@ impl CacheUpdate for MessageCreateEvent {
             }
         }
 
-        // Add the new message to the cache and remove the oldest cached message.
-        let max = cache.settings().max_messages;
-
-        if max == 0 {
-            return None;
-        }
-
-        let mut messages = cache.messages.entry(self.message.channel_id).or_default();
-        let mut queue = cache.message_queue.entry(self.message.channel_id).or_default();
-
-        let mut removed_msg = None;
-
-        if messages.len() == max {
-            if let Some(id) = queue.pop_front() {
-                removed_msg = messages.remove(&id);
-            }
-        }
-
-        queue.push_back(self.message.id);
-        messages.insert(self.message.id, self.message.clone());
-
-        removed_msg
+        // Add the new message to the cache and evict the oldest cached messages.
+        insert_message(cache, &self.message)
     }
 }
 
@@ -403,6 +657,38 @@ fn update_channel_last_message_id(message: &Message, channel: &mut GuildChannel,
     }
 }
 
+impl CacheUpdate for MessageDeleteBulkEvent {
+    /// The deleted messages which were cached.
+    type Output = Vec<Message>;
+
+    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        Some(self.ids.iter().filter_map(|id| remove_message(cache, self.channel_id, *id)).collect())
+    }
+}
+
+impl CacheUpdate for MessageDeleteEvent {
+    /// The deleted message, if it was cached.
+    type Output = Message;
+
+    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        remove_message(cache, self.channel_id, self.message_id)
+    }
+}
+
+fn remove_message(cache: &Cache, channel_id: ChannelId, message_id: MessageId) -> Option<Message> {
+    #[cfg(feature = "temp_cache")]
+    cache.temp_messages.invalidate(&message_id);
+
+    let mut lru = cache.message_lru.lock();
+    if let Some(mut queue) = cache.message_queue.get_mut(&channel_id) {
+        queue.retain(|id| *id != message_id);
+    }
+
+    let message = cache.messages.get_mut(&channel_id)?.remove(&message_id)?;
+    lru.remove_message();
+    Some(message)
+}
+
 impl CacheUpdate for MessageUpdateEvent {
     type Output = Message;
 
@@ -418,9 +704,10 @@ impl CacheUpdate for MessageUpdateEvent {
 }
 
 impl CacheUpdate for PresenceUpdateEvent {
-    type Output = ();
+    /// The member's previous presence in the guild.
+    type Output = Presence;
 
-    fn update(&mut self, cache: &Cache) -> Option<()> {
+    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
         if let Some(user) = self.presence.user.to_user() {
             cache.update_user_entry(&user);
         }
@@ -429,38 +716,41 @@ impl CacheUpdate for PresenceUpdateEvent {
             self.presence.user.update_with_user(&user);
         }
 
+        let mut old = None;
         if let Some(guild_id) = self.presence.guild_id {
             if let Some(mut guild) = cache.guilds.get_mut(&guild_id) {
                 // If the member went offline, remove them from the presence list.
                 if self.presence.status == OnlineStatus::Offline {
-                    guild.presences.remove(&self.presence.user.id);
-                } else {
-                    guild.presences.insert(self.presence.user.id, self.presence.clone());
+                    old = guild.presences.remove(&self.presence.user.id);
+                } else if cache.settings().cache_presences {
+                    old = guild.presences.insert(self.presence.user.id, self.presence.clone());
                 }
 
                 // Create a partial member instance out of the presence update data.
                 if let Some(user) = self.presence.user.to_user() {
-                    guild.members.entry(self.presence.user.id).or_insert_with(|| Member {
-                        deaf: false,
-                        guild_id,
-                        joined_at: None,
-                        mute: false,
-                        nick: None,
-                        user,
-                        roles: vec![],
-                        pending: false,
-                        premium_since: None,
-                        permissions: None,
-                        avatar: None,
-                        communication_disabled_until: None,
-                        flags: GuildMemberFlags::default(),
-                        unusual_dm_activity_until: None,
-                    });
+                    if !guild.members.contains_key(&user.id) {
+                        insert_member(cache, &mut guild, Member {
+                            deaf: false,
+                            guild_id,
+                            joined_at: None,
+                            mute: false,
+                            nick: None,
+                            user,
+                            roles: vec![],
+                            pending: false,
+                            premium_since: None,
+                            permissions: None,
+                            avatar: None,
+                            communication_disabled_until: None,
+                            flags: GuildMemberFlags::default(),
+                            unusual_dm_activity_until: None,
+                        });
+                    }
                 }
             }
         }
 
-        None
+        old
     }
 }
 
@@ -476,6 +766,108 @@ impl CacheUpdate for PresencesReplaceEvent {
     }
 }
 
+impl CacheUpdate for ReactionAddEvent {
+    type Output = ();
+
+    fn update(&mut self, cache: &Cache) -> Option<()> {
+        let is_current_user = is_current_user_reaction(&self.reaction, cache);
+
+        let mut messages = cache.messages.get_mut(&self.reaction.channel_id)?;
+        let message = messages.get_mut(&self.reaction.message_id)?;
+
+        let reaction = message
+            .reactions
+            .iter_mut()
+            .find(|r| same_emoji(&r.reaction_type, &self.reaction.emoji));
+        if let Some(reaction) = reaction {
+            reaction.count += 1;
+            reaction.me |= is_current_user;
+        } else {
+            message.reactions.push(MessageReaction {
+                count: 1,
+                me: is_current_user,
+                reaction_type: self.reaction.emoji.clone(),
+            });
+        }
+
+        None
+    }
+}
+
+impl CacheUpdate for ReactionRemoveEvent {
+    type Output = ();
+
+    fn update(&mut self, cache: &Cache) -> Option<()> {
+        let is_current_user = is_current_user_reaction(&self.reaction, cache);
+
+        let mut messages = cache.messages.get_mut(&self.reaction.channel_id)?;
+        let message = messages.get_mut(&self.reaction.message_id)?;
+
+        let index = message
+            .reactions
+            .iter()
+            .position(|r| same_emoji(&r.reaction_type, &self.reaction.emoji))?;
+        let reaction = &mut message.reactions[index];
+
+        reaction.count = reaction.count.saturating_sub(1);
+        if is_current_user {
+            reaction.me = false;
+        }
+
+        if reaction.count == 0 {
+            message.reactions.remove(index);
+        }
+
+        None
+    }
+}
+
+impl CacheUpdate for ReactionRemoveAllEvent {
+    type Output = ();
+
+    fn update(&mut self, cache: &Cache) -> Option<()> {
+        let mut messages = cache.messages.get_mut(&self.channel_id)?;
+        let message = messages.get_mut(&self.message_id)?;
+
+        message.reactions.clear();
+
+        None
+    }
+}
+
+impl CacheUpdate for ReactionRemoveEmojiEvent {
+    type Output = ();
+
+    fn update(&mut self, cache: &Cache) -> Option<()> {
+        let mut messages = cache.messages.get_mut(&self.reaction.channel_id)?;
+        let message = messages.get_mut(&self.reaction.message_id)?;
+
+        message.reactions.retain(|r| !same_emoji(&r.reaction_type, &self.reaction.emoji));
+
+        None
+    }
+}
+
+fn is_current_user_reaction(reaction: &Reaction, cache: &Cache) -> bool {
+    reaction.user_id.is_some_and(|id| id == cache.current_user().id)
+}
+
+/// Custom emojis are compared by ID only, as their name and animated flag may be missing from
+/// reaction events.
+fn same_emoji(a: &ReactionType, b: &ReactionType) -> bool {
+    match (a, b) {
+        (
+            ReactionType::Custom {
+                id: a, ..
+            },
+            ReactionType::Custom {
+                id: b, ..
+            },
+        ) => a == b,
+        (a, b) => a == b,
+    }
+}
+
 impl CacheUpdate for ReadyEvent {
     type Output = ();
 
@@ -483,7 +875,9 @@ impl CacheUpdate for ReadyEvent {
         let ready = self.ready.clone();
 
         for unavailable in ready.guilds {
-            cache.guilds.remove(&unavailable.id);
+            if let Some(guild) = cache.guilds.remove(&unavailable.id) {
+                unindex_guild(cache, &guild);
+            }
             cache.unavailable_guilds.insert(unavailable.id, ());
         }
 
@@ -493,18 +887,19 @@ impl CacheUpdate for ReadyEvent {
             self.ready.guilds.iter().map(|status| status.id).collect::<HashSet<_>>();
         let shard_data = self.ready.shard.unwrap_or_else(|| ShardInfo::new(ShardId(1), 1));
 
-        for guild_entry in cache.guilds.iter() {
-            let guild = guild_entry.key();
+        for guild in cache.guilds.keys() {
             // Only handle data for our shard.
-            if crate::utils::shard_id(*guild, shard_data.total) == shard_data.id.0
-                && !ready_guilds_hashset.contains(guild)
+            if crate::utils::shard_id(guild, shard_data.total) == shard_data.id.0
+                && !ready_guilds_hashset.contains(&guild)
             {
-                guilds_to_remove.push(*guild);
+                guilds_to_remove.push(guild);
             }
         }
         if !guilds_to_remove.is_empty() {
-            for guild in guilds_to_remove {
-                cache.guilds.remove(&guild);
+            for guild_id in guilds_to_remove {
+                if let Some(guild) = cache.guilds.remove(&guild_id) {
+                    unindex_guild(cache, &guild);
+                }
             }
         }
 
@@ -519,11 +914,45 @@ impl CacheUpdate for ReadyEvent {
     }
 }
 
+impl CacheUpdate for StageInstanceCreateEvent {
+    type Output = ();
+
+    fn update(&mut self, cache: &Cache) -> Option<()> {
+        let instance = &self.stage_instance;
+        let mut guild = cache.guilds.get_mut(&instance.guild_id)?;
+        upsert(&mut guild.stage_instances, instance.clone(), |i| i.id == instance.id);
+
+        None
+    }
+}
+
+impl CacheUpdate for StageInstanceDeleteEvent {
+    type Output = StageInstance;
+
+    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        let instance = &self.stage_instance;
+        let mut guild = cache.guilds.get_mut(&instance.guild_id)?;
+        let index = guild.stage_instances.iter().position(|i| i.id == instance.id)?;
+        Some(guild.stage_instances.remove(index))
+    }
+}
+
+impl CacheUpdate for StageInstanceUpdateEvent {
+    type Output = StageInstance;
+
+    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        let instance = &self.stage_instance;
+        let mut guild = cache.guilds.get_mut(&instance.guild_id)?;
+        upsert(&mut guild.stage_instances, instance.clone(), |i| i.id == instance.id)
+    }
+}
+
 impl CacheUpdate for ThreadCreateEvent {
     type Output = GuildChannel;
 
     fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
         let (guild_id, thread_id) = (self.thread.guild_id, self.thread.id);
+        cache.threads.insert(thread_id, guild_id);
 
         cache.guilds.get_mut(&guild_id).and_then(|mut g| {
             if let Some(i) = g.threads.iter().position(|e| e.id == thread_id) {
@@ -541,6 +970,7 @@ impl CacheUpdate for ThreadUpdateEvent {
 
     fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
         let (guild_id, thread_id) = (self.thread.guild_id, self.thread.id);
+        cache.threads.insert(thread_id, guild_id);
 
         cache.guilds.get_mut(&guild_id).and_then(|mut g| {
             if let Some(i) = g.threads.iter().position(|e| e.id == thread_id) {
@@ -558,6 +988,7 @@ impl CacheUpdate for ThreadDeleteEvent {
 
     fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
         let (guild_id, thread_id) = (self.thread.guild_id, self.thread.id);
+        cache.threads.remove(&thread_id);
 
         cache.guilds.get_mut(&guild_id).and_then(|mut g| {
             g.threads.iter().position(|e| e.id == thread_id).map(|i| g.threads.remove(i))
@@ -565,6 +996,60 @@ impl CacheUpdate for ThreadDeleteEvent {
     }
 }
 
+impl CacheUpdate for ThreadListSyncEvent {
+    type Output = ();
+
+    fn update(&mut self, cache: &Cache) -> Option<()> {
+        let mut guild = cache.guilds.get_mut(&self.guild_id)?;
+
+        // Threads of the synced channels which aren't listed anymore are no longer active.
+        guild.threads.retain(|thread| {
+            let is_synced = match &self.channel_ids {
+                Some(channel_ids) => thread.parent_id.is_some_and(|id| channel_ids.contains(&id)),
+                None => true,
+            };
+            if is_synced {
+                cache.threads.remove(&thread.id);
+            }
+            !is_synced
+        });
+
+        for thread in &self.threads {
+            let mut thread = thread.clone();
+            if let Some(member) = self.members.iter().find(|m| m.id == thread.id) {
+                thread.member = Some(member.inner.clone());
+            }
+
+            let thread_id = thread.id;
+            cache.threads.insert(thread_id, self.guild_id);
+            upsert(&mut guild.threads, thread, |t| t.id == thread_id);
+        }
+
+        None
+    }
+}
+
+impl CacheUpdate for ThreadMembersUpdateEvent {
+    type Output = ();
+
+    fn update(&mut self, cache: &Cache) -> Option<()> {
+        let current_user_id = cache.current_user().id;
+
+        let mut guild = cache.guilds.get_mut(&self.guild_id)?;
+        let thread = guild.threads.iter_mut().find(|t| t.id == self.id)?;
+
+        thread.member_count = Some(u8::try_from(self.member_count.max(0)).unwrap_or(u8::MAX));
+        if let Some(member) = self.added_members.iter().find(|m| m.user_id == current_user_id) {
+            thread.member = Some(member.inner.clone());
+        }
+        if self.removed_member_ids.contains(&current_user_id) {
+            thread.member = None;
+        }
+
+        None
+    }
+}
+
 impl CacheUpdate for UserUpdateEvent {
     type Output = CurrentUser;
 
@@ -581,7 +1066,7 @@ impl CacheUpdate for VoiceStateUpdateEvent {
         if let Some(guild_id) = self.voice_state.guild_id {
             if let Some(mut guild) = cache.guilds.get_mut(&guild_id) {
                 if let Some(member) = &self.voice_state.member {
-                    guild.members.insert(member.user.id, member.clone());
+                    insert_member(cache, &mut guild, member.clone());
                 }
 
                 if self.voice_state.channel_id.is_some() {
@@ -612,3 +1097,14 @@ impl CacheUpdate for VoiceChannelStatusUpdateEvent {
         old
     }
 }
+
+/// Replaces the item matching the predicate with the new item, returning the old item, or pushes
+/// the new item if none matched.
+fn upsert<T>(items: &mut Vec<T>, item: T, is_same: impl Fn(&T) -> bool) -> Option<T> {
+    if let Some(old) = items.iter_mut().find(|i| is_same(i)) {
+        Some(std::mem::replace(old, item))
+    } else {
+        items.push(item);
+        None
+    }
+}
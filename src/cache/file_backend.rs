@@ -0,0 +1,332 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use parking_lot::{Condvar, Mutex};
+use tracing::warn;
+
+use super::backend::{CacheBackend, CacheKey, CacheMap, CacheValue, StorageRef, StorageRefMut};
+use crate::json;
+
+/// A [`CacheBackend`] storing the cache's data in a directory, so that it survives restarts and
+/// may be read by other processes.
+///
+/// Each map is stored in its own subdirectory, such as `guilds` or `users`, with one JSON file
+/// per value, named after its key. For example, the guild with the ID 381880193251409931 is
+/// stored in `guilds/381880193251409931.json`. Files are replaced atomically when written, so
+/// other processes never read partially written values.
+///
+/// Values are read from disk every time they are accessed, so this backend is much slower than
+/// the default [`DashMapBackend`], and is best used with [`Settings`] caching only the data
+/// that's needed. Errors while reading or writing files are logged, and treated as if the value
+/// was missing.
+///
+/// **Note**: This is a blocking backend. Files are read and written synchronously on the thread
+/// accessing the cache, which is an async executor thread while events are dispatched, so this
+/// backend should only be used where blocking the executor during file system access is
+/// acceptable. It is never used unless passed to [`Cache::new_with_backend`].
+///
+/// Like with the [`DashMapBackend`], a value borrowed mutably is locked until the borrow is
+/// dropped, so borrowing the same value again from the same thread in the meantime deadlocks.
+/// Other values of the same map can be accessed freely.
+///
+/// [`DashMapBackend`]: super::DashMapBackend
+/// [`Cache::new_with_backend`]: super::Cache::new_with_backend
+/// [`Settings`]: super::Settings
+#[derive(Clone, Debug)]
+pub struct FileBackend {
+    path: PathBuf,
+}
+
+impl FileBackend {
+    /// Creates a backend storing the cache's data in the given directory, creating it if it
+    /// doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory could not be created.
+    pub fn new(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        fs::create_dir_all(&path)?;
+
+        Ok(Self {
+            path,
+        })
+    }
+
+    /// The directory in which the cache's data is stored.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl CacheBackend for FileBackend {
+    fn map<K: CacheKey, V: CacheValue>(&self, name: &'static str) -> Box<dyn CacheMap<K, V>> {
+        let dir = self.path.join(name);
+        if let Err(why) = fs::create_dir_all(&dir) {
+            warn!("Failed to create the cache directory {}: {why:?}", dir.display());
+        }
+
+        let map = FileMap {
+            dir,
+            locked: Mutex::default(),
+            unlocked: Condvar::new(),
+            len: AtomicUsize::new(0),
+            phantom: PhantomData,
+        };
+        map.len.store(map.keys().len(), Ordering::Relaxed);
+
+        Box::new(map)
+    }
+}
+
+#[derive(Debug)]
+struct FileMap<K, V> {
+    dir: PathBuf,
+    /// The keys of the values being written, or borrowed mutably, so that concurrent
+    /// modifications from this process are not lost.
+    locked: Mutex<HashSet<K>>,
+    /// Notified whenever a key is removed from `locked`.
+    unlocked: Condvar,
+    /// The number of stored values, counted once when the map is created, to avoid listing the
+    /// directory on every call to [`CacheMap::len`].
+    len: AtomicUsize,
+    phantom: PhantomData<fn() -> (K, V)>,
+}
+
+/// Unlocks a key of a [`FileMap`] when dropped.
+struct KeyGuard<'a, K: CacheKey, V> {
+    map: &'a FileMap<K, V>,
+    key: K,
+}
+
+impl<K: CacheKey, V> Drop for KeyGuard<'_, K, V> {
+    fn drop(&mut self) {
+        self.map.locked.lock().remove(&self.key);
+        self.map.unlocked.notify_all();
+    }
+}
+
+impl<K: CacheKey, V: CacheValue> FileMap<K, V> {
+    fn lock(&self, key: K) -> KeyGuard<'_, K, V> {
+        let mut locked = self.locked.lock();
+        while !locked.insert(key) {
+            self.unlocked.wait(&mut locked);
+        }
+
+        KeyGuard {
+            map: self,
+            key,
+        }
+    }
+
+    fn path(&self, key: &K) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+
+    fn read(&self, key: &K) -> Option<V> {
+        let path = self.path(key);
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(why) if why.kind() == ErrorKind::NotFound => return None,
+            Err(why) => {
+                warn!("Failed to read {}: {why:?}", path.display());
+                return None;
+            },
+        };
+
+        json::from_slice(&bytes)
+            .map_err(|why| warn!("Failed to deserialize {}: {why:?}", path.display()))
+            .ok()
+    }
+
+    /// Writes the value, keeping track of the number of stored values. The key must be locked.
+    fn write(&self, key: &K, value: &V) {
+        let path = self.path(key);
+        let tmp_path = path.with_extension("json.tmp");
+        let existed = path.exists();
+
+        let result = json::to_vec(value)
+            .map_err(io::Error::other)
+            .and_then(|bytes| fs::write(&tmp_path, bytes))
+            .and_then(|()| fs::rename(&tmp_path, &path));
+
+        match result {
+            Ok(()) if !existed => {
+                self.len.fetch_add(1, Ordering::Relaxed);
+            },
+            Ok(()) => {},
+            Err(why) => warn!("Failed to write {}: {why:?}", path.display()),
+        }
+    }
+
+    /// Deletes the value, keeping track of the number of stored values. The key must be locked.
+    fn delete(&self, key: &K) {
+        let path = self.path(key);
+        match fs::remove_file(&path) {
+            Ok(()) => {
+                self.len.fetch_sub(1, Ordering::Relaxed);
+            },
+            Err(why) if why.kind() != ErrorKind::NotFound => {
+                warn!("Failed to delete {}: {why:?}", path.display());
+            },
+            Err(_) => {},
+        }
+    }
+
+    fn write_back<'a>(&'a self, guard: KeyGuard<'a, K, V>, value: V) -> StorageRefMut<'a, K, V> {
+        StorageRefMut::write_back(value, move |value| {
+            self.write(&guard.key, &value);
+            drop(guard);
+        })
+    }
+}
+
+impl<K: CacheKey, V: CacheValue> CacheMap<K, V> for FileMap<K, V> {
+    fn get(&self, key: &K) -> Option<StorageRef<'_, K, V>> {
+        self.read(key).map(|value| StorageRef::owned(*key, value))
+    }
+
+    fn get_mut(&self, key: &K) -> Option<StorageRefMut<'_, K, V>> {
+        let guard = self.lock(*key);
+        let value = self.read(key)?;
+        Some(self.write_back(guard, value))
+    }
+
+    fn get_mut_or_insert_with(&self, key: K, default: &dyn Fn() -> V) -> StorageRefMut<'_, K, V> {
+        let guard = self.lock(key);
+        let value = self.read(&key).unwrap_or_else(default);
+        self.write_back(guard, value)
+    }
+
+    fn insert(&self, key: K, value: V) -> Option<V> {
+        let _guard = self.lock(key);
+
+        let old = self.read(&key);
+        self.write(&key, &value);
+        old
+    }
+
+    fn remove(&self, key: &K) -> Option<V> {
+        let _guard = self.lock(*key);
+
+        let old = self.read(key);
+        self.delete(key);
+        old
+    }
+
+    fn keys(&self) -> Vec<K> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(why) => {
+                warn!("Failed to list {}: {why:?}", self.dir.display());
+                return Vec::new();
+            },
+        };
+
+        entries
+            .filter_map(|entry| {
+                let name = entry.ok()?.file_name();
+                name.to_str()?.strip_suffix(".json")?.parse().ok()
+            })
+            .collect()
+    }
+
+    fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        self.path(key).exists()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{Cache, Settings};
+    use crate::model::prelude::*;
+
+    #[test]
+    fn cache_survives_restarts() {
+        let dir = std::env::temp_dir().join(format!("serenity-cache-{}", std::process::id()));
+        let backend = FileBackend::new(&dir).unwrap();
+        let settings = Settings {
+            max_messages: 10,
+            ..Default::default()
+        };
+
+        let guild_id = GuildId::new(1);
+        let channel = GuildChannel {
+            id: ChannelId::new(2),
+            guild_id,
+            name: "general".into(),
+            ..Default::default()
+        };
+
+        {
+            let cache = Cache::new_with_backend(settings.clone(), &backend);
+            cache.update(&mut GuildCreateEvent {
+                guild: Guild {
+                    id: guild_id,
+                    name: "guild".into(),
+                    channels: HashMap::from([(channel.id, channel.clone())]),
+                    ..Default::default()
+                },
+            });
+
+            let mut channel = channel.clone();
+            channel.name = "renamed".into();
+            cache.update(&mut ChannelUpdateEvent {
+                channel,
+            });
+            cache.update(&mut MessageCreateEvent {
+                message: Message {
+                    id: MessageId::new(3),
+                    channel_id: ChannelId::new(2),
+                    content: "hello".into(),
+                    ..Default::default()
+                },
+            });
+        }
+
+        let cache = Cache::new_with_backend(settings, &backend);
+        assert_eq!(cache.guilds(), [guild_id]);
+        assert_eq!(cache.guild(guild_id).unwrap().name, "guild");
+        assert_eq!(cache.guild(guild_id).unwrap().channels[&channel.id].name, "renamed");
+        assert_eq!(cache.guild_channel_count(), 1);
+        assert_eq!(cache.message(ChannelId::new(2), MessageId::new(3)).unwrap().content, "hello");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn values_are_locked_separately() {
+        let dir = std::env::temp_dir().join(format!("serenity-cache-locks-{}", std::process::id()));
+        let backend = FileBackend::new(&dir).unwrap();
+        let map = backend.map::<UserId, String>("users");
+
+        map.insert(UserId::new(1), "one".into());
+        {
+            let mut one = map.get_mut(&UserId::new(1)).unwrap();
+            one.push_str(" updated");
+
+            // Other values stay accessible while one is borrowed mutably.
+            map.insert(UserId::new(2), "two".into());
+            assert_eq!(*map.get_mut(&UserId::new(2)).unwrap(), "two");
+            assert_eq!(*map.get(&UserId::new(1)).unwrap(), "one");
+        }
+
+        assert_eq!(*map.get(&UserId::new(1)).unwrap(), "one updated");
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.remove(&UserId::new(2)).as_deref(), Some("two"));
+        assert_eq!(map.len(), 1);
+        assert_eq!(backend.map::<UserId, String>("users").len(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
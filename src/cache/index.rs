@@ -0,0 +1,284 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Cache, Settings};
+use crate::model::guild::{Guild, Member};
+use crate::model::id::{GuildId, RoleId, UserId};
+
+/// Reverse indexes over the cached members of a [`Guild`], maintained by the cache when enabled
+/// by [`Settings::index_member_names`] or [`Settings::index_member_roles`].
+///
+/// Every lookup checks that the members found still match, so a member modified without updating
+/// the index is never returned for a name or role it doesn't have.
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub(crate) struct MemberIndex {
+    /// The members by lowercased username.
+    usernames: Option<HashMap<String, HashSet<UserId>>>,
+    /// The members by lowercased nickname.
+    nicknames: Option<HashMap<String, HashSet<UserId>>>,
+    /// The members by role.
+    roles: Option<HashMap<RoleId, HashSet<UserId>>>,
+}
+
+impl MemberIndex {
+    fn new(settings: &Settings, members: &HashMap<UserId, Member>) -> Self {
+        let mut index = Self {
+            usernames: settings.index_member_names.then(HashMap::new),
+            nicknames: settings.index_member_names.then(HashMap::new),
+            roles: settings.index_member_roles.then(HashMap::new),
+        };
+        for member in members.values() {
+            index.insert(member);
+        }
+
+        index
+    }
+
+    fn insert(&mut self, member: &Member) {
+        let user_id = member.user.id;
+        if let Some(usernames) = &mut self.usernames {
+            usernames.entry(member.user.name.to_lowercase()).or_default().insert(user_id);
+        }
+        if let (Some(nicknames), Some(nick)) = (&mut self.nicknames, &member.nick) {
+            nicknames.entry(nick.to_lowercase()).or_default().insert(user_id);
+        }
+        if let Some(roles) = &mut self.roles {
+            for role_id in &member.roles {
+                roles.entry(*role_id).or_default().insert(user_id);
+            }
+        }
+    }
+
+    fn remove(&mut self, member: &Member) {
+        let user_id = member.user.id;
+        if let Some(usernames) = &mut self.usernames {
+            remove_from(usernames, &member.user.name.to_lowercase(), user_id);
+        }
+        if let (Some(nicknames), Some(nick)) = (&mut self.nicknames, &member.nick) {
+            remove_from(nicknames, &nick.to_lowercase(), user_id);
+        }
+        if let Some(roles) = &mut self.roles {
+            for role_id in &member.roles {
+                remove_from(roles, role_id, user_id);
+            }
+        }
+    }
+
+    /// The members whose username is `name`, ignoring case, or None if names aren't indexed.
+    pub(crate) fn members_with_username<'a>(
+        &'a self,
+        members: &'a HashMap<UserId, Member>,
+        name: &str,
+    ) -> Option<impl Iterator<Item = &'a Member> + 'a> {
+        let name = name.to_lowercase();
+        let user_ids = self.usernames.as_ref()?.get(&name);
+        Some(
+            lookup(members, user_ids).filter(move |member| member.user.name.to_lowercase() == name),
+        )
+    }
+
+    /// The members whose nickname is `name`, ignoring case, or None if names aren't indexed.
+    pub(crate) fn members_with_nickname<'a>(
+        &'a self,
+        members: &'a HashMap<UserId, Member>,
+        name: &str,
+    ) -> Option<impl Iterator<Item = &'a Member> + 'a> {
+        let name = name.to_lowercase();
+        let user_ids = self.nicknames.as_ref()?.get(&name);
+        Some(lookup(members, user_ids).filter(move |member| {
+            member.nick.as_ref().is_some_and(|nick| nick.to_lowercase() == name)
+        }))
+    }
+
+    /// The members whose username or nickname starts with `prefix`, ignoring case, or None if
+    /// names aren't indexed.
+    pub(crate) fn members_starting_with<'a>(
+        &'a self,
+        members: &'a HashMap<UserId, Member>,
+        prefix: &str,
+    ) -> Option<Vec<&'a Member>> {
+        let usernames = self.usernames.as_ref()?;
+        let prefix = prefix.to_lowercase();
+        let user_ids: HashSet<_> = usernames
+            .iter()
+            .chain(self.nicknames.iter().flatten())
+            .filter(|(name, _)| name.starts_with(&prefix))
+            .flat_map(|(_, user_ids)| user_ids)
+            .collect();
+
+        Some(
+            user_ids
+                .into_iter()
+                .filter_map(|user_id| members.get(user_id))
+                .filter(|member| member_starts_with(member, &prefix))
+                .collect(),
+        )
+    }
+
+    /// The members with the given role, or None if roles aren't indexed.
+    pub(crate) fn members_with_role<'a>(
+        &'a self,
+        members: &'a HashMap<UserId, Member>,
+        role_id: RoleId,
+    ) -> Option<impl Iterator<Item = &'a Member> + 'a> {
+        let user_ids = self.roles.as_ref()?.get(&role_id);
+        Some(lookup(members, user_ids).filter(move |member| member.roles.contains(&role_id)))
+    }
+}
+
+fn lookup<'a>(
+    members: &'a HashMap<UserId, Member>,
+    user_ids: Option<&'a HashSet<UserId>>,
+) -> impl Iterator<Item = &'a Member> + 'a {
+    user_ids.into_iter().flatten().filter_map(|user_id| members.get(user_id))
+}
+
+/// Whether the member's username or nickname starts with the lowercased `prefix`, ignoring case.
+pub(super) fn member_starts_with(member: &Member, prefix: &str) -> bool {
+    std::iter::once(&member.user.name)
+        .chain(&member.nick)
+        .any(|name| name.to_lowercase().starts_with(prefix))
+}
+
+fn remove_from<K: Eq + std::hash::Hash>(
+    map: &mut HashMap<K, HashSet<UserId>>,
+    key: &K,
+    user_id: UserId,
+) {
+    if let Some(user_ids) = map.get_mut(key) {
+        user_ids.remove(&user_id);
+        if user_ids.is_empty() {
+            map.remove(key);
+        }
+    }
+}
+
+/// Builds the member indexes of a guild about to be cached, and records its members in the
+/// user to guilds index.
+pub(super) fn index_guild(cache: &Cache, guild: &Guild) {
+    let index = MemberIndex::new(&cache.settings(), &guild.members);
+    cache.member_indexes.insert(guild.id, index);
+
+    for user_id in guild.members.keys() {
+        add_user_guild(cache, *user_id, guild.id);
+    }
+}
+
+/// Removes the indexes of a guild removed from the cache.
+pub(super) fn unindex_guild(cache: &Cache, guild: &Guild) {
+    cache.member_indexes.remove(&guild.id);
+    for user_id in guild.members.keys() {
+        remove_user_guild(cache, *user_id, guild.id);
+    }
+}
+
+/// Updates the indexes after a member of a cached guild was inserted, updated or removed.
+pub(super) fn index_member(
+    cache: &Cache,
+    guild_id: GuildId,
+    old: Option<&Member>,
+    new: Option<&Member>,
+) {
+    if let Some(mut index) = cache.member_indexes.get_mut(&guild_id) {
+        if let Some(old) = old {
+            index.remove(old);
+        }
+        if let Some(new) = new {
+            index.insert(new);
+        }
+    }
+
+    match (old, new) {
+        (None, Some(member)) => add_user_guild(cache, member.user.id, guild_id),
+        (Some(member), None) => remove_user_guild(cache, member.user.id, guild_id),
+        _ => {},
+    }
+}
+
+fn add_user_guild(cache: &Cache, user_id: UserId, guild_id: GuildId) {
+    if let Some(mut guild_ids) = cache.user_guilds.get_mut_or_default(user_id) {
+        guild_ids.insert(guild_id);
+    }
+}
+
+fn remove_user_guild(cache: &Cache, user_id: UserId, guild_id: GuildId) {
+    let is_empty = match cache.user_guilds.get_mut(&user_id) {
+        Some(mut guild_ids) => {
+            guild_ids.remove(&guild_id);
+            guild_ids.is_empty()
+        },
+        None => false,
+    };
+
+    if is_empty {
+        cache.user_guilds.remove(&user_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::test::{guild, member};
+    use crate::model::prelude::*;
+
+    #[test]
+    #[cfg(feature = "model")]
+    fn member_indexes() {
+        let cache = Cache::new_with_settings(Settings {
+            index_user_guilds: true,
+            index_member_roles: true,
+            index_member_names: true,
+            ..Default::default()
+        });
+
+        let guild_id = GuildId::new(1);
+        let role_id = RoleId::new(2);
+        let named = |mut member: Member, name: &str, nick: Option<&str>| {
+            member.user.name = name.into();
+            member.nick = nick.map(Into::into);
+            member
+        };
+        let alice = named(member(1, 3, &[2]), "Alice", None);
+        let bob = named(member(1, 4, &[]), "bob", Some("Al"));
+
+        cache.update(&mut GuildCreateEvent {
+            guild: guild(1, [alice.clone(), bob]),
+        });
+
+        assert_eq!(cache.member_named(guild_id, "Alice").unwrap().user.id, alice.user.id);
+        assert_eq!(cache.member_named(guild_id, "Al").unwrap().user.name, "bob");
+        assert!(cache.member_named(guild_id, "alice").is_none());
+        assert_eq!(cache.members_with_role(guild_id, role_id).len(), 1);
+        assert_eq!(cache.search_members(guild_id, "AL", 10).len(), 2);
+        assert_eq!(cache.search_members(guild_id, "AL", 1)[0].user.id, alice.user.id);
+        assert_eq!(cache.user_guilds(alice.user.id), [guild_id]);
+
+        cache.update(&mut GuildMemberAddEvent {
+            member: named(member(1, 4, &[2]), "bob", Some("Bobby")),
+        });
+        cache.update(&mut GuildMemberRemoveEvent {
+            guild_id,
+            user: alice.user.clone(),
+        });
+
+        assert!(cache.member_named(guild_id, "Al").is_none());
+        assert_eq!(cache.member_named(guild_id, "Bobby").unwrap().user.name, "bob");
+        assert_eq!(cache.members_with_role(guild_id, role_id)[0].user.name, "bob");
+        assert!(cache.user_guilds(alice.user.id).is_empty());
+
+        // Members modified without updating the indexes aren't returned for stale entries.
+        cache.guilds.get_mut(&guild_id).unwrap().members.get_mut(&UserId::new(4)).unwrap().roles =
+            vec![];
+        assert!(cache.members_with_role(guild_id, role_id).is_empty());
+
+        cache.update(&mut GuildDeleteEvent {
+            guild: UnavailableGuild {
+                id: guild_id,
+                unavailable: false,
+            },
+        });
+        assert!(!cache.member_indexes.contains(&guild_id));
+    }
+}
@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+use std::ops::AddAssign;
+
+use typesize::TypeSize;
+
+use super::{Cache, MessageEvictions};
+use crate::model::id::GuildId;
+
+/// An estimate of the memory used by a part of the [`Cache`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MemoryUsage {
+    /// The estimated number of bytes used.
+    pub bytes: usize,
+    /// The number of cached items, such as guilds or messages.
+    pub count: usize,
+}
+
+impl MemoryUsage {
+    fn of(value: &impl TypeSize) -> Self {
+        Self {
+            bytes: value.extra_size(),
+            count: value.get_collection_item_count().unwrap_or(1),
+        }
+    }
+}
+
+impl AddAssign for MemoryUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.bytes += other.bytes;
+        self.count += other.count;
+    }
+}
+
+/// An estimate of the memory used by the [`Cache`], returned by [`Cache::memory_report`].
+///
+/// The categories don't overlap, so [`Self::guilds`] excludes the members, channels and
+/// presences of guilds, which are counted in their own categories.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct MemoryReport {
+    /// Guild data other than members, channels and presences, such as roles and emojis.
+    pub guilds: MemoryUsage,
+    /// The members of all guilds.
+    pub members: MemoryUsage,
+    /// The channels and threads of all guilds.
+    pub channels: MemoryUsage,
+    /// The cached messages of all channels.
+    pub messages: MemoryUsage,
+    /// The presences of all guilds, and the presences received outside of guilds.
+    pub presences: MemoryUsage,
+    /// The cached users.
+    pub users: MemoryUsage,
+    /// The channels temporarily cached after being fetched.
+    #[cfg(feature = "temp_cache")]
+    pub temp_channels: MemoryUsage,
+    /// The private channels temporarily cached after being created.
+    #[cfg(feature = "temp_cache")]
+    pub temp_private_channels: MemoryUsage,
+    /// The messages temporarily cached after being fetched.
+    #[cfg(feature = "temp_cache")]
+    pub temp_messages: MemoryUsage,
+    /// The users temporarily cached after being fetched.
+    #[cfg(feature = "temp_cache")]
+    pub temp_users: MemoryUsage,
+    /// The memory used by each guild, largest first.
+    pub per_guild: Vec<GuildMemoryUsage>,
+    /// The number of messages evicted from the cache since it was created.
+    pub message_evictions: MessageEvictions,
+}
+
+impl MemoryReport {
+    /// The estimated number of bytes used by the whole cache.
+    #[must_use]
+    pub fn total_bytes(&self) -> usize {
+        let total = self.guilds.bytes
+            + self.members.bytes
+            + self.channels.bytes
+            + self.messages.bytes
+            + self.presences.bytes
+            + self.users.bytes;
+
+        #[cfg(feature = "temp_cache")]
+        let total = total
+            + self.temp_channels.bytes
+            + self.temp_private_channels.bytes
+            + self.temp_messages.bytes
+            + self.temp_users.bytes;
+
+        total
+    }
+}
+
+/// An estimate of the memory used by a single guild in the [`Cache`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct GuildMemoryUsage {
+    /// The Id of the guild.
+    pub guild_id: GuildId,
+    /// The estimated number of bytes used by the guild, including its members, channels,
+    /// presences and messages.
+    pub bytes: usize,
+    /// The guild's cached members.
+    pub members: MemoryUsage,
+    /// The guild's channels and threads.
+    pub channels: MemoryUsage,
+    /// The presences of the guild's members.
+    pub presences: MemoryUsage,
+    /// The cached messages of the guild's channels and threads.
+    pub messages: MemoryUsage,
+}
+
+impl Cache {
+    /// Estimates the memory used by the cache, per category of data and per guild.
+    ///
+    /// The estimates only include the cached values themselves, and not the overhead of the
+    /// [`CacheBackend`] storing them. Each value is read separately, so this may be slow for
+    /// large caches, or backends which don't keep their data in memory.
+    ///
+    /// **Note**: Requires the `typesize` feature.
+    ///
+    /// # Examples
+    ///
+    /// Print the guilds using the most memory:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::cache::Cache;
+    /// #
+    /// # let cache = Cache::default();
+    /// let report = cache.memory_report();
+    /// println!("The cache uses {} bytes", report.total_bytes());
+    ///
+    /// for guild in report.per_guild.iter().take(5) {
+    ///     println!("{}: {} bytes, {} members", guild.guild_id, guild.bytes, guild.members.count);
+    /// }
+    /// ```
+    ///
+    /// [`CacheBackend`]: super::CacheBackend
+    #[must_use]
+    pub fn memory_report(&self) -> MemoryReport {
+        let mut report = MemoryReport::default();
+        let mut per_guild = HashMap::new();
+
+        for guild in self.guilds.iter() {
+            let guild_id = *guild.key();
+            let members = MemoryUsage::of(&guild.members);
+            let mut channels = MemoryUsage::of(&guild.channels);
+            channels += MemoryUsage::of(&guild.threads);
+            let presences = MemoryUsage::of(&guild.presences);
+
+            let bytes = guild.get_size();
+            report.guilds += MemoryUsage {
+                bytes: bytes - members.bytes - channels.bytes - presences.bytes,
+                count: 1,
+            };
+            report.members += members;
+            report.channels += channels;
+            report.presences += presences;
+
+            per_guild.insert(guild_id, GuildMemoryUsage {
+                guild_id,
+                bytes,
+                members,
+                channels,
+                presences,
+                messages: MemoryUsage::default(),
+            });
+        }
+
+        for messages in self.messages.iter() {
+            let channel_id = *messages.key();
+            let usage = MemoryUsage::of(messages.value());
+            report.messages += usage;
+
+            let guild_id = self.channels.get(&channel_id).or_else(|| self.threads.get(&channel_id));
+            if let Some(guild) = guild_id.and_then(|guild_id| per_guild.get_mut(&*guild_id)) {
+                guild.messages += usage;
+                guild.bytes += usage.bytes;
+            }
+        }
+
+        for queue in self.message_queue.iter() {
+            report.messages.bytes += queue.extra_size();
+        }
+
+        for user in self.users.iter() {
+            report.users += MemoryUsage {
+                bytes: user.get_size(),
+                count: 1,
+            };
+        }
+
+        for presence in self.presences.iter() {
+            report.presences += MemoryUsage {
+                bytes: presence.get_size(),
+                count: 1,
+            };
+        }
+
+        #[cfg(feature = "temp_cache")]
+        {
+            report.temp_channels = MemoryUsage::of(&self.temp_channels);
+            report.temp_private_channels = MemoryUsage::of(&self.temp_private_channels);
+            report.temp_messages = MemoryUsage::of(&self.temp_messages);
+            report.temp_users = MemoryUsage::of(&self.temp_users);
+        }
+
+        report.message_evictions = self.message_evictions();
+        report.per_guild = per_guild.into_values().collect();
+        report.per_guild.sort_unstable_by_key(|guild| std::cmp::Reverse(guild.bytes));
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::test::{guild, member};
+    use crate::model::prelude::*;
+
+    #[test]
+    fn memory_report_per_guild() {
+        let cache = Cache::new();
+
+        cache.update(&mut GuildCreateEvent {
+            guild: guild(1, [member(1, 1, &[])]),
+        });
+        cache.update(&mut GuildCreateEvent {
+            guild: guild(2, (1..=50).map(|id| member(2, id, &[]))),
+        });
+
+        let report = cache.memory_report();
+        assert_eq!(report.guilds.count, 2);
+        assert_eq!(report.members.count, 51);
+        assert_eq!(report.users.count, 50);
+
+        let guilds: Vec<_> = report.per_guild.iter().map(|g| g.guild_id).collect();
+        assert_eq!(guilds, [GuildId::new(2), GuildId::new(1)]);
+        assert_eq!(report.per_guild[0].members.count, 50);
+        assert!(report.per_guild[0].bytes > report.per_guild[1].bytes);
+        assert!(report.total_bytes() > report.members.bytes);
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::time::Duration;
+
+use tracing::trace;
+
+use super::wrappers::MaybeMap;
+use super::Cache;
+use crate::model::channel::Message;
+use crate::model::id::{ChannelId, MessageId};
+use crate::model::Timestamp;
+
+/// The number of messages evicted from the cache, by reason, returned by
+/// [`Cache::message_evictions`].
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MessageEvictions {
+    /// Messages evicted because their channel reached its maximum number of cached messages.
+    pub channel_limit: u64,
+    /// Messages evicted because the cache reached [`Settings::max_total_messages`].
+    ///
+    /// [`Settings::max_total_messages`]: super::Settings::max_total_messages
+    pub total_limit: u64,
+    /// Messages evicted because they were older than [`Settings::max_message_age`].
+    ///
+    /// [`Settings::max_message_age`]: super::Settings::max_message_age
+    pub expired: u64,
+}
+
+/// Tracks the channels with cached messages in order of activity, to evict messages from the
+/// least recently active channels first.
+#[derive(Debug, Default)]
+pub(crate) struct MessageLru {
+    /// The last activity of each channel with cached messages.
+    last_active: HashMap<ChannelId, u64>,
+    /// The channels with cached messages, least recently active first.
+    channels: BTreeMap<u64, ChannelId>,
+    /// Incremented on each activity.
+    tick: u64,
+    /// The total number of cached messages.
+    len: usize,
+    evictions: MessageEvictions,
+}
+
+impl MessageLru {
+    /// Tracks the messages already stored in the cache's backend, for example by a [`FileBackend`]
+    /// before the process restarted. Channels are ordered by their newest message.
+    ///
+    /// [`FileBackend`]: super::FileBackend
+    pub(super) fn new(
+        messages: &MaybeMap<ChannelId, HashMap<MessageId, Message>>,
+        message_queue: &MaybeMap<ChannelId, VecDeque<MessageId>>,
+    ) -> Self {
+        let mut lru = Self::default();
+        for messages in messages.iter() {
+            lru.len += messages.len();
+        }
+
+        let mut channels: Vec<_> =
+            message_queue.iter().filter_map(|queue| Some((*queue.back()?, *queue.key()))).collect();
+        channels.sort_unstable();
+        for (_, channel_id) in channels {
+            lru.touch(channel_id);
+        }
+
+        lru
+    }
+
+    /// Marks the channel as the most recently active one.
+    fn touch(&mut self, channel_id: ChannelId) {
+        self.tick += 1;
+        if let Some(tick) = self.last_active.insert(channel_id, self.tick) {
+            self.channels.remove(&tick);
+        }
+        self.channels.insert(self.tick, channel_id);
+    }
+
+    /// Stops tracking a channel after `removed` of its messages were removed.
+    pub(super) fn remove_channel(&mut self, channel_id: ChannelId, removed: usize) {
+        if let Some(tick) = self.last_active.remove(&channel_id) {
+            self.channels.remove(&tick);
+        }
+        self.len = self.len.saturating_sub(removed);
+    }
+
+    /// Records the removal of a single message, for example after it was deleted.
+    pub(super) fn remove_message(&mut self) {
+        self.len = self.len.saturating_sub(1);
+    }
+}
+
+/// Why a message was evicted.
+#[derive(Clone, Copy, Debug)]
+enum Reason {
+    ChannelLimit,
+    TotalLimit,
+    Expired,
+}
+
+fn is_expired(message: &Message, max_age: Option<Duration>) -> bool {
+    max_age.is_some_and(|max_age| {
+        let max_age = i64::try_from(max_age.as_secs()).unwrap_or(i64::MAX);
+        let age = Timestamp::now().unix_timestamp() - message.timestamp.unix_timestamp();
+        age > max_age
+    })
+}
+
+/// Evicts the oldest message of a channel, returning it.
+fn evict_oldest(
+    lru: &mut MessageLru,
+    channel_id: ChannelId,
+    messages: &mut HashMap<MessageId, Message>,
+    queue: &mut VecDeque<MessageId>,
+    reason: Reason,
+) -> Option<Message> {
+    let message_id = queue.pop_front()?;
+    let message = messages.remove(&message_id);
+    if message.is_some() {
+        lru.len = lru.len.saturating_sub(1);
+        match reason {
+            Reason::ChannelLimit => lru.evictions.channel_limit += 1,
+            Reason::TotalLimit => lru.evictions.total_limit += 1,
+            Reason::Expired => lru.evictions.expired += 1,
+        }
+        trace!(%channel_id, %message_id, ?reason, "Evicted message from the cache");
+    }
+
+    message
+}
+
+/// Caches a message, evicting messages as required by the settings. Returns the oldest message of
+/// the channel if it was evicted because the channel reached its maximum number of messages.
+pub(super) fn insert_message(cache: &Cache, message: &Message) -> Option<Message> {
+    let channel_id = message.channel_id;
+    let (max, max_total, max_age) = {
+        let settings = cache.settings();
+        (
+            settings.max_messages_in(channel_id),
+            settings.max_total_messages,
+            settings.max_message_age,
+        )
+    };
+    if max == 0 {
+        return None;
+    }
+
+    // The order is always held before the messages, to not deadlock when evicting messages from
+    // other channels.
+    let mut lru = cache.message_lru.lock();
+    let mut removed = None;
+    {
+        let mut messages = cache.messages.get_mut_or_default(channel_id)?;
+        let mut queue = cache.message_queue.get_mut_or_default(channel_id)?;
+
+        while queue
+            .front()
+            .is_some_and(|id| messages.get(id).is_some_and(|m| is_expired(m, max_age)))
+        {
+            evict_oldest(&mut lru, channel_id, &mut messages, &mut queue, Reason::Expired);
+        }
+        while messages.len() >= max {
+            let Some(message) =
+                evict_oldest(&mut lru, channel_id, &mut messages, &mut queue, Reason::ChannelLimit)
+            else {
+                break;
+            };
+            removed = Some(message);
+        }
+
+        if messages.insert(message.id, message.clone()).is_none() {
+            queue.push_back(message.id);
+            lru.len += 1;
+        }
+    }
+    lru.touch(channel_id);
+
+    evict_least_recent(cache, &mut lru, max_total, max_age);
+    removed
+}
+
+/// Evicts the oldest messages of the least recently active channels while the cache holds more
+/// than the maximum total number of messages, or while they are older than the maximum age.
+fn evict_least_recent(
+    cache: &Cache,
+    lru: &mut MessageLru,
+    max_total: Option<usize>,
+    max_age: Option<Duration>,
+) {
+    while let Some((_, &channel_id)) = lru.channels.first_key_value() {
+        let over_budget = max_total.is_some_and(|max| lru.len > max);
+
+        let queue = cache.message_queue.get_mut(&channel_id);
+        let messages = cache.messages.get_mut(&channel_id);
+        let (Some(mut queue), Some(mut messages)) = (queue, messages) else {
+            lru.remove_channel(channel_id, 0);
+            continue;
+        };
+
+        let Some(oldest) = queue.front().and_then(|id| messages.get(id)) else {
+            drop((queue, messages));
+            lru.remove_channel(channel_id, 0);
+            continue;
+        };
+
+        let reason = if is_expired(oldest, max_age) {
+            Reason::Expired
+        } else if over_budget {
+            Reason::TotalLimit
+        } else {
+            break;
+        };
+
+        evict_oldest(lru, channel_id, &mut messages, &mut queue, reason);
+        if queue.is_empty() {
+            drop((queue, messages));
+            lru.remove_channel(channel_id, 0);
+        }
+    }
+}
+
+impl Cache {
+    /// Evicts the cached messages of every channel which are older than
+    /// [`Settings::max_message_age`].
+    ///
+    /// Expired messages are otherwise only evicted when a message is cached, from the channel of
+    /// that message and from the least recently active channels, so this may be called
+    /// periodically to also evict them from inactive channels.
+    ///
+    /// [`Settings::max_message_age`]: super::Settings::max_message_age
+    pub fn evict_expired_messages(&self) {
+        let Some(max_age) = self.settings().max_message_age else {
+            return;
+        };
+
+        let mut lru = self.message_lru.lock();
+        for channel_id in self.message_queue.keys() {
+            let (Some(mut queue), Some(mut messages)) =
+                (self.message_queue.get_mut(&channel_id), self.messages.get_mut(&channel_id))
+            else {
+                continue;
+            };
+
+            while queue
+                .front()
+                .is_some_and(|id| messages.get(id).is_some_and(|m| is_expired(m, Some(max_age))))
+            {
+                evict_oldest(&mut lru, channel_id, &mut messages, &mut queue, Reason::Expired);
+            }
+        }
+    }
+
+    /// Returns the number of messages evicted from the cache since it was created, by reason.
+    ///
+    /// Evictions are also logged at the `TRACE` level.
+    pub fn message_evictions(&self) -> MessageEvictions {
+        self.message_lru.lock().evictions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{FileBackend, Settings};
+    use crate::model::event::MessageCreateEvent;
+
+    fn create(cache: &Cache, channel_id: u64, message_id: u64, age: i64) {
+        let timestamp = Timestamp::now().unix_timestamp() - age;
+        cache.update(&mut MessageCreateEvent {
+            message: Message {
+                id: MessageId::new(message_id),
+                channel_id: ChannelId::new(channel_id),
+                timestamp: Timestamp::from_unix_timestamp(timestamp).unwrap(),
+                ..Default::default()
+            },
+        });
+    }
+
+    fn is_cached(cache: &Cache, channel_id: u64, message_id: u64) -> bool {
+        cache.message(ChannelId::new(channel_id), MessageId::new(message_id)).is_some()
+    }
+
+    #[test]
+    fn message_budget() {
+        let cache = Cache::new_with_settings(Settings {
+            max_messages: 10,
+            max_total_messages: Some(3),
+            channel_max_messages: HashMap::from([(ChannelId::new(3), 1)]),
+            ..Default::default()
+        });
+
+        create(&cache, 1, 1, 0);
+        create(&cache, 1, 2, 0);
+        create(&cache, 2, 3, 0);
+        create(&cache, 2, 4, 0);
+        assert!(!is_cached(&cache, 1, 1));
+        assert!(is_cached(&cache, 1, 2));
+
+        create(&cache, 3, 5, 0);
+        create(&cache, 3, 6, 0);
+        assert!(!is_cached(&cache, 1, 2));
+        assert!(!is_cached(&cache, 3, 5));
+        assert!(is_cached(&cache, 2, 3) && is_cached(&cache, 2, 4) && is_cached(&cache, 3, 6));
+
+        let evictions = cache.message_evictions();
+        assert_eq!(evictions.total_limit, 2);
+        assert_eq!(evictions.channel_limit, 1);
+        assert_eq!(evictions.expired, 0);
+    }
+
+    #[test]
+    fn message_budget_after_restart() {
+        let dir = std::env::temp_dir().join(format!("serenity-messages-{}", std::process::id()));
+        let backend = FileBackend::new(&dir).unwrap();
+        let settings = Settings {
+            max_messages: 10,
+            max_total_messages: Some(2),
+            ..Default::default()
+        };
+
+        let cache = Cache::new_with_backend(settings.clone(), &backend);
+        create(&cache, 1, 1, 0);
+        create(&cache, 2, 2, 0);
+        drop(cache);
+
+        // Messages stored before restarting count towards the budget, and are evicted from the
+        // channel with the oldest messages first.
+        let cache = Cache::new_with_backend(settings, &backend);
+        create(&cache, 3, 3, 0);
+        assert!(!is_cached(&cache, 1, 1));
+        assert!(is_cached(&cache, 2, 2) && is_cached(&cache, 3, 3));
+        assert_eq!(cache.message_evictions().total_limit, 1);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn message_max_age() {
+        let cache = Cache::new_with_settings(Settings {
+            max_messages: 10,
+            max_message_age: Some(Duration::from_secs(60)),
+            ..Default::default()
+        });
+
+        create(&cache, 1, 1, 120);
+        create(&cache, 2, 2, 0);
+        assert!(!is_cached(&cache, 1, 1));
+        assert_eq!(cache.message_evictions().expired, 1);
+
+        // The least recently active channel has no expired message, so the expired message of a
+        // more recently active channel is kept until all channels are checked.
+        create(&cache, 1, 3, 120);
+        assert!(is_cached(&cache, 1, 3));
+        cache.evict_expired_messages();
+        assert!(!is_cached(&cache, 1, 3));
+        assert!(is_cached(&cache, 2, 2));
+        assert_eq!(cache.message_evictions().expired, 2);
+    }
+}
@@ -30,36 +30,74 @@ use std::sync::Arc;
 #[cfg(feature = "temp_cache")]
 use std::time::Duration;
 
-use dashmap::mapref::entry::Entry;
-use dashmap::mapref::one::{MappedRef, Ref};
-use dashmap::DashMap;
 #[cfg(feature = "temp_cache")]
 use mini_moka::sync::Cache as MokaCache;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use tracing::instrument;
 
+use self::backend::MappedStorageRef;
+pub use self::backend::{
+    CacheBackend,
+    CacheKey,
+    CacheMap,
+    CacheValue,
+    DashMapBackend,
+    StorageRef,
+    StorageRefMut,
+};
 pub use self::cache_update::CacheUpdate;
-pub use self::settings::Settings;
+pub use self::diff::{
+    CacheDiff,
+    ChannelDiff,
+    CollectionDiff,
+    EmojiDiff,
+    EmojisDiff,
+    GuildDiff,
+    MemberDiff,
+    MessageDiff,
+    PresenceDiff,
+    RoleDiff,
+    ScheduledEventDiff,
+    StageInstanceDiff,
+    StickerDiff,
+    StickersDiff,
+    VoiceStateDiff,
+};
+pub use self::file_backend::FileBackend;
+use self::index::{member_starts_with, MemberIndex};
+#[cfg(feature = "typesize")]
+pub use self::memory::{GuildMemoryUsage, MemoryReport, MemoryUsage};
+pub use self::messages::MessageEvictions;
+use self::messages::MessageLru;
+pub use self::settings::{MemberEviction, Settings};
 use crate::model::prelude::*;
 
+mod backend;
 mod cache_update;
+mod diff;
 mod event;
+mod file_backend;
+mod index;
+#[cfg(feature = "typesize")]
+mod memory;
+mod messages;
 mod settings;
+mod snapshot;
 mod wrappers;
 
+#[cfg(feature = "temp_cache")]
+use wrappers::BuildHasher;
 #[cfg(feature = "temp_cache")]
 pub(crate) use wrappers::MaybeOwnedArc;
-use wrappers::{BuildHasher, MaybeMap, ReadOnlyMapRef};
-
-type MessageCache = DashMap<ChannelId, HashMap<MessageId, Message>, BuildHasher>;
+use wrappers::{MaybeMap, ReadOnlyMapRef};
 
 struct NotSend;
 
 enum CacheRefInner<'a, K, V, T> {
     #[cfg(feature = "temp_cache")]
     Arc(Arc<V>),
-    DashRef(Ref<'a, K, V, BuildHasher>),
-    DashMappedRef(MappedRef<'a, K, T, V, BuildHasher>),
+    Storage(StorageRef<'a, K, V>),
+    MappedStorage(MappedStorageRef<'a, K, T, V>),
     ReadGuard(parking_lot::RwLockReadGuard<'a, V>),
 }
 
@@ -81,12 +119,12 @@ impl<'a, K, V, T> CacheRef<'a, K, V, T> {
         Self::new(CacheRefInner::Arc(inner.get_inner()))
     }
 
-    fn from_ref(inner: Ref<'a, K, V, BuildHasher>) -> Self {
-        Self::new(CacheRefInner::DashRef(inner))
+    fn from_ref(inner: StorageRef<'a, K, V>) -> Self {
+        Self::new(CacheRefInner::Storage(inner))
     }
 
-    fn from_mapped_ref(inner: MappedRef<'a, K, T, V, BuildHasher>) -> Self {
-        Self::new(CacheRefInner::DashMappedRef(inner))
+    fn from_mapped_ref(inner: MappedStorageRef<'a, K, T, V>) -> Self {
+        Self::new(CacheRefInner::MappedStorage(inner))
     }
 
     fn from_guard(inner: parking_lot::RwLockReadGuard<'a, V>) -> Self {
@@ -101,8 +139,8 @@ impl<K: Eq + Hash, V, T> std::ops::Deref for CacheRef<'_, K, V, T> {
         match &self.inner {
             #[cfg(feature = "temp_cache")]
             CacheRefInner::Arc(inner) => inner,
-            CacheRefInner::DashRef(inner) => inner.value(),
-            CacheRefInner::DashMappedRef(inner) => inner.value(),
+            CacheRefInner::Storage(inner) => inner,
+            CacheRefInner::MappedStorage(inner) => inner,
             CacheRefInner::ReadGuard(inner) => inner,
         }
     }
@@ -117,6 +155,7 @@ pub type GuildRoleRef<'a> = MappedGuildRef<'a, Role>;
 pub type SettingsRef<'a> = CacheRef<'a, (), Settings>;
 pub type CurrentUserRef<'a> = CacheRef<'a, (), CurrentUser>;
 pub type GuildChannelRef<'a> = MappedGuildRef<'a, GuildChannel>;
+pub type ScheduledEventRef<'a> = MappedGuildRef<'a, ScheduledEvent>;
 pub type GuildRolesRef<'a> = MappedGuildRef<'a, HashMap<RoleId, Role>>;
 pub type GuildChannelsRef<'a> = MappedGuildRef<'a, HashMap<ChannelId, GuildChannel>>;
 pub type ChannelMessagesRef<'a> = CacheRef<'a, ChannelId, HashMap<MessageId, Message>>;
@@ -137,7 +176,14 @@ pub(crate) struct CachedShardData {
 ///
 /// This is the list of cached resources and the events that populate them:
 /// - channels: [`ChannelCreateEvent`], [`ChannelUpdateEvent`], [`GuildCreateEvent`]
+/// - threads: [`ThreadCreateEvent`], [`ThreadUpdateEvent`], [`ThreadDeleteEvent`],
+///   [`ThreadListSyncEvent`], [`ThreadMembersUpdateEvent`], [`GuildCreateEvent`]
 /// - guilds: [`GuildCreateEvent`]
+/// - scheduled events: [`GuildScheduledEventCreateEvent`], [`GuildScheduledEventUpdateEvent`],
+///   [`GuildScheduledEventDeleteEvent`], [`GuildScheduledEventUserAddEvent`],
+///   [`GuildScheduledEventUserRemoveEvent`], [`GuildCreateEvent`]
+/// - stage instances: [`StageInstanceCreateEvent`], [`StageInstanceUpdateEvent`],
+///   [`StageInstanceDeleteEvent`], [`GuildCreateEvent`]
 /// - unavailable_guilds: [`ReadyEvent`], [`GuildDeleteEvent`]
 /// - users: [`GuildMemberAddEvent`], [`GuildMemberRemoveEvent`], [`GuildMembersChunkEvent`],
 ///   [`PresenceUpdateEvent`], [`ReadyEvent`]
@@ -178,6 +224,8 @@ pub struct Cache {
     // Channels cache:
     /// A map of channel ids to the guilds in which the channel data is stored.
     pub(crate) channels: MaybeMap<ChannelId, GuildId>,
+    /// A map of thread ids to the guilds in which the thread data is stored.
+    pub(crate) threads: MaybeMap<ChannelId, GuildId>,
 
     // Guilds cache:
     // ---
@@ -209,16 +257,26 @@ pub struct Cache {
     /// A map of users' presences. This is updated in real-time. Note that status updates are often
     /// "eaten" by the gateway, and this should not be treated as being entirely 100% accurate.
     pub(crate) presences: MaybeMap<UserId, Presence>,
+    /// A map of users to the guilds they are cached as a member of, maintained if
+    /// [`Settings::index_user_guilds`] is enabled.
+    pub(crate) user_guilds: MaybeMap<UserId, HashSet<GuildId>>,
+    /// A map of guilds to indexes over their cached members, maintained if
+    /// [`Settings::index_member_names`] or [`Settings::index_member_roles`] is enabled.
+    pub(crate) member_indexes: MaybeMap<GuildId, MemberIndex>,
 
     // Messages cache:
     // ---
-    pub(crate) messages: MessageCache,
+    pub(crate) messages: MaybeMap<ChannelId, HashMap<MessageId, Message>>,
     /// Queue of message IDs for each channel.
     ///
     /// This is simply a vecdeque so we can keep track of the order of messages inserted into the
     /// cache. When a maximum number of messages are in a channel's cache, we can pop the front and
     /// remove that ID from the cache.
-    pub(crate) message_queue: DashMap<ChannelId, VecDeque<MessageId>, BuildHasher>,
+    pub(crate) message_queue: MaybeMap<ChannelId, VecDeque<MessageId>>,
+    /// The channels with cached messages in order of activity, used to evict messages from the
+    /// least recently active channels first.
+    #[cfg_attr(feature = "typesize", typesize(skip))]
+    pub(crate) message_lru: Mutex<MessageLru>,
 
     // Miscellanous fixed-size data
     // ---
@@ -257,6 +315,14 @@ impl Cache {
     /// ```
     #[instrument]
     pub fn new_with_settings(settings: Settings) -> Self {
+        Self::new_with_backend(settings, &DashMapBackend)
+    }
+
+    /// Creates a new cache instance with settings applied, storing its data in the given backend.
+    ///
+    /// Refer to [`CacheBackend`] for more information.
+    #[instrument(skip(backend))]
+    pub fn new_with_backend(settings: Settings, backend: &impl CacheBackend) -> Self {
         #[cfg(feature = "temp_cache")]
         fn temp_cache<K, V>(ttl: Duration) -> MokaCache<K, V, BuildHasher>
         where
@@ -266,6 +332,10 @@ impl Cache {
             MokaCache::builder().time_to_live(ttl).build_with_hasher(BuildHasher::default())
         }
 
+        let messages = MaybeMap::new(backend, "messages", true);
+        let message_queue = MaybeMap::new(backend, "message_queue", true);
+        let message_lru = Mutex::new(MessageLru::new(&messages, &message_queue));
+
         Self {
             #[cfg(feature = "temp_cache")]
             temp_private_channels: temp_cache(settings.time_to_live),
@@ -276,16 +346,28 @@ impl Cache {
             #[cfg(feature = "temp_cache")]
             temp_users: temp_cache(settings.time_to_live),
 
-            channels: MaybeMap(settings.cache_channels.then(DashMap::default)),
-
-            guilds: MaybeMap(settings.cache_guilds.then(DashMap::default)),
-            unavailable_guilds: MaybeMap(settings.cache_guilds.then(DashMap::default)),
-
-            users: MaybeMap(settings.cache_users.then(DashMap::default)),
-            presences: MaybeMap(settings.cache_users.then(DashMap::default)),
-
-            messages: DashMap::default(),
-            message_queue: DashMap::default(),
+            channels: MaybeMap::new(backend, "channels", settings.cache_channels),
+            threads: MaybeMap::new(backend, "threads", settings.cache_channels),
+
+            guilds: MaybeMap::new(backend, "guilds", settings.cache_guilds),
+            unavailable_guilds: MaybeMap::new(backend, "unavailable_guilds", settings.cache_guilds),
+
+            users: MaybeMap::new(backend, "users", settings.cache_users),
+            presences: MaybeMap::new(
+                backend,
+                "presences",
+                settings.cache_users && settings.cache_presences,
+            ),
+            user_guilds: MaybeMap::new(backend, "user_guilds", settings.index_user_guilds),
+            member_indexes: MaybeMap::new(
+                backend,
+                "member_indexes",
+                settings.index_member_names || settings.index_member_roles,
+            ),
+
+            messages,
+            message_queue,
+            message_lru,
 
             shard_data: RwLock::new(CachedShardData {
                 total: 1,
@@ -324,9 +406,7 @@ impl Cache {
     pub fn unknown_members(&self) -> u64 {
         let mut total = 0;
 
-        for guild_entry in self.guilds.iter() {
-            let guild = guild_entry.value();
-
+        for guild in self.guilds.iter() {
             let members = guild.members.len() as u64;
 
             if guild.member_count > members {
@@ -366,11 +446,115 @@ impl Cache {
     /// [`Context`]: crate::client::Context
     /// [`Shard`]: crate::gateway::Shard
     pub fn guilds(&self) -> Vec<GuildId> {
-        let unavailable_guilds = self.unavailable_guilds();
+        let mut guild_ids = self.guilds.keys();
+        guild_ids.extend(self.unavailable_guilds.keys());
+        guild_ids
+    }
+
+    /// Retrieves the Ids of the cached [`Guild`]s which the given user is a cached member of.
+    ///
+    /// This uses an index if [`Settings::index_user_guilds`] is enabled, and otherwise searches
+    /// the members of every cached guild.
+    pub fn user_guilds(&self, user_id: UserId) -> Vec<GuildId> {
+        if self.settings().index_user_guilds {
+            return self
+                .user_guilds
+                .get(&user_id)
+                .map(|guild_ids| guild_ids.iter().copied().collect())
+                .unwrap_or_default();
+        }
+
+        self.guilds
+            .iter()
+            .filter(|guild| guild.members.contains_key(&user_id))
+            .map(|guild| *guild.key())
+            .collect()
+    }
+
+    /// Retrieves a cached member of a guild by their username, optionally with their
+    /// discriminator, or by their nickname, like [`Guild::member_named`].
+    ///
+    /// This uses an index if [`Settings::index_member_names`] is enabled, and otherwise searches
+    /// the members of the guild.
+    #[cfg(feature = "model")]
+    pub fn member_named(&self, guild_id: impl Into<GuildId>, name: &str) -> Option<Member> {
+        let guild_id = guild_id.into();
+        let guild = self.guilds.get(&guild_id)?;
+        let index = self.member_indexes.get(&guild_id);
 
-        let unavailable_guild_ids = unavailable_guilds.iter().map(|i| *i.key());
+        let (username, discrim) = match crate::utils::parse_user_tag(name) {
+            Some((username, discrim)) => (username, Some(discrim)),
+            None => (name, None),
+        };
+        let Some(mut members) =
+            index.as_ref().and_then(|index| index.members_with_username(&guild.members, username))
+        else {
+            return guild.member_named(name).cloned();
+        };
 
-        self.guilds.iter().map(|i| *i.key()).chain(unavailable_guild_ids).collect()
+        let member = members
+            .find(|member| {
+                member.user.name == username
+                    && discrim.map_or(true, |d| member.user.discriminator == d)
+            })
+            .or_else(|| {
+                index
+                    .as_ref()?
+                    .members_with_nickname(&guild.members, name)?
+                    .find(|member| member.nick.as_deref() == Some(name))
+            });
+        member.cloned()
+    }
+
+    /// Retrieves the cached members of a guild who have the given role.
+    ///
+    /// This uses an index if [`Settings::index_member_roles`] is enabled, and otherwise searches
+    /// the members of the guild.
+    pub fn members_with_role(
+        &self,
+        guild_id: impl Into<GuildId>,
+        role_id: impl Into<RoleId>,
+    ) -> Vec<Member> {
+        let (guild_id, role_id) = (guild_id.into(), role_id.into());
+        let Some(guild) = self.guilds.get(&guild_id) else { return Vec::new() };
+        let index = self.member_indexes.get(&guild_id);
+
+        let members =
+            match index.as_ref().and_then(|i| i.members_with_role(&guild.members, role_id)) {
+                Some(members) => members.cloned().collect(),
+                None => {
+                    guild.members.values().filter(|m| m.roles.contains(&role_id)).cloned().collect()
+                },
+            };
+        members
+    }
+
+    /// Searches the cached members of a guild whose username or nickname starts with the query,
+    /// ignoring case. At most `limit` members are returned, sorted by their user Id.
+    ///
+    /// Unlike [`GuildId::search_members`], which searches all of the guild's members via the API,
+    /// this only finds the members that are cached. This uses an index if
+    /// [`Settings::index_member_names`] is enabled, and otherwise searches the members of the
+    /// guild.
+    pub fn search_members(
+        &self,
+        guild_id: impl Into<GuildId>,
+        query: &str,
+        limit: usize,
+    ) -> Vec<Member> {
+        let guild_id = guild_id.into();
+        let Some(guild) = self.guilds.get(&guild_id) else { return Vec::new() };
+        let index = self.member_indexes.get(&guild_id);
+
+        let mut members = index
+            .as_ref()
+            .and_then(|index| index.members_starting_with(&guild.members, query))
+            .unwrap_or_else(|| {
+                let query = query.to_lowercase();
+                guild.members.values().filter(|m| member_starts_with(m, &query)).collect()
+            });
+        members.sort_unstable_by_key(|member| member.user.id);
+        members.into_iter().take(limit).cloned().collect()
     }
 
     /// Retrieves a [`GuildChannel`] from the cache based on the given Id.
@@ -539,6 +723,176 @@ impl Cache {
         self.channels.len()
     }
 
+    /// Retrieves an active thread from the cache based on the given Id.
+    ///
+    /// # Examples
+    ///
+    /// Print the name of a thread and of the channel it was created in:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::cache::Cache;
+    /// #
+    /// # let cache = Cache::default();
+    /// if let Some(thread) = cache.thread(7) {
+    ///     println!("Thread name: {}", thread.name);
+    /// };
+    ///
+    /// if let Some(parent) = cache.thread_parent(7) {
+    ///     println!("Created in: {}", parent.name);
+    /// };
+    /// ```
+    #[inline]
+    pub fn thread(&self, thread_id: impl Into<ChannelId>) -> Option<GuildChannelRef<'_>> {
+        self._thread(thread_id.into())
+    }
+
+    fn _thread(&self, thread_id: ChannelId) -> Option<GuildChannelRef<'_>> {
+        let guild_id = *self.threads.get(&thread_id)?;
+        let thread = self
+            .guilds
+            .get(&guild_id)?
+            .try_map(|g| g.threads.iter().find(|t| t.id == thread_id))
+            .ok()?;
+        Some(CacheRef::from_mapped_ref(thread))
+    }
+
+    /// Retrieves the channel in which an active thread was created, based on the thread's Id.
+    #[inline]
+    pub fn thread_parent(&self, thread_id: impl Into<ChannelId>) -> Option<GuildChannelRef<'_>> {
+        self._thread_parent(thread_id.into())
+    }
+
+    fn _thread_parent(&self, thread_id: ChannelId) -> Option<GuildChannelRef<'_>> {
+        let guild_id = *self.threads.get(&thread_id)?;
+        let parent = self
+            .guilds
+            .get(&guild_id)?
+            .try_map(|g| {
+                let parent_id = g.threads.iter().find(|t| t.id == thread_id)?.parent_id?;
+                g.channels.get(&parent_id)
+            })
+            .ok()?;
+        Some(CacheRef::from_mapped_ref(parent))
+    }
+
+    /// Calculates the permissions of a member in a channel or thread, based on the channel's and
+    /// user's given Ids.
+    ///
+    /// Threads use the permission overwrites of their parent channel. On top of the overwrites,
+    /// the permissions Discord implicitly denies are removed: members who can't view the channel
+    /// have no permissions in it, members who can't send messages can't mention everyone, attach
+    /// files, embed links or send TTS messages, and timed out members can only view the channel
+    /// and read its history.
+    ///
+    /// In threads, [Send Messages] is never included, since sending messages there depends on
+    /// [Send Messages In Threads] instead.
+    ///
+    /// # Examples
+    ///
+    /// Check whether the current user can send messages in the channel a message was sent in,
+    /// even if that's a thread:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::cache::Cache;
+    /// # use serenity::model::prelude::*;
+    /// #
+    /// # fn run(cache: &Cache, message: &Message) -> serenity::Result<()> {
+    /// let user_id = cache.current_user().id;
+    /// let permissions = cache.permissions_in(message.channel_id, user_id)?;
+    ///
+    /// let can_reply = if cache.thread(message.channel_id).is_some() {
+    ///     permissions.send_messages_in_threads()
+    /// } else {
+    ///     permissions.send_messages()
+    /// };
+    /// if can_reply {
+    ///     println!("Can reply");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ModelError::ChannelNotFound`] if the channel, or the parent of the thread,
+    /// could not be found in the cache, a [`ModelError::GuildNotFound`] if its guild could not be
+    /// found, or a [`ModelError::MemberNotFound`] if the user is not a cached member of the guild.
+    ///
+    /// [`ModelError::ChannelNotFound`]: crate::model::ModelError::ChannelNotFound
+    /// [`ModelError::GuildNotFound`]: crate::model::ModelError::GuildNotFound
+    /// [`ModelError::MemberNotFound`]: crate::model::ModelError::MemberNotFound
+    /// [Send Messages]: Permissions::SEND_MESSAGES
+    /// [Send Messages In Threads]: Permissions::SEND_MESSAGES_IN_THREADS
+    #[cfg(feature = "model")]
+    #[inline]
+    pub fn permissions_in(
+        &self,
+        channel_id: impl Into<ChannelId>,
+        user_id: impl Into<UserId>,
+    ) -> crate::Result<Permissions> {
+        self._permissions_in(channel_id.into(), user_id.into())
+    }
+
+    #[cfg(feature = "model")]
+    fn _permissions_in(
+        &self,
+        channel_id: ChannelId,
+        user_id: UserId,
+    ) -> crate::Result<Permissions> {
+        use crate::model::guild::apply_implicit_permissions;
+        use crate::model::ModelError;
+
+        let guild_id = self
+            .channels
+            .get(&channel_id)
+            .or_else(|| self.threads.get(&channel_id))
+            .map(|guild_id| *guild_id)
+            .ok_or(ModelError::ChannelNotFound)?;
+        let guild = self.guilds.get(&guild_id).ok_or(ModelError::GuildNotFound)?;
+
+        let (channel, in_thread) = if let Some(channel) = guild.channels.get(&channel_id) {
+            (channel, false)
+        } else {
+            let parent = guild
+                .threads
+                .iter()
+                .find(|thread| thread.id == channel_id)
+                .and_then(|thread| guild.channels.get(&thread.parent_id?))
+                .ok_or(ModelError::ChannelNotFound)?;
+            (parent, true)
+        };
+        let member = guild.members.get(&user_id).ok_or(ModelError::MemberNotFound)?;
+
+        let permissions = guild.user_permissions_in(channel, member);
+        let timed_out =
+            member.communication_disabled_until.is_some_and(|until| until > Timestamp::now());
+        Ok(apply_implicit_permissions(permissions, in_thread, timed_out))
+    }
+
+    /// Retrieves a guild's scheduled event from the cache based on the guild's and event's given
+    /// Ids.
+    #[inline]
+    pub fn scheduled_event(
+        &self,
+        guild_id: impl Into<GuildId>,
+        event_id: impl Into<ScheduledEventId>,
+    ) -> Option<ScheduledEventRef<'_>> {
+        self._scheduled_event(guild_id.into(), event_id.into())
+    }
+
+    fn _scheduled_event(
+        &self,
+        guild_id: GuildId,
+        event_id: ScheduledEventId,
+    ) -> Option<ScheduledEventRef<'_>> {
+        let event = self
+            .guilds
+            .get(&guild_id)?
+            .try_map(|g| g.scheduled_events.iter().find(|e| e.id == event_id))
+            .ok()?;
+        Some(CacheRef::from_mapped_ref(event))
+    }
+
     /// Returns the number of shards.
     #[inline]
     pub fn shard_count(&self) -> u32 {
@@ -649,6 +1003,16 @@ impl Cache {
         self.settings.write().max_messages = max;
     }
 
+    /// Sets the maximum amount of messages to cache in the given channel, overriding
+    /// [`Settings::max_messages`], or removes the override if `max` is None.
+    pub fn set_channel_max_messages(&self, channel_id: ChannelId, max: Option<usize>) {
+        let overrides = &mut self.settings.write().channel_max_messages;
+        match max {
+            Some(max) => overrides.insert(channel_id, max),
+            None => overrides.remove(&channel_id),
+        };
+    }
+
     /// Retrieves a [`User`] from the cache's [`Self::users`] map, if it exists.
     ///
     /// The only advantage of this method is that you can pass in anything that is indirectly a
@@ -754,14 +1118,7 @@ impl Cache {
 
     pub(crate) fn update_user_entry(&self, user: &User) {
         if let Some(users) = &self.users.0 {
-            match users.entry(user.id) {
-                Entry::Vacant(e) => {
-                    e.insert(user.clone());
-                },
-                Entry::Occupied(mut e) => {
-                    e.get_mut().clone_from(user);
-                },
-            }
+            users.get_mut_or_insert_with(user.id, &|| user.clone()).clone_from(user);
         }
     }
 }
@@ -773,11 +1130,35 @@ impl Default for Cache {
 }
 
 #[cfg(test)]
-mod test {
+pub(crate) mod test {
 
-    use crate::cache::{Cache, CacheUpdate, Settings};
+    use std::collections::HashSet;
+
+    use crate::cache::{Cache, CacheUpdate, MemberEviction, Settings};
     use crate::model::prelude::*;
 
+    /// A member of the guild with the given user and roles, shared by the cache's tests.
+    pub(crate) fn member(guild_id: u64, user_id: u64, roles: &[u64]) -> Member {
+        Member {
+            guild_id: GuildId::new(guild_id),
+            user: User {
+                id: UserId::new(user_id),
+                ..Default::default()
+            },
+            roles: roles.iter().copied().map(RoleId::new).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// A guild with the given members, shared by the cache's tests.
+    pub(crate) fn guild(id: u64, members: impl IntoIterator<Item = Member>) -> Guild {
+        Guild {
+            id: GuildId::new(id),
+            members: members.into_iter().map(|m| (m.user.id, m)).collect(),
+            ..Default::default()
+        }
+    }
+
     #[test]
     fn test_cache_messages() {
         let settings = Settings {
@@ -796,7 +1177,7 @@ mod test {
         };
 
         // Check that the channel cache doesn't exist.
-        assert!(!cache.messages.contains_key(&event.message.channel_id));
+        assert!(!cache.messages.contains(&event.message.channel_id));
         // Add first message, none because message ID 2 doesn't already exist.
         assert!(event.update(&cache).is_none());
         // None, it only returns the oldest message if the cache was already full.
@@ -833,7 +1214,7 @@ mod test {
             channel: channel.clone(),
         };
         assert!(cache.update(&mut delete).is_some());
-        assert!(!cache.messages.contains_key(&delete.channel.id));
+        assert!(!cache.messages.contains(&delete.channel.id));
 
         // Test deletion of a guild channel's message cache when a GuildDeleteEvent is received.
         let mut guild_create = GuildCreateEvent {
@@ -857,6 +1238,444 @@ mod test {
         assert!(cache.update(&mut guild_delete).is_some());
 
         // Assert that the channel's message cache no longer exists.
-        assert!(!cache.messages.contains_key(&ChannelId::new(2)));
+        assert!(!cache.messages.contains(&ChannelId::new(2)));
+    }
+
+    #[test]
+    fn test_cache_message_deletes() {
+        let settings = Settings {
+            max_messages: 10,
+            ..Default::default()
+        };
+        let cache = Cache::new_with_settings(settings);
+        let channel_id = ChannelId::new(2);
+
+        for id in 1..=4 {
+            cache.update(&mut MessageCreateEvent {
+                message: Message {
+                    id: MessageId::new(id),
+                    channel_id,
+                    content: format!("message {id}"),
+                    ..Default::default()
+                },
+            });
+        }
+
+        // The deleted message is evicted from the cache and returned.
+        let mut delete = MessageDeleteEvent {
+            guild_id: None,
+            channel_id,
+            message_id: MessageId::new(1),
+        };
+        assert_eq!(cache.update(&mut delete).unwrap().content, "message 1");
+        assert!(cache.message(channel_id, MessageId::new(1)).is_none());
+        assert!(cache.update(&mut delete).is_none());
+
+        // Only the cached messages of a bulk delete are returned.
+        let mut delete_bulk = MessageDeleteBulkEvent {
+            guild_id: None,
+            channel_id,
+            ids: vec![MessageId::new(2), MessageId::new(3), MessageId::new(5)],
+        };
+        let deleted = cache.update(&mut delete_bulk).unwrap();
+        assert_eq!(deleted.iter().map(|m| m.id.get()).collect::<Vec<_>>(), [2, 3]);
+
+        assert_eq!(cache.messages.get(&channel_id).unwrap().len(), 1);
+        assert_eq!(*cache.message_queue.get(&channel_id).unwrap(), [MessageId::new(4)]);
+    }
+
+    #[test]
+    fn test_cache_message_reactions() {
+        let settings = Settings {
+            max_messages: 10,
+            ..Default::default()
+        };
+        let cache = Cache::new_with_settings(settings);
+        cache.user.write().id = UserId::new(1);
+
+        let (channel_id, message_id) = (ChannelId::new(2), MessageId::new(3));
+        cache.update(&mut MessageCreateEvent {
+            message: Message {
+                id: message_id,
+                channel_id,
+                ..Default::default()
+            },
+        });
+
+        let reaction = |user_id, emoji: ReactionType| Reaction {
+            user_id: Some(UserId::new(user_id)),
+            channel_id,
+            message_id,
+            guild_id: None,
+            member: None,
+            emoji,
+        };
+        let custom = |name: Option<&str>| ReactionType::Custom {
+            animated: false,
+            id: EmojiId::new(4),
+            name: name.map(ToString::to_string),
+        };
+        let reactions = || {
+            let message = cache.message(channel_id, message_id).unwrap();
+            message.reactions.iter().map(|r| (r.count, r.me)).collect::<Vec<_>>()
+        };
+
+        cache.update(&mut ReactionAddEvent {
+            reaction: reaction(1, custom(Some("emoji"))),
+        });
+        // Custom emojis are matched by ID, regardless of their name.
+        cache.update(&mut ReactionAddEvent {
+            reaction: reaction(5, custom(None)),
+        });
+        cache.update(&mut ReactionAddEvent {
+            reaction: reaction(5, '\u{1f44d}'.into()),
+        });
+        assert_eq!(reactions(), [(2, true), (1, false)]);
+
+        cache.update(&mut ReactionRemoveEvent {
+            reaction: reaction(1, custom(None)),
+        });
+        assert_eq!(reactions(), [(1, false), (1, false)]);
+
+        // Reactions are removed once nobody reacts with them anymore.
+        cache.update(&mut ReactionRemoveEvent {
+            reaction: reaction(5, '\u{1f44d}'.into()),
+        });
+        assert_eq!(reactions(), [(1, false)]);
+
+        cache.update(&mut ReactionRemoveEmojiEvent {
+            reaction: reaction(5, custom(None)),
+        });
+        assert!(reactions().is_empty());
+
+        cache.update(&mut ReactionAddEvent {
+            reaction: reaction(1, '\u{1f44d}'.into()),
+        });
+        cache.update(&mut ReactionRemoveAllEvent {
+            channel_id,
+            message_id,
+            guild_id: None,
+        });
+        assert!(reactions().is_empty());
+    }
+
+    #[test]
+    fn test_cache_threads_and_scheduled_events() {
+        let cache = Cache::new();
+        cache.user.write().id = UserId::new(1);
+
+        let guild_id = GuildId::new(1);
+        let parent = GuildChannel {
+            id: ChannelId::new(2),
+            guild_id,
+            name: "parent".into(),
+            ..Default::default()
+        };
+        let thread = |id, parent_id| GuildChannel {
+            id: ChannelId::new(id),
+            guild_id,
+            parent_id: Some(ChannelId::new(parent_id)),
+            ..Default::default()
+        };
+        cache.update(&mut GuildCreateEvent {
+            guild: Guild {
+                id: guild_id,
+                channels: HashMap::from([(parent.id, parent.clone())]),
+                threads: vec![thread(3, 2), thread(4, 5)],
+                ..Default::default()
+            },
+        });
+        assert_eq!(cache.thread_parent(ChannelId::new(3)).unwrap().name, "parent");
+
+        // Only the threads of the synced channels are replaced.
+        cache.update(&mut ThreadListSyncEvent {
+            guild_id,
+            channel_ids: Some(vec![parent.id]),
+            threads: vec![thread(6, 2)],
+            members: vec![],
+        });
+        assert!(cache.thread(ChannelId::new(3)).is_none());
+        assert!(cache.thread(ChannelId::new(4)).is_some());
+        assert!(cache.thread(ChannelId::new(6)).is_some());
+
+        cache.update(&mut ThreadMembersUpdateEvent {
+            id: ChannelId::new(6),
+            guild_id,
+            member_count: 3,
+            added_members: vec![],
+            removed_member_ids: vec![UserId::new(1)],
+        });
+        assert_eq!(cache.thread(ChannelId::new(6)).unwrap().member_count, Some(3));
+
+        // Threads are removed along with their parent.
+        cache.update(&mut ChannelDeleteEvent {
+            channel: parent,
+        });
+        assert!(cache.thread(ChannelId::new(6)).is_none());
+        assert!(cache.thread(ChannelId::new(4)).is_some());
+
+        let event_id = ScheduledEventId::new(7);
+        let mut event: ScheduledEvent = crate::json::from_value(crate::json::json!({
+            "id": event_id,
+            "guild_id": guild_id,
+            "name": "before",
+            "scheduled_start_time": "2024-01-01T00:00:00Z",
+            "privacy_level": 2,
+            "status": 1,
+            "entity_type": 3,
+            "user_count": 0,
+        }))
+        .unwrap();
+        cache.update(&mut GuildScheduledEventCreateEvent {
+            event: event.clone(),
+        });
+        cache.update(&mut GuildScheduledEventUserAddEvent {
+            scheduled_event_id: event_id,
+            user_id: UserId::new(1),
+            guild_id,
+        });
+        assert_eq!(cache.scheduled_event(guild_id, event_id).unwrap().user_count, Some(1));
+
+        event.name = "after".into();
+        let old = cache.update(&mut GuildScheduledEventUpdateEvent {
+            event: event.clone(),
+        });
+        assert_eq!(old.unwrap().name, "before");
+        assert_eq!(cache.scheduled_event(guild_id, event_id).unwrap().name, "after");
+
+        cache.update(&mut GuildScheduledEventDeleteEvent {
+            event,
+        });
+        assert!(cache.scheduled_event(guild_id, event_id).is_none());
+    }
+
+    #[test]
+    fn test_cache_member_filters() {
+        let settings = Settings {
+            cache_presences: false,
+            cache_emojis: false,
+            member_guilds: Some(HashSet::from([GuildId::new(1)])),
+            members_with_roles_only: true,
+            max_members_per_guild: Some(2),
+            member_eviction: MemberEviction::OldestJoined,
+            ..Default::default()
+        };
+        let cache = Cache::new_with_settings(settings);
+
+        let joined = |member: Member, joined_at| Member {
+            joined_at: Some(Timestamp::from_unix_timestamp(joined_at).unwrap()),
+            ..member
+        };
+        let emoji: Emoji =
+            crate::json::from_value(crate::json::json!({"id": "9", "name": "emoji"})).unwrap();
+
+        cache.update(&mut GuildCreateEvent {
+            guild: Guild {
+                emojis: HashMap::from([(emoji.id, emoji)]),
+                ..guild(1, [
+                    joined(member(1, 2, &[1]), 10),
+                    joined(member(1, 3, &[]), 20),
+                    joined(member(1, 4, &[1]), 30),
+                ])
+            },
+        });
+        cache.update(&mut GuildCreateEvent {
+            guild: guild(2, [joined(member(2, 2, &[1]), 10)]),
+        });
+
+        let members = |id| {
+            let mut members: Vec<_> =
+                cache.guild(GuildId::new(id)).unwrap().members.keys().map(|id| id.get()).collect();
+            members.sort_unstable();
+            members
+        };
+        assert_eq!(members(1), [2, 4]);
+        assert!(members(2).is_empty());
+        assert!(cache.guild(GuildId::new(1)).unwrap().emojis.is_empty());
+
+        // The member who joined the longest ago is evicted.
+        cache.update(&mut GuildMemberAddEvent {
+            member: joined(member(1, 5, &[1]), 40),
+        });
+        assert_eq!(members(1), [4, 5]);
+
+        // Members losing their last role are removed.
+        cache.update(&mut GuildMemberUpdateEvent {
+            guild_id: GuildId::new(1),
+            nick: None,
+            joined_at: Timestamp::from_unix_timestamp(30).unwrap(),
+            roles: vec![],
+            user: User {
+                id: UserId::new(4),
+                ..Default::default()
+            },
+            premium_since: None,
+            pending: false,
+            deaf: false,
+            mute: false,
+            avatar: None,
+            communication_disabled_until: None,
+            unusual_dm_activity_until: None,
+        });
+        assert_eq!(members(1), [5]);
+
+        let presence: Presence = crate::json::from_value(crate::json::json!({
+            "user": {"id": "5"},
+            "guild_id": "1",
+            "status": "online",
+            "activities": [],
+        }))
+        .unwrap();
+        cache.update(&mut PresenceUpdateEvent {
+            presence,
+        });
+        assert!(cache.guild(GuildId::new(1)).unwrap().presences.is_empty());
+        assert_eq!(cache.presences.len(), 0);
+    }
+
+    #[test]
+    fn test_cache_member_eviction_batches() {
+        let cache = Cache::new_with_settings(Settings {
+            max_members_per_guild: Some(20),
+            member_eviction: MemberEviction::OldestJoined,
+            ..Default::default()
+        });
+        cache.update(&mut GuildCreateEvent {
+            guild: guild(1, []),
+        });
+
+        for id in 2..=22 {
+            cache.update(&mut GuildMemberAddEvent {
+                member: Member {
+                    joined_at: Some(Timestamp::from_unix_timestamp(id as i64).unwrap()),
+                    ..member(1, id, &[])
+                },
+            });
+        }
+
+        // Exceeding the limit evicts a tenth of it at once, oldest members first.
+        let guild = cache.guild(GuildId::new(1)).unwrap();
+        assert_eq!(guild.members.len(), 18);
+        assert!((2..=4).all(|id| !guild.members.contains_key(&UserId::new(id))));
+    }
+
+    /// Checks the examples from https://discord.com/developers/docs/topics/permissions
+    #[test]
+    #[cfg(feature = "model")]
+    fn test_cache_permissions_in() {
+        let cache = Cache::new();
+        let guild_id = GuildId::new(1);
+
+        let role = |id, permissions| Role {
+            id: RoleId::new(id),
+            guild_id,
+            permissions,
+            ..Default::default()
+        };
+        let overwrite = |kind, allow, deny| PermissionOverwrite {
+            allow,
+            deny,
+            kind,
+        };
+        let everyone = PermissionOverwriteType::Role(RoleId::new(1));
+        let channel = |id, parent_id: Option<u64>, permission_overwrites| GuildChannel {
+            id: ChannelId::new(id),
+            guild_id,
+            parent_id: parent_id.map(ChannelId::new),
+            permission_overwrites,
+            ..Default::default()
+        };
+
+        let base = Permissions::VIEW_CHANNEL
+            | Permissions::READ_MESSAGE_HISTORY
+            | Permissions::SEND_MESSAGES
+            | Permissions::SEND_MESSAGES_IN_THREADS
+            | Permissions::ATTACH_FILES
+            | Permissions::MENTION_EVERYONE;
+        let mut timed_out = member(1, 24, &[]);
+        timed_out.communication_disabled_until =
+            Some(Timestamp::from_unix_timestamp(i64::from(u32::MAX)).unwrap());
+
+        cache.update(&mut GuildCreateEvent {
+            guild: Guild {
+                owner_id: UserId::new(99),
+                roles: HashMap::from([
+                    (RoleId::new(1), role(1, base)),
+                    (RoleId::new(2), role(2, Permissions::empty())),
+                    (RoleId::new(3), role(3, Permissions::ADMINISTRATOR)),
+                ]),
+                channels: HashMap::from([
+                    (
+                        ChannelId::new(10),
+                        channel(10, None, vec![
+                            overwrite(everyone, Permissions::empty(), Permissions::SEND_MESSAGES),
+                            overwrite(
+                                PermissionOverwriteType::Role(RoleId::new(2)),
+                                Permissions::SEND_MESSAGES,
+                                Permissions::empty(),
+                            ),
+                            overwrite(
+                                PermissionOverwriteType::Member(UserId::new(22)),
+                                Permissions::empty(),
+                                Permissions::SEND_MESSAGES,
+                            ),
+                        ]),
+                    ),
+                    (
+                        ChannelId::new(11),
+                        channel(11, None, vec![overwrite(
+                            everyone,
+                            Permissions::empty(),
+                            Permissions::VIEW_CHANNEL,
+                        )]),
+                    ),
+                ]),
+                threads: vec![channel(12, Some(10), vec![])],
+                ..guild(1, [
+                    member(1, 20, &[]),
+                    member(1, 21, &[2]),
+                    member(1, 22, &[2]),
+                    member(1, 23, &[3]),
+                    timed_out,
+                ])
+            },
+        });
+        let permissions = |channel_id, user_id| {
+            cache.permissions_in(ChannelId::new(channel_id), UserId::new(user_id)).unwrap()
+        };
+
+        // Role overwrites allowing permissions take precedence over @everyone overwrites denying
+        // them, and member overwrites take precedence over both.
+        assert!(permissions(10, 21).send_messages());
+        assert!(!permissions(10, 22).send_messages());
+
+        // Members who can't send messages can't mention everyone or attach files.
+        assert_eq!(
+            permissions(10, 20),
+            Permissions::VIEW_CHANNEL
+                | Permissions::READ_MESSAGE_HISTORY
+                | Permissions::SEND_MESSAGES_IN_THREADS
+        );
+
+        // Threads inherit the overwrites of their parent, but sending messages in them depends
+        // on Send Messages In Threads.
+        assert!(!permissions(12, 20).send_messages());
+        assert!(permissions(12, 20).attach_files());
+        assert!(!permissions(12, 21).send_messages());
+        assert!(permissions(12, 21).send_messages_in_threads());
+
+        // Members who can't view a channel have no permissions in it, unless they're
+        // administrators.
+        assert_eq!(permissions(11, 20), Permissions::empty());
+        assert_eq!(permissions(11, 23), Permissions::all());
+
+        // Timed out members can only view channels and read their history.
+        assert_eq!(
+            permissions(10, 24),
+            Permissions::VIEW_CHANNEL | Permissions::READ_MESSAGE_HISTORY
+        );
+
+        assert!(cache.permissions_in(ChannelId::new(13), UserId::new(20)).is_err());
+        assert!(cache.permissions_in(ChannelId::new(10), UserId::new(25)).is_err());
     }
 }
@@ -1,5 +1,9 @@
+use std::collections::{HashMap, HashSet};
 use std::time::Duration;
 
+use crate::model::guild::Member;
+use crate::model::id::{ChannelId, GuildId};
+
 /// Settings for the cache.
 ///
 /// # Examples
@@ -20,6 +24,27 @@ pub struct Settings {
     ///
     /// Defaults to 0.
     pub max_messages: usize,
+    /// Overrides of [`Self::max_messages`] for specific channels, for example to only cache the
+    /// messages of some channels by setting [`Self::max_messages`] to 0.
+    ///
+    /// Defaults to empty.
+    pub channel_max_messages: HashMap<ChannelId, usize>,
+    /// The maximum number of messages to store across all channels, or None for no limit. Once
+    /// the limit is reached, the oldest messages of the least recently active channels are
+    /// evicted first.
+    ///
+    /// Defaults to None.
+    pub max_total_messages: Option<usize>,
+    /// The maximum age of cached messages, based on their timestamp, or None for no limit.
+    ///
+    /// Older messages are evicted when a new message is cached, from the channel of that message
+    /// and from the least recently active channels. Call [`Cache::evict_expired_messages`] to
+    /// evict them from all channels.
+    ///
+    /// Defaults to None.
+    ///
+    /// [`Cache::evict_expired_messages`]: super::Cache::evict_expired_messages
+    pub max_message_age: Option<Duration>,
     /// How long temporarily-cached data should be stored before being thrown out.
     ///
     /// Defaults to one hour.
@@ -36,16 +61,128 @@ pub struct Settings {
     ///
     /// Defaults to true.
     pub cache_users: bool,
+    /// Whether to cache presence data received from gateway, both in the global presence cache
+    /// and in [`Guild::presences`]. Members are still cached if this is false.
+    ///
+    /// Defaults to true.
+    ///
+    /// [`Guild::presences`]: crate::model::guild::Guild::presences
+    pub cache_presences: bool,
+    /// Whether to cache the emojis of guilds.
+    ///
+    /// Defaults to true.
+    pub cache_emojis: bool,
+    /// Whether to cache the stickers of guilds.
+    ///
+    /// Defaults to true.
+    pub cache_stickers: bool,
+    /// Whether to cache the soundboard sounds of guilds.
+    ///
+    /// Defaults to true.
+    pub cache_soundboard_sounds: bool,
+    /// If set, members are only cached for the given guilds.
+    ///
+    /// The current user's members are always cached.
+    ///
+    /// Defaults to None.
+    pub member_guilds: Option<HashSet<GuildId>>,
+    /// Whether to only cache members who have at least one role. Members are removed from the
+    /// cache when they lose their last role.
+    ///
+    /// The current user's members are always cached.
+    ///
+    /// Defaults to false.
+    pub members_with_roles_only: bool,
+    /// The maximum number of members to cache per guild, or None for no limit. Once a guild's
+    /// members reach the limit, [`Self::member_eviction`] decides which members are kept.
+    ///
+    /// When members are evicted, a tenth of the limit is evicted at once, so that the members to
+    /// evict don't have to be looked for every time a member is cached.
+    ///
+    /// Defaults to None.
+    pub max_members_per_guild: Option<usize>,
+    /// Which members to evict once a guild reaches [`Self::max_members_per_guild`].
+    ///
+    /// Defaults to [`MemberEviction::KeepExisting`].
+    pub member_eviction: MemberEviction,
+    /// Whether to index the guilds each user is a cached member of, used by
+    /// [`Cache::user_guilds`].
+    ///
+    /// Defaults to false.
+    ///
+    /// [`Cache::user_guilds`]: super::Cache::user_guilds
+    pub index_user_guilds: bool,
+    /// Whether to index the cached members of each guild by role, used by
+    /// [`Cache::members_with_role`].
+    ///
+    /// Defaults to false.
+    ///
+    /// [`Cache::members_with_role`]: super::Cache::members_with_role
+    pub index_member_roles: bool,
+    /// Whether to index the cached members of each guild by lowercased username and nickname,
+    /// used by [`Cache::member_named`], [`Cache::search_members`] and the [`Member`] argument
+    /// converter.
+    ///
+    /// Defaults to false.
+    ///
+    /// [`Cache::member_named`]: super::Cache::member_named
+    /// [`Cache::search_members`]: super::Cache::search_members
+    pub index_member_names: bool,
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
             max_messages: 0,
+            channel_max_messages: HashMap::new(),
+            max_total_messages: None,
+            max_message_age: None,
             time_to_live: Duration::from_secs(60 * 60),
             cache_guilds: true,
             cache_channels: true,
             cache_users: true,
+            cache_presences: true,
+            cache_emojis: true,
+            cache_stickers: true,
+            cache_soundboard_sounds: true,
+            member_guilds: None,
+            members_with_roles_only: false,
+            max_members_per_guild: None,
+            member_eviction: MemberEviction::KeepExisting,
+            index_user_guilds: false,
+            index_member_roles: false,
+            index_member_names: false,
         }
     }
 }
+
+impl Settings {
+    /// The maximum number of messages to store in the given channel's message cache.
+    pub(crate) fn max_messages_in(&self, channel_id: ChannelId) -> usize {
+        self.channel_max_messages.get(&channel_id).copied().unwrap_or(self.max_messages)
+    }
+
+    /// Whether the member passes the filters on which members to cache, ignoring the limit of
+    /// members per guild.
+    pub(crate) fn caches_member(&self, member: &Member) -> bool {
+        self.member_guilds.as_ref().map_or(true, |guilds| guilds.contains(&member.guild_id))
+            && (!self.members_with_roles_only || !member.roles.is_empty())
+    }
+}
+
+/// Which members to evict from a guild once it reaches [`Settings::max_members_per_guild`].
+///
+/// The current user's member is never evicted.
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MemberEviction {
+    /// Keep the cached members, and don't cache any new member until some are removed.
+    #[default]
+    KeepExisting,
+    /// Evict the members who joined the guild the longest ago, so that recently joined members
+    /// are kept. Members whose join date is unknown are evicted first.
+    OldestJoined,
+    /// Evict any member, which is the cheapest policy.
+    Arbitrary,
+}
@@ -0,0 +1,249 @@
+use std::io::{Read, Write};
+
+use serde::ser::{Serialize, Serializer};
+use serde::Deserialize;
+
+use super::backend::{CacheBackend, CacheKey, CacheValue, DashMapBackend};
+use super::event::filter_guild;
+use super::index::index_guild;
+use super::wrappers::MaybeMap;
+use super::{Cache, Settings};
+use crate::internal::prelude::*;
+use crate::json;
+use crate::model::prelude::*;
+
+/// The version of the snapshot format, bumped whenever the format changes incompatibly.
+const SNAPSHOT_VERSION: u64 = 1;
+
+#[derive(serde::Serialize)]
+struct SnapshotRef<'a> {
+    version: u64,
+    user: &'a CurrentUser,
+    guilds: Values<'a, GuildId, Guild>,
+    unavailable_guilds: Vec<GuildId>,
+    users: Values<'a, UserId, User>,
+    presences: Values<'a, UserId, Presence>,
+}
+
+/// Everything but the version defaults to being empty, so that snapshots of other versions are
+/// still deserialized, and rejected afterwards.
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct Snapshot {
+    version: u64,
+    user: CurrentUser,
+    guilds: Vec<Guild>,
+    unavailable_guilds: Vec<GuildId>,
+    users: Vec<User>,
+    presences: Vec<Presence>,
+}
+
+/// Serializes the values of a map one at a time, without locking the whole map.
+struct Values<'a, K, V>(&'a MaybeMap<K, V>);
+
+impl<K: CacheKey, V: CacheValue> Serialize for Values<'_, K, V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter())
+    }
+}
+
+impl Cache {
+    /// Writes a snapshot of the cache's guilds, channels, users, presences and current user, so
+    /// that it may be restored with [`Self::restore`], for example after restarting the process.
+    ///
+    /// Each value is read separately, so the cache may keep being updated while the snapshot is
+    /// written. A guild, with its channels, members and roles, is always consistent on its own,
+    /// but the snapshot may contain a user updated after a guild it's a member of was written.
+    ///
+    /// Messages and temporarily cached data are not included.
+    ///
+    /// # Examples
+    ///
+    /// Save the cache when shutting down, and restore it when starting again:
+    ///
+    /// ```rust,no_run
+    /// use std::fs::File;
+    /// use std::io::{BufReader, BufWriter};
+    ///
+    /// use serenity::cache::{Cache, Settings};
+    ///
+    /// # fn run(cache: Cache) -> serenity::Result<()> {
+    /// cache.snapshot(BufWriter::new(File::create("cache.json")?))?;
+    ///
+    /// let reader = BufReader::new(File::open("cache.json")?);
+    /// let cache = Cache::restore(reader, Settings::default())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot could not be serialized or written.
+    pub fn snapshot(&self, mut writer: impl Write) -> Result<()> {
+        let user = self.user.read();
+        let snapshot = SnapshotRef {
+            version: SNAPSHOT_VERSION,
+            user: &user,
+            guilds: Values(&self.guilds),
+            unavailable_guilds: self.unavailable_guilds.keys(),
+            users: Values(&self.users),
+            presences: Values(&self.presences),
+        };
+
+        json::to_writer(&mut writer, &snapshot)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Creates a cache with the given settings from a snapshot written by [`Self::snapshot`].
+    ///
+    /// The channel and thread lookups are rebuilt from the restored guilds, and guilds which are
+    /// also restored as available are not considered unavailable. Data disabled by the settings,
+    /// such as users if [`Settings::cache_users`] is `false` or members filtered out by
+    /// [`Settings::member_guilds`], is skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Other`] if the snapshot's version is not supported by this version of the
+    /// library, or an error if it could not be read or deserialized.
+    pub fn restore(reader: impl Read, settings: Settings) -> Result<Self> {
+        Self::restore_with_backend(reader, settings, &DashMapBackend)
+    }
+
+    /// Creates a cache with the given settings and backend from a snapshot written by
+    /// [`Self::snapshot`]. Refer to [`Self::restore`] and [`Self::new_with_backend`] for more
+    /// information.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Other`] if the snapshot's version is not supported by this version of the
+    /// library, or an error if it could not be read or deserialized.
+    pub fn restore_with_backend(
+        reader: impl Read,
+        settings: Settings,
+        backend: &impl CacheBackend,
+    ) -> Result<Self> {
+        let snapshot: Snapshot = json::from_reader(reader)?;
+        if snapshot.version != SNAPSHOT_VERSION {
+            return Err(Error::Other("Unsupported cache snapshot version"));
+        }
+
+        let cache = Self::new_with_backend(settings, backend);
+        *cache.user.write() = snapshot.user;
+
+        for mut guild in snapshot.guilds {
+            filter_guild(&cache, &mut guild);
+            index_guild(&cache, &guild);
+            for channel_id in guild.channels.keys() {
+                cache.channels.insert(*channel_id, guild.id);
+            }
+            for thread in &guild.threads {
+                cache.threads.insert(thread.id, guild.id);
+            }
+
+            cache.guilds.insert(guild.id, guild);
+        }
+
+        for guild_id in snapshot.unavailable_guilds {
+            if !cache.guilds.contains(&guild_id) {
+                cache.unavailable_guilds.insert(guild_id, ());
+            }
+        }
+
+        for user in snapshot.users {
+            cache.users.insert(user.id, user);
+        }
+
+        for presence in snapshot.presences {
+            cache.presences.insert(presence.user.id, presence);
+        }
+
+        Ok(cache)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::FileBackend;
+
+    #[test]
+    fn snapshot_roundtrip() {
+        let cache = Cache::new();
+        cache.user.write().name = "bot".into();
+
+        let channel = GuildChannel {
+            id: ChannelId::new(2),
+            guild_id: GuildId::new(1),
+            name: "general".into(),
+            ..Default::default()
+        };
+        let user = User {
+            id: UserId::new(3),
+            name: "member".into(),
+            ..Default::default()
+        };
+        let member = Member {
+            user: user.clone(),
+            guild_id: GuildId::new(1),
+            nick: Some("nick".into()),
+            ..Default::default()
+        };
+
+        cache.update(&mut GuildCreateEvent {
+            guild: Guild {
+                id: GuildId::new(1),
+                name: "guild".into(),
+                channels: HashMap::from([(channel.id, channel)]),
+                members: HashMap::from([(user.id, member)]),
+                ..Default::default()
+            },
+        });
+        cache.unavailable_guilds.insert(GuildId::new(4), ());
+
+        let mut snapshot = Vec::new();
+        cache.snapshot(&mut snapshot).unwrap();
+        let restored = Cache::restore(snapshot.as_slice(), Settings::default()).unwrap();
+
+        assert_eq!(restored.current_user().name, "bot");
+        assert_eq!(restored.guilds(), cache.guilds());
+        assert_eq!(restored.guild_channel_count(), 1);
+        assert_eq!(restored.user(user.id).unwrap().name, "member");
+
+        let guild = restored.guild(GuildId::new(1)).unwrap();
+        assert_eq!(guild.name, "guild");
+        assert_eq!(guild.channels[&ChannelId::new(2)].name, "general");
+        assert_eq!(guild.members[&user.id].nick.as_deref(), Some("nick"));
+    }
+
+    #[test]
+    fn restore_into_backend() {
+        let cache = Cache::new();
+        cache.update(&mut GuildCreateEvent {
+            guild: Guild {
+                id: GuildId::new(1),
+                name: "guild".into(),
+                ..Default::default()
+            },
+        });
+
+        let mut snapshot = Vec::new();
+        cache.snapshot(&mut snapshot).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("serenity-snapshot-{}", std::process::id()));
+        let backend = FileBackend::new(&dir).unwrap();
+        Cache::restore_with_backend(snapshot.as_slice(), Settings::default(), &backend).unwrap();
+
+        let cache = Cache::new_with_backend(Settings::default(), &backend);
+        assert_eq!(cache.guild(GuildId::new(1)).unwrap().name, "guild");
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn restore_rejects_other_versions() {
+        let snapshot = br#"{"version": 0}"#;
+        let result = Cache::restore(snapshot.as_slice(), Settings::default());
+        assert!(matches!(result, Err(Error::Other(_))));
+    }
+}
@@ -1,40 +1,53 @@
 //! Wrappers around library types for easier use.
 
-use std::hash::Hash;
 #[cfg(feature = "temp_cache")]
 use std::sync::Arc;
 
-use dashmap::mapref::multiple::RefMulti;
-use dashmap::mapref::one::{Ref, RefMut};
-use dashmap::DashMap;
 #[cfg(feature = "typesize")]
 use typesize::TypeSize;
 
+use super::backend::{CacheBackend, CacheKey, CacheMap, CacheValue, StorageRef, StorageRefMut};
+
 #[derive(Debug)]
-/// A wrapper around Option<DashMap<K, V>> to ease disabling specific cache fields.
-pub(crate) struct MaybeMap<K: Eq + Hash, V>(pub(super) Option<DashMap<K, V, BuildHasher>>);
-impl<K: Eq + Hash, V> MaybeMap<K, V> {
-    pub fn iter(&self) -> impl Iterator<Item = RefMulti<'_, K, V, BuildHasher>> {
-        Option::iter(&self.0).flat_map(DashMap::iter)
+/// A wrapper around Option<CacheMap<K, V>> to ease disabling specific cache fields.
+pub(crate) struct MaybeMap<K, V>(pub(super) Option<Box<dyn CacheMap<K, V>>>);
+impl<K: CacheKey, V: CacheValue> MaybeMap<K, V> {
+    pub fn new(backend: &impl CacheBackend, name: &'static str, enabled: bool) -> Self {
+        Self(enabled.then(|| backend.map(name)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = StorageRef<'_, K, V>> {
+        self.as_read_only().into_iter()
+    }
+
+    pub fn keys(&self) -> Vec<K> {
+        self.0.as_ref().map(|m| m.keys()).unwrap_or_default()
     }
 
-    pub fn get(&self, k: &K) -> Option<Ref<'_, K, V, BuildHasher>> {
+    pub fn get(&self, k: &K) -> Option<StorageRef<'_, K, V>> {
         self.0.as_ref()?.get(k)
     }
 
-    pub fn get_mut(&self, k: &K) -> Option<RefMut<'_, K, V, BuildHasher>> {
+    pub fn get_mut(&self, k: &K) -> Option<StorageRefMut<'_, K, V>> {
         self.0.as_ref()?.get_mut(k)
     }
 
+    pub fn get_mut_or_default(&self, k: K) -> Option<StorageRefMut<'_, K, V>>
+    where
+        V: Default,
+    {
+        Some(self.0.as_ref()?.get_mut_or_insert_with(k, &V::default))
+    }
+
     pub fn contains(&self, k: &K) -> bool {
-        self.0.as_ref().is_some_and(|m| m.contains_key(k))
+        self.0.as_ref().is_some_and(|m| m.contains(k))
     }
 
     pub fn insert(&self, k: K, v: V) -> Option<V> {
         self.0.as_ref()?.insert(k, v)
     }
 
-    pub fn remove(&self, k: &K) -> Option<(K, V)> {
+    pub fn remove(&self, k: &K) -> Option<V> {
         self.0.as_ref()?.remove(k)
     }
 
@@ -49,38 +62,59 @@ impl<K: Eq + Hash, V> MaybeMap<K, V> {
     }
 
     pub(crate) fn as_read_only(&self) -> ReadOnlyMapRef<'_, K, V> {
-        ReadOnlyMapRef(self.0.as_ref())
+        ReadOnlyMapRef(self.0.as_deref())
     }
 }
 
 #[cfg(feature = "typesize")]
-impl<K: Eq + Hash + TypeSize, V: TypeSize> TypeSize for MaybeMap<K, V> {
+impl<K, V> TypeSize for MaybeMap<K, V> {
     fn extra_size(&self) -> usize {
-        self.0.as_ref().map(DashMap::extra_size).unwrap_or_default()
+        self.0.as_ref().map(|map| map.extra_size()).unwrap_or_default()
     }
 
     fn get_collection_item_count(&self) -> Option<usize> {
-        self.0.as_ref().and_then(DashMap::get_collection_item_count)
+        self.0.as_ref().map(|map| map.len())
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Debug)]
 /// A wrapper around a reference to a MaybeMap, allowing for public inspection of the underlying
 /// map without allowing mutation of internal cache fields, which could cause issues.
-pub struct ReadOnlyMapRef<'a, K: Eq + Hash, V>(Option<&'a DashMap<K, V, BuildHasher>>);
-impl<'a, K: Eq + Hash, V> ReadOnlyMapRef<'a, K, V> {
-    pub fn iter(&self) -> impl Iterator<Item = RefMulti<'_, K, V, BuildHasher>> {
-        self.0.into_iter().flat_map(DashMap::iter)
+pub struct ReadOnlyMapRef<'a, K, V>(Option<&'a dyn CacheMap<K, V>>);
+
+impl<K, V> Clone for ReadOnlyMapRef<'_, K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for ReadOnlyMapRef<'_, K, V> {}
+impl<'a, K: CacheKey, V: CacheValue> ReadOnlyMapRef<'a, K, V> {
+    pub fn iter(&self) -> impl Iterator<Item = StorageRef<'a, K, V>> {
+        self.into_iter()
     }
 
-    pub fn get(&self, k: &K) -> Option<Ref<'_, K, V, BuildHasher>> {
+    pub fn get(&self, k: &K) -> Option<StorageRef<'a, K, V>> {
         self.0?.get(k)
     }
 
     pub fn len(&self) -> usize {
-        self.0.map_or(0, DashMap::len)
+        self.0.map_or(0, |map| map.len())
     }
 }
+
+impl<'a, K: CacheKey, V: CacheValue> IntoIterator for ReadOnlyMapRef<'a, K, V> {
+    type Item = StorageRef<'a, K, V>;
+    type IntoIter = Box<dyn Iterator<Item = Self::Item> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self.0 {
+            Some(map) => map.iter(),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+}
+
 pub struct Hasher(fxhash::FxHasher);
 impl std::hash::Hasher for Hasher {
     fn finish(&self) -> u64 {
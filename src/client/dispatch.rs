@@ -6,15 +6,13 @@ use tracing::debug;
 use super::event_handler::{EventHandler, RawEventHandler};
 use super::{Context, FullEvent};
 #[cfg(feature = "cache")]
-use crate::cache::{Cache, CacheUpdate};
+use crate::cache::{Cache, CacheDiff, CacheUpdate};
 #[cfg(feature = "framework")]
 use crate::framework::Framework;
 use crate::internal::tokio::spawn_named;
 use crate::model::channel::ChannelType;
 use crate::model::event::Event;
 use crate::model::guild::Member;
-#[cfg(feature = "cache")]
-use crate::model::id::GuildId;
 
 #[cfg(feature = "cache")]
 macro_rules! if_cache {
@@ -42,6 +40,13 @@ macro_rules! update_cache {
     ($cache:ident, $event:ident) => {};
 }
 
+#[cfg(feature = "cache")]
+fn diff_event(diff: Option<CacheDiff>) -> Option<FullEvent> {
+    diff.map(|diff| FullEvent::CacheDiff {
+        diff,
+    })
+}
+
 pub(crate) fn dispatch_model(
     event: Event,
     context: &Context,
@@ -87,8 +92,9 @@ pub(crate) fn dispatch_model(
 
 /// Updates the cache with the incoming event data and builds the full event data out of it.
 ///
-/// Can return a secondary [`FullEvent`] for "virtual" events like [`FullEvent::CacheReady`] or
-/// [`FullEvent::ShardsReady`]. Secondary events are traditionally dispatched first.
+/// Can return a secondary [`FullEvent`] for "virtual" events like [`FullEvent::CacheReady`],
+/// [`FullEvent::ShardsReady`] or [`FullEvent::CacheDiff`]. Secondary events are traditionally
+/// dispatched first.
 ///
 /// Can return `None` if an event is unknown.
 #[cfg_attr(not(feature = "cache"), allow(unused_mut))]
@@ -147,6 +153,8 @@ fn update_cache_with_event(
         },
         Event::ChannelUpdate(mut event) => {
             let old_channel = if_cache!(event.update(cache));
+            extra_event =
+                if_cache!(diff_event(CacheDiff::channel(old_channel.as_ref(), &event.channel)));
 
             FullEvent::ChannelUpdate {
                 old: old_channel,
@@ -175,8 +183,7 @@ fn update_cache_with_event(
                 if cache.unavailable_guilds.len() == 0 {
                     cache.unavailable_guilds.shrink_to_fit();
 
-                    let guild_amount =
-                        cache.guilds.iter().map(|i| *i.key()).collect::<Vec<GuildId>>();
+                    let guild_amount = cache.guilds.keys();
 
                     extra_event = Some(FullEvent::CacheReady {
                         guilds: guild_amount,
@@ -198,7 +205,12 @@ fn update_cache_with_event(
             }
         },
         Event::GuildEmojisUpdate(mut event) => {
-            update_cache!(cache, event);
+            #[cfg(feature = "cache")]
+            {
+                let old = event.update(cache);
+                extra_event =
+                    diff_event(CacheDiff::emojis(event.guild_id, old.as_ref(), &event.emojis));
+            }
 
             FullEvent::GuildEmojisUpdate {
                 guild_id: event.guild_id,
@@ -230,6 +242,9 @@ fn update_cache_with_event(
                 let guild = cache.guild(event.guild_id);
                 guild.and_then(|g| g.members.get(&event.user.id).cloned())
             });
+            extra_event = if_cache!(after
+                .as_ref()
+                .and_then(|after| diff_event(CacheDiff::member(before.as_ref(), after))));
 
             FullEvent::GuildMemberUpdate {
                 old_if_available: before,
@@ -262,22 +277,74 @@ fn update_cache_with_event(
         },
         Event::GuildRoleUpdate(mut event) => {
             let before = if_cache!(event.update(cache));
+            extra_event = if_cache!(diff_event(CacheDiff::role(before.as_ref(), &event.role)));
 
             FullEvent::GuildRoleUpdate {
                 old_data_if_available: before,
                 new: event.role,
             }
         },
-        Event::GuildStickersUpdate(mut event) => {
+        Event::GuildSoundboardSoundCreate(mut event) => {
             update_cache!(cache, event);
 
+            FullEvent::GuildSoundboardSoundCreate {
+                sound: event.sound,
+            }
+        },
+        Event::GuildSoundboardSoundDelete(mut event) => {
+            let sound = if_cache!(event.update(cache));
+
+            FullEvent::GuildSoundboardSoundDelete {
+                guild_id: event.guild_id,
+                sound_id: event.sound_id,
+                removed_data_if_available: sound,
+            }
+        },
+        Event::GuildSoundboardSoundUpdate(mut event) => {
+            let before = if_cache!(event.update(cache));
+            extra_event =
+                if_cache!(diff_event(CacheDiff::soundboard_sound(before.as_ref(), &event.sound)));
+
+            FullEvent::GuildSoundboardSoundUpdate {
+                old_data_if_available: before,
+                new: event.sound,
+            }
+        },
+        Event::GuildSoundboardSoundsUpdate(mut event) => {
+            #[cfg(feature = "cache")]
+            {
+                let old = event.update(cache);
+                extra_event = diff_event(CacheDiff::soundboard_sounds(
+                    event.guild_id,
+                    old.as_deref(),
+                    &event.soundboard_sounds,
+                ));
+            }
+
+            FullEvent::GuildSoundboardSoundsUpdate {
+                guild_id: event.guild_id,
+                sounds: event.soundboard_sounds,
+            }
+        },
+        Event::GuildStickersUpdate(mut event) => {
+            #[cfg(feature = "cache")]
+            {
+                let old = event.update(cache);
+                extra_event =
+                    diff_event(CacheDiff::stickers(event.guild_id, old.as_ref(), &event.stickers));
+            }
+
             FullEvent::GuildStickersUpdate {
                 guild_id: event.guild_id,
                 current_state: event.stickers,
             }
         },
-        Event::GuildUpdate(event) => {
+        Event::GuildUpdate(mut event) => {
             let before = if_cache!(cache.guild(event.guild.id).map(|g| g.clone()));
+            update_cache!(cache, event);
+            extra_event = if_cache!(cache
+                .guild(event.guild.id)
+                .and_then(|after| diff_event(CacheDiff::guild(before.as_ref(), &after))));
 
             FullEvent::GuildUpdate {
                 old_data_if_available: before,
@@ -297,19 +364,32 @@ fn update_cache_with_event(
                 new_message: event.message,
             }
         },
-        Event::MessageDeleteBulk(event) => FullEvent::MessageDeleteBulk {
-            channel_id: event.channel_id,
-            multiple_deleted_messages_ids: event.ids,
-            guild_id: event.guild_id,
+        Event::MessageDeleteBulk(mut event) => {
+            let deleted_messages = if_cache!(event.update(cache));
+
+            FullEvent::MessageDeleteBulk {
+                channel_id: event.channel_id,
+                multiple_deleted_messages_ids: event.ids,
+                guild_id: event.guild_id,
+                deleted_messages_if_available: deleted_messages,
+            }
         },
-        Event::MessageDelete(event) => FullEvent::MessageDelete {
-            channel_id: event.channel_id,
-            deleted_message_id: event.message_id,
-            guild_id: event.guild_id,
+        Event::MessageDelete(mut event) => {
+            let deleted_message = if_cache!(event.update(cache));
+
+            FullEvent::MessageDelete {
+                channel_id: event.channel_id,
+                deleted_message_id: event.message_id,
+                guild_id: event.guild_id,
+                deleted_message_if_available: deleted_message,
+            }
         },
         Event::MessageUpdate(mut event) => {
             let before = if_cache!(event.update(cache));
             let after = if_cache!(cache.message(event.channel_id, event.id).map(|m| m.clone()));
+            extra_event = if_cache!(after
+                .as_ref()
+                .and_then(|after| diff_event(CacheDiff::message(before.as_ref(), after))));
 
             FullEvent::MessageUpdate {
                 old_if_available: before,
@@ -325,24 +405,44 @@ fn update_cache_with_event(
             }
         },
         Event::PresenceUpdate(mut event) => {
-            update_cache!(cache, event);
+            #[cfg(feature = "cache")]
+            {
+                let old = event.update(cache);
+                extra_event = diff_event(CacheDiff::presence(old.as_ref(), &event.presence));
+            }
 
             FullEvent::PresenceUpdate {
                 new_data: event.presence,
             }
         },
-        Event::ReactionAdd(event) => FullEvent::ReactionAdd {
-            add_reaction: event.reaction,
+        Event::ReactionAdd(mut event) => {
+            update_cache!(cache, event);
+
+            FullEvent::ReactionAdd {
+                add_reaction: event.reaction,
+            }
         },
-        Event::ReactionRemove(event) => FullEvent::ReactionRemove {
-            removed_reaction: event.reaction,
+        Event::ReactionRemove(mut event) => {
+            update_cache!(cache, event);
+
+            FullEvent::ReactionRemove {
+                removed_reaction: event.reaction,
+            }
         },
-        Event::ReactionRemoveAll(event) => FullEvent::ReactionRemoveAll {
-            channel_id: event.channel_id,
-            removed_from_message_id: event.message_id,
+        Event::ReactionRemoveAll(mut event) => {
+            update_cache!(cache, event);
+
+            FullEvent::ReactionRemoveAll {
+                channel_id: event.channel_id,
+                removed_from_message_id: event.message_id,
+            }
         },
-        Event::ReactionRemoveEmoji(event) => FullEvent::ReactionRemoveEmoji {
-            removed_reactions: event.reaction,
+        Event::ReactionRemoveEmoji(mut event) => {
+            update_cache!(cache, event);
+
+            FullEvent::ReactionRemoveEmoji {
+                removed_reactions: event.reaction,
+            }
         },
         Event::Ready(mut event) => {
             update_cache!(cache, event);
@@ -388,6 +488,8 @@ fn update_cache_with_event(
         },
         Event::VoiceStateUpdate(mut event) => {
             let before = if_cache!(event.update(cache));
+            extra_event =
+                if_cache!(diff_event(CacheDiff::voice_state(before.as_ref(), &event.voice_state)));
 
             FullEvent::VoiceStateUpdate {
                 old: before,
@@ -423,14 +525,31 @@ fn update_cache_with_event(
             guild_id: event.guild_id,
             application_id: event.application_id,
         },
-        Event::StageInstanceCreate(event) => FullEvent::StageInstanceCreate {
-            stage_instance: event.stage_instance,
+        Event::StageInstanceCreate(mut event) => {
+            update_cache!(cache, event);
+
+            FullEvent::StageInstanceCreate {
+                stage_instance: event.stage_instance,
+            }
         },
-        Event::StageInstanceUpdate(event) => FullEvent::StageInstanceUpdate {
-            stage_instance: event.stage_instance,
+        Event::StageInstanceUpdate(mut event) => {
+            #[cfg(feature = "cache")]
+            {
+                let old = event.update(cache);
+                extra_event =
+                    diff_event(CacheDiff::stage_instance(old.as_ref(), &event.stage_instance));
+            }
+
+            FullEvent::StageInstanceUpdate {
+                stage_instance: event.stage_instance,
+            }
         },
-        Event::StageInstanceDelete(event) => FullEvent::StageInstanceDelete {
-            stage_instance: event.stage_instance,
+        Event::StageInstanceDelete(mut event) => {
+            update_cache!(cache, event);
+
+            FullEvent::StageInstanceDelete {
+                stage_instance: event.stage_instance,
+            }
         },
         Event::ThreadCreate(mut event) => {
             update_cache!(cache, event);
@@ -441,6 +560,7 @@ fn update_cache_with_event(
         },
         Event::ThreadUpdate(mut event) => {
             let old = if_cache!(event.update(cache));
+            extra_event = if_cache!(diff_event(CacheDiff::thread(old.as_ref(), &event.thread)));
 
             FullEvent::ThreadUpdate {
                 old,
@@ -455,29 +575,61 @@ fn update_cache_with_event(
                 full_thread_data,
             }
         },
-        Event::ThreadListSync(event) => FullEvent::ThreadListSync {
-            thread_list_sync: event,
+        Event::ThreadListSync(mut event) => {
+            update_cache!(cache, event);
+
+            FullEvent::ThreadListSync {
+                thread_list_sync: event,
+            }
         },
         Event::ThreadMemberUpdate(event) => FullEvent::ThreadMemberUpdate {
             thread_member: event.member,
         },
-        Event::ThreadMembersUpdate(event) => FullEvent::ThreadMembersUpdate {
-            thread_members_update: event,
+        Event::ThreadMembersUpdate(mut event) => {
+            update_cache!(cache, event);
+
+            FullEvent::ThreadMembersUpdate {
+                thread_members_update: event,
+            }
         },
-        Event::GuildScheduledEventCreate(event) => FullEvent::GuildScheduledEventCreate {
-            event: event.event,
+        Event::GuildScheduledEventCreate(mut event) => {
+            update_cache!(cache, event);
+
+            FullEvent::GuildScheduledEventCreate {
+                event: event.event,
+            }
         },
-        Event::GuildScheduledEventUpdate(event) => FullEvent::GuildScheduledEventUpdate {
-            event: event.event,
+        Event::GuildScheduledEventUpdate(mut event) => {
+            #[cfg(feature = "cache")]
+            {
+                let old = event.update(cache);
+                extra_event = diff_event(CacheDiff::scheduled_event(old.as_ref(), &event.event));
+            }
+
+            FullEvent::GuildScheduledEventUpdate {
+                event: event.event,
+            }
         },
-        Event::GuildScheduledEventDelete(event) => FullEvent::GuildScheduledEventDelete {
-            event: event.event,
+        Event::GuildScheduledEventDelete(mut event) => {
+            update_cache!(cache, event);
+
+            FullEvent::GuildScheduledEventDelete {
+                event: event.event,
+            }
         },
-        Event::GuildScheduledEventUserAdd(event) => FullEvent::GuildScheduledEventUserAdd {
-            subscribed: event,
+        Event::GuildScheduledEventUserAdd(mut event) => {
+            update_cache!(cache, event);
+
+            FullEvent::GuildScheduledEventUserAdd {
+                subscribed: event,
+            }
         },
-        Event::GuildScheduledEventUserRemove(event) => FullEvent::GuildScheduledEventUserRemove {
-            unsubscribed: event,
+        Event::GuildScheduledEventUserRemove(mut event) => {
+            update_cache!(cache, event);
+
+            FullEvent::GuildScheduledEventUserRemove {
+                unsubscribed: event,
+            }
         },
         Event::EntitlementCreate(event) => FullEvent::EntitlementCreate {
             entitlement: event.entitlement,
@@ -488,6 +640,12 @@ fn update_cache_with_event(
         Event::EntitlementDelete(event) => FullEvent::EntitlementDelete {
             entitlement: event.entitlement,
         },
+        Event::MessagePollVoteAdd(event) => FullEvent::PollVoteAdd {
+            event,
+        },
+        Event::MessagePollVoteRemove(event) => FullEvent::PollVoteRemove {
+            event,
+        },
     };
 
     Some((event, extra_event))
@@ -1,6 +1,8 @@
 use async_trait::async_trait;
 
 use super::context::Context;
+#[cfg(feature = "cache")]
+use crate::cache::CacheDiff;
 use crate::gateway::ShardStageUpdateEvent;
 use crate::http::RatelimitInfo;
 use crate::model::prelude::*;
@@ -114,6 +116,14 @@ event_handler! {
     #[cfg(feature = "cache")]
     ShardsReady { total_shards: u32 } => async fn shards_ready(&self, ctx: Context);
 
+    /// Dispatched when the cache applied an update which changed cached data, alongside the
+    /// event for the update itself.
+    ///
+    /// Provides the changed fields, with their old and new values. Only dispatched if the old data
+    /// was cached.
+    #[cfg(feature = "cache")]
+    CacheDiff { diff: CacheDiff } => async fn cache_diff(&self, ctx: Context);
+
     /// Dispatched when a channel is created.
     ///
     /// Provides said channel's data.
@@ -236,6 +246,28 @@ event_handler! {
     /// available) and new data.
     GuildRoleUpdate { old_data_if_available: Option<Role>, new: Role } => async fn guild_role_update(&self, ctx: Context);
 
+    /// Dispatched when a soundboard sound is created.
+    ///
+    /// Provides the new sound's data.
+    GuildSoundboardSoundCreate { sound: SoundboardSound } => async fn guild_soundboard_sound_create(&self, ctx: Context);
+
+    /// Dispatched when a soundboard sound is deleted.
+    ///
+    /// Provides the guild's id, the sound's id and its data (if cache feature is enabled and the
+    /// data is available).
+    GuildSoundboardSoundDelete { guild_id: GuildId, sound_id: SoundId, removed_data_if_available: Option<SoundboardSound> } => async fn guild_soundboard_sound_delete(&self, ctx: Context);
+
+    /// Dispatched when a soundboard sound is updated.
+    ///
+    /// Provides the sound's old (if cache feature is enabled and the data is available) and new
+    /// data.
+    GuildSoundboardSoundUpdate { old_data_if_available: Option<SoundboardSound>, new: SoundboardSound } => async fn guild_soundboard_sound_update(&self, ctx: Context);
+
+    /// Dispatched when multiple soundboard sounds are updated at once.
+    ///
+    /// Provides the guild's id and the new data of the updated sounds.
+    GuildSoundboardSoundsUpdate { guild_id: GuildId, sounds: HashMap<SoundId, SoundboardSound> } => async fn guild_soundboard_sounds_update(&self, ctx: Context);
+
     /// Dispatched when the stickers are updated.
     ///
     /// Provides the guild's id and the new state of the stickers in the guild.
@@ -264,13 +296,15 @@ event_handler! {
 
     /// Dispatched when a message is deleted.
     ///
-    /// Provides the guild's id, the channel's id and the message's id.
-    MessageDelete { channel_id: ChannelId, deleted_message_id: MessageId, guild_id: Option<GuildId> } => async fn message_delete(&self, ctx: Context);
+    /// Provides the guild's id, the channel's id and the message's id, as well as the deleted
+    /// message if cache feature is enabled and the message was cached.
+    MessageDelete { channel_id: ChannelId, deleted_message_id: MessageId, guild_id: Option<GuildId>, deleted_message_if_available: Option<Message> } => async fn message_delete(&self, ctx: Context);
 
     /// Dispatched when multiple messages were deleted at once.
     ///
-    /// Provides the guild's id, channel's id and the deleted messages' ids.
-    MessageDeleteBulk { channel_id: ChannelId, multiple_deleted_messages_ids: Vec<MessageId>, guild_id: Option<GuildId> } => async fn message_delete_bulk(&self, ctx: Context);
+    /// Provides the guild's id, channel's id and the deleted messages' ids, as well as those of
+    /// the deleted messages which were cached if cache feature is enabled.
+    MessageDeleteBulk { channel_id: ChannelId, multiple_deleted_messages_ids: Vec<MessageId>, guild_id: Option<GuildId>, deleted_messages_if_available: Option<Vec<Message>> } => async fn message_delete_bulk(&self, ctx: Context);
 
     /// Dispatched when a message is updated.
     ///
@@ -468,6 +502,16 @@ event_handler! {
     /// be set.
     EntitlementDelete { entitlement: Entitlement } => async fn entitlement_delete(&self, ctx: Context);
 
+    /// Dispatched when a user votes for an answer of a poll.
+    ///
+    /// Provides data about the vote.
+    PollVoteAdd { event: MessagePollVoteAddEvent } => async fn poll_vote_add(&self, ctx: Context);
+
+    /// Dispatched when a user removes their vote for an answer of a poll.
+    ///
+    /// Provides data about the removed vote.
+    PollVoteRemove { event: MessagePollVoteRemoveEvent } => async fn poll_vote_remove(&self, ctx: Context);
+
     /// Dispatched when an HTTP rate limit is hit
     Ratelimit { data: RatelimitInfo } => async fn ratelimit(&self);
 }
@@ -22,6 +22,7 @@ mod error;
 mod event_handler;
 
 use std::future::IntoFuture;
+use std::num::NonZeroU16;
 use std::ops::Range;
 use std::sync::Arc;
 #[cfg(feature = "framework")]
@@ -48,7 +49,13 @@ use crate::cache::Settings as CacheSettings;
 use crate::framework::Framework;
 #[cfg(feature = "voice")]
 use crate::gateway::VoiceGatewayManager;
-use crate::gateway::{ActivityData, PresenceData};
+use crate::gateway::{
+    ActivityData,
+    IdentifyQueue,
+    PresenceData,
+    ShardSessionState,
+    TransportCompression,
+};
 #[cfg(feature = "gateway")]
 use crate::gateway::{ShardManager, ShardManagerOptions};
 use crate::http::Http;
@@ -67,6 +74,8 @@ pub struct ClientBuilder {
     intents: GatewayIntents,
     #[cfg(feature = "cache")]
     cache_settings: CacheSettings,
+    #[cfg(feature = "cache")]
+    cache: Option<Arc<Cache>>,
     #[cfg(feature = "framework")]
     framework: Option<Box<dyn Framework>>,
     #[cfg(feature = "voice")]
@@ -74,6 +83,9 @@ pub struct ClientBuilder {
     event_handlers: Vec<Arc<dyn EventHandler>>,
     raw_event_handlers: Vec<Arc<dyn RawEventHandler>>,
     presence: PresenceData,
+    identify_queue: Option<Arc<dyn IdentifyQueue>>,
+    compression: TransportCompression,
+    session_states: Vec<ShardSessionState>,
 }
 
 #[cfg(feature = "gateway")]
@@ -85,6 +97,8 @@ impl ClientBuilder {
             intents,
             #[cfg(feature = "cache")]
             cache_settings: CacheSettings::default(),
+            #[cfg(feature = "cache")]
+            cache: None,
             #[cfg(feature = "framework")]
             framework: None,
             #[cfg(feature = "voice")]
@@ -92,6 +106,9 @@ impl ClientBuilder {
             event_handlers: vec![],
             raw_event_handlers: vec![],
             presence: PresenceData::default(),
+            identify_queue: None,
+            compression: TransportCompression::default(),
+            session_states: vec![],
         }
     }
 
@@ -179,6 +196,16 @@ impl ClientBuilder {
         &self.cache_settings
     }
 
+    /// Uses the given cache, such as one created with [`Cache::new_with_backend`], instead of
+    /// creating one from the [cache settings].
+    ///
+    /// [cache settings]: Self::cache_settings
+    #[cfg(feature = "cache")]
+    pub fn cache(mut self, cache: Arc<Cache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
     /// Sets the command framework to be used. It will receive messages sent over the gateway and
     /// then consider - based on its settings - whether to dispatch a command.
     ///
@@ -320,6 +347,49 @@ impl ClientBuilder {
     pub fn get_presence(&self) -> &PresenceData {
         &self.presence
     }
+
+    /// Sets the queue deciding when each shard may identify. Use this to share the identify
+    /// ratelimit between several processes, for example with a [`RemoteIdentifyQueue`].
+    ///
+    /// If not set, shards are only ratelimited within this process.
+    ///
+    /// [`RemoteIdentifyQueue`]: crate::gateway::RemoteIdentifyQueue
+    pub fn identify_queue(mut self, identify_queue: Arc<dyn IdentifyQueue>) -> Self {
+        self.identify_queue = Some(identify_queue);
+
+        self
+    }
+
+    /// Gets the identify queue, if set. See [`Self::identify_queue`] for more info.
+    pub fn get_identify_queue(&self) -> Option<Arc<dyn IdentifyQueue>> {
+        self.identify_queue.clone()
+    }
+
+    /// Sets the compression to request for gateway connections. Refer to
+    /// [`TransportCompression`] for more information.
+    pub fn transport_compression(mut self, compression: TransportCompression) -> Self {
+        self.compression = compression;
+
+        self
+    }
+
+    /// Gets the transport compression. See [`Self::transport_compression`] for more info.
+    pub fn get_transport_compression(&self) -> TransportCompression {
+        self.compression
+    }
+
+    /// Sets the sessions of a previous process that shards should resume instead of identifying,
+    /// as returned by [`ShardManager::shutdown_all_resumable`].
+    pub fn session_states(mut self, session_states: Vec<ShardSessionState>) -> Self {
+        self.session_states = session_states;
+
+        self
+    }
+
+    /// Gets the sessions to resume. See [`Self::session_states`] for more info.
+    pub fn get_session_states(&self) -> &[ShardSessionState] {
+        &self.session_states
+    }
 }
 
 #[cfg(feature = "gateway")]
@@ -337,6 +407,9 @@ impl IntoFuture for ClientBuilder {
         let raw_event_handlers = self.raw_event_handlers;
         let intents = self.intents;
         let presence = self.presence;
+        let identify_queue = self.identify_queue;
+        let compression = self.compression;
+        let session_states = self.session_states;
 
         let mut http = self.http;
 
@@ -356,16 +429,28 @@ impl IntoFuture for ClientBuilder {
         let voice_manager = self.voice_manager;
 
         #[cfg(feature = "cache")]
-        let cache = Arc::new(Cache::new_with_settings(self.cache_settings));
+        let cache = match self.cache {
+            Some(cache) => cache,
+            None => Arc::new(Cache::new_with_settings(self.cache_settings)),
+        };
 
         Box::pin(async move {
-            let ws_url = Arc::new(Mutex::new(match http.get_gateway().await {
-                Ok(response) => response.url,
+            let (ws_url, max_concurrency) = match http.get_bot_gateway().await {
+                Ok(response) => {
+                    let max_concurrency =
+                        u16::try_from(response.session_start_limit.max_concurrency)
+                            .ok()
+                            .and_then(NonZeroU16::new)
+                            .unwrap_or(NonZeroU16::MIN);
+
+                    (response.url, max_concurrency)
+                },
                 Err(err) => {
                     tracing::warn!("HTTP request to get gateway URL failed: {}", err);
-                    "wss://gateway.discord.gg".to_string()
+                    ("wss://gateway.discord.gg".to_string(), NonZeroU16::MIN)
                 },
-            }));
+            };
+            let ws_url = Arc::new(Mutex::new(ws_url));
 
             #[cfg(feature = "framework")]
             let framework_cell = Arc::new(OnceLock::new());
@@ -378,6 +463,8 @@ impl IntoFuture for ClientBuilder {
                 shard_index: 0,
                 shard_init: 0,
                 shard_total: 0,
+                max_concurrency,
+                identify_queue,
                 #[cfg(feature = "voice")]
                 voice_manager: voice_manager.clone(),
                 ws_url: Arc::clone(&ws_url),
@@ -386,6 +473,8 @@ impl IntoFuture for ClientBuilder {
                 http: Arc::clone(&http),
                 intents,
                 presence: Some(presence),
+                compression,
+                session_states,
             });
 
             let client = Client {
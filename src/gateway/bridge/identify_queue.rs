@@ -0,0 +1,331 @@
+use std::fmt;
+use std::io::{Error as IoError, ErrorKind};
+use std::num::NonZeroU16;
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::Mutex;
+use tokio::time::{sleep_until, Duration, Instant};
+use tracing::{debug, instrument, warn};
+
+use super::shard_queuer::WAIT_BETWEEN_BOOTS_IN_SECONDS;
+use crate::internal::prelude::*;
+use crate::internal::tokio::spawn_named;
+use crate::model::gateway::ShardInfo;
+use crate::model::id::ShardId;
+
+/// A coordinator deciding when a shard is allowed to send an IDENTIFY to the gateway.
+///
+/// The [`ShardQueuer`] awaits [`Self::wait_for_identify`] before starting each shard, which is
+/// followed by the shard identifying. By default, a [`LocalIdentifyQueue`] is used, which only
+/// ratelimits the shards of the current process. If shards are split across several processes
+/// using the same token, such as with [`Client::start_shard_range`], all processes should share
+/// one queue, for example through an [`IdentifyQueueServer`] and [`RemoteIdentifyQueue`]s.
+///
+/// [`ShardQueuer`]: super::ShardQueuer
+/// [`Client::start_shard_range`]: crate::Client::start_shard_range
+#[async_trait]
+pub trait IdentifyQueue: Send + Sync {
+    /// Waits until the given shard may identify.
+    ///
+    /// Implementations must only return once the shard has been accounted for in the identify
+    /// ratelimit, as the shard will identify right after.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the queue could not be reached. The shard will then be requeued.
+    async fn wait_for_identify(&self, shard_info: ShardInfo) -> Result<()>;
+}
+
+/// An in-process [`IdentifyQueue`], allowing one shard per identify bucket to identify every 5
+/// seconds.
+///
+/// The identify bucket of a shard is `shard_id % max_concurrency`.
+pub struct LocalIdentifyQueue {
+    buckets: Vec<Mutex<Option<Instant>>>,
+}
+
+impl LocalIdentifyQueue {
+    /// Creates a new queue with `max_concurrency` identify buckets, as given by
+    /// [`SessionStartLimit::max_concurrency`].
+    ///
+    /// [`SessionStartLimit::max_concurrency`]: crate::model::gateway::SessionStartLimit::max_concurrency
+    #[must_use]
+    pub fn new(max_concurrency: NonZeroU16) -> Self {
+        let buckets = (0..max_concurrency.get()).map(|_| Mutex::new(None)).collect();
+
+        Self {
+            buckets,
+        }
+    }
+}
+
+impl fmt::Debug for LocalIdentifyQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalIdentifyQueue").field("max_concurrency", &self.buckets.len()).finish()
+    }
+}
+
+#[async_trait]
+impl IdentifyQueue for LocalIdentifyQueue {
+    #[instrument(skip(self))]
+    async fn wait_for_identify(&self, shard_info: ShardInfo) -> Result<()> {
+        let index = shard_info.id.0 as usize % self.buckets.len();
+
+        // The lock is held while sleeping, so that shards of the same bucket identify in the order
+        // they started waiting.
+        let mut last_identify = self.buckets[index].lock().await;
+
+        if let Some(instant) = *last_identify {
+            sleep_until(instant + Duration::from_secs(WAIT_BETWEEN_BOOTS_IN_SECONDS)).await;
+        }
+
+        *last_identify = Some(Instant::now());
+
+        Ok(())
+    }
+}
+
+/// Serves an [`IdentifyQueue`] over a local TCP or Unix socket, to be used by
+/// [`RemoteIdentifyQueue`]s in other processes.
+///
+/// # Examples
+///
+/// Sharing the identify ratelimit of a bot with a maximum concurrency of 1 between processes:
+///
+/// ```rust,no_run
+/// use std::num::NonZeroU16;
+/// use std::sync::Arc;
+///
+/// use serenity::gateway::{IdentifyQueueServer, LocalIdentifyQueue};
+/// use tokio::net::TcpListener;
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let queue = Arc::new(LocalIdentifyQueue::new(NonZeroU16::MIN));
+/// let listener = TcpListener::bind("127.0.0.1:7654").await?;
+///
+/// IdentifyQueueServer::new(queue).serve_tcp(listener).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct IdentifyQueueServer {
+    queue: Arc<dyn IdentifyQueue>,
+}
+
+impl IdentifyQueueServer {
+    /// Creates a server handing out identifies from the given queue.
+    #[must_use]
+    pub fn new(queue: Arc<dyn IdentifyQueue>) -> Self {
+        Self {
+            queue,
+        }
+    }
+
+    /// Accepts connections on the given TCP listener until an error occurs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if accepting a connection failed.
+    pub async fn serve_tcp(&self, listener: TcpListener) -> Result<()> {
+        loop {
+            let (stream, addr) = listener.accept().await?;
+            debug!("[Identify Queue] Accepted connection from {}", addr);
+
+            self.spawn_connection(stream);
+        }
+    }
+
+    /// Accepts connections on the given Unix listener until an error occurs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if accepting a connection failed.
+    #[cfg(unix)]
+    pub async fn serve_unix(&self, listener: UnixListener) -> Result<()> {
+        loop {
+            let (stream, _) = listener.accept().await?;
+            debug!("[Identify Queue] Accepted connection");
+
+            self.spawn_connection(stream);
+        }
+    }
+
+    fn spawn_connection<S>(&self, stream: S)
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let queue = Arc::clone(&self.queue);
+
+        spawn_named("identify_queue::connection", async move {
+            if let Err(why) = handle_connection(&*queue, stream).await {
+                warn!("[Identify Queue] Err handling connection: {:?}", why);
+            }
+        });
+    }
+}
+
+impl fmt::Debug for IdentifyQueueServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdentifyQueueServer").finish_non_exhaustive()
+    }
+}
+
+/// An [`IdentifyQueue`] which asks an [`IdentifyQueueServer`] over a local TCP or Unix socket
+/// whether a shard may identify.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+///
+/// use serenity::gateway::RemoteIdentifyQueue;
+/// use serenity::prelude::*;
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let token = std::env::var("DISCORD_TOKEN")?;
+/// let mut client = Client::builder(&token, GatewayIntents::default())
+///     .identify_queue(Arc::new(RemoteIdentifyQueue::tcp("127.0.0.1:7654")))
+///     .await?;
+///
+/// client.start_shard_range(0..8, 16).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct RemoteIdentifyQueue {
+    addr: RemoteAddr,
+}
+
+#[derive(Clone, Debug)]
+enum RemoteAddr {
+    Tcp(String),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl RemoteIdentifyQueue {
+    /// Creates a queue connecting to an [`IdentifyQueueServer`] on the given TCP address.
+    #[must_use]
+    pub fn tcp(addr: impl Into<String>) -> Self {
+        Self {
+            addr: RemoteAddr::Tcp(addr.into()),
+        }
+    }
+
+    /// Creates a queue connecting to an [`IdentifyQueueServer`] on the given Unix socket path.
+    #[cfg(unix)]
+    #[must_use]
+    pub fn unix(path: impl Into<PathBuf>) -> Self {
+        Self {
+            addr: RemoteAddr::Unix(path.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl IdentifyQueue for RemoteIdentifyQueue {
+    #[instrument(skip(self))]
+    async fn wait_for_identify(&self, shard_info: ShardInfo) -> Result<()> {
+        match &self.addr {
+            RemoteAddr::Tcp(addr) => {
+                request_identify(TcpStream::connect(addr).await?, shard_info).await
+            },
+            #[cfg(unix)]
+            RemoteAddr::Unix(path) => {
+                request_identify(UnixStream::connect(path).await?, shard_info).await
+            },
+        }
+    }
+}
+
+// The protocol is line based: the client sends `<shard id> <shard total>\n` and the server replies
+// with `\n` once that shard may identify. A connection can be reused for several requests.
+async fn request_identify<S>(stream: S, shard_info: ShardInfo) -> Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut stream = BufReader::new(stream);
+    let request = format!("{} {}\n", shard_info.id.0, shard_info.total);
+    stream.write_all(request.as_bytes()).await?;
+    stream.flush().await?;
+
+    let mut reply = String::new();
+    if stream.read_line(&mut reply).await? == 0 {
+        return Err(IoError::from(ErrorKind::UnexpectedEof).into());
+    }
+
+    Ok(())
+}
+
+async fn handle_connection<S>(queue: &dyn IdentifyQueue, stream: S) -> Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut stream = BufReader::new(stream);
+    let mut request = String::new();
+
+    while stream.read_line(&mut request).await? != 0 {
+        let shard_info = parse_request(&request).ok_or_else(|| {
+            IoError::new(ErrorKind::InvalidData, format!("invalid identify request: {request:?}"))
+        })?;
+
+        queue.wait_for_identify(shard_info).await?;
+        stream.write_all(b"\n").await?;
+        stream.flush().await?;
+
+        request.clear();
+    }
+
+    Ok(())
+}
+
+fn parse_request(request: &str) -> Option<ShardInfo> {
+    let (id, total) = request.trim_end().split_once(' ')?;
+
+    Some(ShardInfo::new(ShardId(id.parse().ok()?), total.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU16;
+    use std::sync::Arc;
+
+    use tokio::net::TcpListener;
+    use tokio::time::{Duration, Instant};
+
+    use super::*;
+
+    #[test]
+    fn request_parsing() {
+        let shard_info = parse_request("3 16\n").unwrap();
+        assert_eq!(shard_info.id, ShardId(3));
+        assert_eq!(shard_info.total, 16);
+
+        assert!(parse_request("3\n").is_none());
+        assert!(parse_request("a 16\n").is_none());
+    }
+
+    #[tokio::test]
+    async fn remote_queue_identifies_through_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let queue = Arc::new(LocalIdentifyQueue::new(NonZeroU16::new(2).unwrap()));
+        let server = IdentifyQueueServer::new(queue);
+        tokio::spawn(async move { server.serve_tcp(listener).await });
+
+        // Shards in different buckets may identify at the same time.
+        let remote = RemoteIdentifyQueue::tcp(addr.to_string());
+        let start = Instant::now();
+        remote.wait_for_identify(ShardInfo::new(ShardId(0), 2)).await.unwrap();
+        remote.wait_for_identify(ShardInfo::new(ShardId(1), 2)).await.unwrap();
+        assert!(start.elapsed() < Duration::from_secs(WAIT_BETWEEN_BOOTS_IN_SECONDS));
+    }
+}
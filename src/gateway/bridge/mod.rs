@@ -41,6 +41,7 @@
 //! [`Shard`]: crate::gateway::Shard
 
 mod event;
+mod identify_queue;
 mod shard_manager;
 mod shard_messenger;
 mod shard_queuer;
@@ -53,9 +54,15 @@ use std::fmt;
 use std::time::Duration as StdDuration;
 
 pub use self::event::ShardStageUpdateEvent;
+pub use self::identify_queue::{
+    IdentifyQueue,
+    IdentifyQueueServer,
+    LocalIdentifyQueue,
+    RemoteIdentifyQueue,
+};
 pub use self::shard_manager::{ShardManager, ShardManagerOptions};
 pub use self::shard_messenger::ShardMessenger;
-pub use self::shard_queuer::ShardQueuer;
+pub use self::shard_queuer::{ShardQueue, ShardQueuer};
 pub use self::shard_runner::{ShardRunner, ShardRunnerOptions};
 pub use self::shard_runner_message::ShardRunnerMessage;
 #[cfg(feature = "voice")]
@@ -1,4 +1,5 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::HashMap;
+use std::num::NonZeroU16;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 #[cfg(feature = "framework")]
@@ -14,13 +15,27 @@ use typemap_rev::TypeMap;
 
 #[cfg(feature = "voice")]
 use super::VoiceGatewayManager;
-use super::{ShardId, ShardQueuer, ShardQueuerMessage, ShardRunnerInfo};
+use super::{
+    IdentifyQueue,
+    LocalIdentifyQueue,
+    ShardId,
+    ShardQueue,
+    ShardQueuer,
+    ShardQueuerMessage,
+    ShardRunnerInfo,
+};
 #[cfg(feature = "cache")]
 use crate::cache::Cache;
 use crate::client::{EventHandler, RawEventHandler};
 #[cfg(feature = "framework")]
 use crate::framework::Framework;
-use crate::gateway::{ConnectionStage, GatewayError, PresenceData};
+use crate::gateway::{
+    ConnectionStage,
+    GatewayError,
+    PresenceData,
+    ShardSessionState,
+    TransportCompression,
+};
 use crate::http::Http;
 use crate::internal::prelude::*;
 use crate::internal::tokio::spawn_named;
@@ -49,11 +64,12 @@ use crate::model::gateway::GatewayIntents;
 /// # async fn run() -> Result<(), Box<dyn Error>> {
 /// #
 /// use std::env;
+/// use std::num::NonZeroU16;
 /// use std::sync::{Arc, OnceLock};
 ///
 /// use serenity::client::{EventHandler, RawEventHandler};
 /// use serenity::framework::{Framework, StandardFramework};
-/// use serenity::gateway::{ShardManager, ShardManagerOptions};
+/// use serenity::gateway::{ShardManager, ShardManagerOptions, TransportCompression};
 /// use serenity::http::Http;
 /// use serenity::model::gateway::GatewayIntents;
 /// use serenity::prelude::*;
@@ -65,7 +81,12 @@ use crate::model::gateway::GatewayIntents;
 /// impl RawEventHandler for Handler {}
 ///
 /// # let http: Arc<Http> = unimplemented!();
-/// let ws_url = Arc::new(Mutex::new(http.get_gateway().await?.url));
+/// let gateway = http.get_bot_gateway().await?;
+/// let ws_url = Arc::new(Mutex::new(gateway.url));
+/// let max_concurrency = u16::try_from(gateway.session_start_limit.max_concurrency)
+///     .ok()
+///     .and_then(NonZeroU16::new)
+///     .unwrap_or(NonZeroU16::MIN);
 /// let data = Arc::new(RwLock::new(TypeMap::new()));
 /// let event_handler = Arc::new(Handler) as Arc<dyn EventHandler>;
 /// let framework = Arc::new(StandardFramework::new()) as Arc<dyn Framework + 'static>;
@@ -81,6 +102,10 @@ use crate::model::gateway::GatewayIntents;
 ///     shard_init: 3,
 ///     // the total number of shards in use
 ///     shard_total: 5,
+///     // the number of shards that may identify at the same time
+///     max_concurrency,
+///     // use the default in-process identify ratelimiting
+///     identify_queue: None,
 ///     # #[cfg(feature = "voice")]
 ///     # voice_manager: None,
 ///     ws_url,
@@ -89,6 +114,9 @@ use crate::model::gateway::GatewayIntents;
 ///     # http,
 ///     intents: GatewayIntents::non_privileged(),
 ///     presence: None,
+///     compression: TransportCompression::Payload,
+///     // sessions of a previous process to resume
+///     session_states: vec![],
 /// });
 /// # Ok(())
 /// # }
@@ -116,6 +144,8 @@ pub struct ShardManager {
     shard_shutdown: Mutex<Receiver<ShardId>>,
     shard_shutdown_send: Sender<ShardId>,
     gateway_intents: GatewayIntents,
+    /// The sessions of shards that were shut down without invalidating them.
+    session_states: Mutex<HashMap<ShardId, ShardSessionState>>,
 }
 
 impl ShardManager {
@@ -139,6 +169,7 @@ impl ShardManager {
             shard_shutdown_send: shutdown_send,
             runners: Arc::clone(&runners),
             gateway_intents: opt.intents,
+            session_states: Mutex::new(HashMap::new()),
         });
 
         let mut shard_queuer = ShardQueuer {
@@ -148,8 +179,11 @@ impl ShardManager {
             #[cfg(feature = "framework")]
             framework: opt.framework,
             last_start: None,
+            identify_queue: opt
+                .identify_queue
+                .unwrap_or_else(|| Arc::new(LocalIdentifyQueue::new(opt.max_concurrency))),
             manager: Arc::clone(&manager),
-            queue: VecDeque::new(),
+            queue: ShardQueue::new(opt.max_concurrency),
             runners,
             rx: shard_queue_rx,
             #[cfg(feature = "voice")]
@@ -160,6 +194,12 @@ impl ShardManager {
             http: opt.http,
             intents: opt.intents,
             presence: opt.presence,
+            compression: opt.compression,
+            session_states: opt
+                .session_states
+                .into_iter()
+                .map(|state| (state.shard_info.id, state))
+                .collect(),
         };
 
         spawn_named("shard_queuer::run", async move {
@@ -301,6 +341,24 @@ impl ShardManager {
     /// [`Self::shutdown`] method.
     #[instrument(skip(self))]
     pub async fn shutdown_all(&self) {
+        self.shutdown_all_with_code(1000).await;
+    }
+
+    /// Shuts down all shards like [`Self::shutdown_all`], but without invalidating their sessions,
+    /// and returns the state of these sessions.
+    ///
+    /// The returned sessions can be passed to [`ShardManagerOptions::session_states`] of a new
+    /// process, which will then resume them instead of identifying all shards again. Discord only
+    /// allows resuming a session for a short time after it was closed.
+    #[instrument(skip(self))]
+    pub async fn shutdown_all_resumable(&self) -> Vec<ShardSessionState> {
+        // Discord invalidates sessions closed with 1000 or 1001.
+        self.shutdown_all_with_code(4000).await;
+
+        self.session_states.lock().await.drain().map(|(_, state)| state).collect()
+    }
+
+    async fn shutdown_all_with_code(&self, code: u16) {
         let keys = {
             let runners = self.runners.lock().await;
 
@@ -314,7 +372,7 @@ impl ShardManager {
         info!("Shutting down all shards");
 
         for shard_id in keys {
-            self.shutdown(shard_id, 1000).await;
+            self.shutdown(shard_id, code).await;
         }
 
         drop(self.shard_queuer.unbounded_send(ShardQueuerMessage::Shutdown));
@@ -345,6 +403,21 @@ impl ShardManager {
         }
     }
 
+    /// Stores the session of a shard that is shutting down, or removes it if the session can't be
+    /// resumed.
+    pub(super) async fn set_session_state(
+        &self,
+        id: ShardId,
+        session_state: Option<ShardSessionState>,
+    ) {
+        let mut session_states = self.session_states.lock().await;
+
+        match session_state {
+            Some(session_state) => session_states.insert(id, session_state),
+            None => session_states.remove(&id),
+        };
+    }
+
     pub fn shutdown_finished(&self, id: ShardId) {
         if let Err(e) = self.shard_shutdown_send.unbounded_send(id) {
             tracing::warn!("failed to notify about finished shutdown: {}", e);
@@ -392,6 +465,14 @@ pub struct ShardManagerOptions {
     pub shard_index: u32,
     pub shard_init: u32,
     pub shard_total: u32,
+    /// The number of shards that may identify at the same time, as given by
+    /// [`SessionStartLimit::max_concurrency`].
+    ///
+    /// [`SessionStartLimit::max_concurrency`]: crate::model::gateway::SessionStartLimit::max_concurrency
+    pub max_concurrency: NonZeroU16,
+    /// The queue deciding when each shard may identify. Defaults to a [`LocalIdentifyQueue`] using
+    /// [`Self::max_concurrency`].
+    pub identify_queue: Option<Arc<dyn IdentifyQueue>>,
     #[cfg(feature = "voice")]
     pub voice_manager: Option<Arc<dyn VoiceGatewayManager>>,
     pub ws_url: Arc<Mutex<String>>,
@@ -400,4 +481,110 @@ pub struct ShardManagerOptions {
     pub http: Arc<Http>,
     pub intents: GatewayIntents,
     pub presence: Option<PresenceData>,
+    /// The compression to request for gateway connections.
+    pub compression: TransportCompression,
+    /// Sessions of a previous process to resume instead of identifying, as returned by
+    /// [`ShardManager::shutdown_all_resumable`].
+    pub session_states: Vec<ShardSessionState>,
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use std::num::NonZeroU16;
+    use std::sync::Arc;
+    #[cfg(feature = "framework")]
+    use std::sync::OnceLock;
+    use std::time::Duration;
+
+    use futures::StreamExt;
+    use tokio::net::TcpListener;
+    use tokio::sync::{Mutex, RwLock};
+    use tokio_tungstenite::tungstenite::Message;
+    use typemap_rev::TypeMap;
+
+    use super::{ShardManager, ShardManagerOptions};
+    #[cfg(feature = "cache")]
+    use crate::cache::Cache;
+    use crate::gateway::{ShardSessionState, TransportCompression};
+    use crate::http::Http;
+    use crate::model::gateway::{GatewayIntents, ShardInfo};
+    use crate::model::id::ShardId;
+
+    #[tokio::test]
+    async fn shutdown_all_resumable_keeps_sessions() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let ws_url = format!("ws://{}/", listener.local_addr().unwrap());
+        let resume_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let resume_ws_url = format!("ws://{}/", resume_listener.local_addr().unwrap());
+
+        // The shard first connects to the base URL, and then to the resume URL to resume the
+        // restored session.
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut base = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let (stream, _) = resume_listener.accept().await.unwrap();
+            let mut resume = tokio_tungstenite::accept_async(stream).await.unwrap();
+            while base.next().await.is_some() {}
+
+            let mut opcodes = Vec::new();
+            let mut close_code = None;
+            while let Some(Ok(message)) = resume.next().await {
+                match message {
+                    Message::Text(text) => {
+                        let payload: crate::json::Value = crate::json::from_str(&text).unwrap();
+                        opcodes.push(payload["op"].clone());
+                    },
+                    Message::Close(frame) => close_code = frame.map(|frame| u16::from(frame.code)),
+                    _ => {},
+                }
+            }
+            (opcodes, close_code)
+        });
+
+        let (manager, _ret) = ShardManager::new(ShardManagerOptions {
+            data: Arc::new(RwLock::new(TypeMap::new())),
+            event_handlers: vec![],
+            raw_event_handlers: vec![],
+            #[cfg(feature = "framework")]
+            framework: Arc::new(OnceLock::new()),
+            shard_index: 0,
+            shard_init: 1,
+            shard_total: 1,
+            max_concurrency: NonZeroU16::MIN,
+            identify_queue: None,
+            #[cfg(feature = "voice")]
+            voice_manager: None,
+            ws_url: Arc::new(Mutex::new(ws_url)),
+            #[cfg(feature = "cache")]
+            cache: Arc::new(Cache::new()),
+            http: Arc::new(Http::new("token")),
+            intents: GatewayIntents::empty(),
+            presence: None,
+            compression: TransportCompression::Payload,
+            session_states: vec![ShardSessionState {
+                session_id: "abc".into(),
+                seq: 42,
+                resume_ws_url: Some(resume_ws_url),
+                shard_info: ShardInfo::new(ShardId(0), 1),
+            }],
+        });
+        manager.initialize().unwrap();
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !manager.has(ShardId(0)).await {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        let states = manager.shutdown_all_resumable().await;
+        assert_eq!(states.len(), 1);
+        assert_eq!(states[0].session_id, "abc");
+        assert_eq!(states[0].seq, 42);
+
+        let (opcodes, close_code) = server.await.unwrap();
+        assert_eq!(opcodes, [6]);
+        assert_eq!(close_code, Some(4000));
+    }
 }
@@ -61,7 +61,7 @@ impl ShardMessenger {
     /// # use tokio::sync::Mutex;
     /// # use serenity::model::gateway::{GatewayIntents, ShardInfo};
     /// # use serenity::model::id::ShardId;
-    /// # use serenity::gateway::{ChunkGuildFilter, Shard};
+    /// # use serenity::gateway::{ChunkGuildFilter, Shard, TransportCompression};
     /// # use std::sync::Arc;
     /// #
     /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
@@ -71,7 +71,7 @@ impl ShardMessenger {
     /// #         id: ShardId(0),
     /// #         total: 1,
     /// #     };
-    /// #     let mut shard = Shard::new(mutex.clone(), "", shard_info, GatewayIntents::all(), None).await?;
+    /// #     let mut shard = Shard::new(mutex.clone(), "", shard_info, GatewayIntents::all(), None, TransportCompression::Payload).await?;
     /// #
     /// use serenity::model::id::GuildId;
     ///
@@ -87,7 +87,7 @@ impl ShardMessenger {
     /// # use tokio::sync::Mutex;
     /// # use serenity::model::gateway::{GatewayIntents, ShardInfo};
     /// # use serenity::model::id::ShardId;
-    /// # use serenity::gateway::{ChunkGuildFilter, Shard};
+    /// # use serenity::gateway::{ChunkGuildFilter, Shard, TransportCompression};
     /// # use std::sync::Arc;
     /// #
     /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
@@ -98,7 +98,7 @@ impl ShardMessenger {
     /// #         total: 1,
     /// #     };
     /// #
-    /// #     let mut shard = Shard::new(mutex.clone(), "", shard_info, GatewayIntents::all(), None).await?;;
+    /// #     let mut shard = Shard::new(mutex.clone(), "", shard_info, GatewayIntents::all(), None, TransportCompression::Payload).await?;;
     /// #
     /// use serenity::model::id::GuildId;
     ///
@@ -139,7 +139,7 @@ impl ShardMessenger {
     ///
     /// ```rust,no_run
     /// # use tokio::sync::Mutex;
-    /// # use serenity::gateway::{Shard};
+    /// # use serenity::gateway::{Shard, TransportCompression};
     /// # use serenity::model::id::ShardId;
     /// # use serenity::model::gateway::{GatewayIntents, ShardInfo};
     /// # use std::sync::Arc;
@@ -152,7 +152,7 @@ impl ShardMessenger {
     /// #         total: 1,
     /// #     };
     /// #
-    /// #     let mut shard = Shard::new(mutex.clone(), "", shard_info, GatewayIntents::all(), None).await?;
+    /// #     let mut shard = Shard::new(mutex.clone(), "", shard_info, GatewayIntents::all(), None, TransportCompression::Payload).await?;
     /// use serenity::gateway::ActivityData;
     ///
     /// shard.set_activity(Some(ActivityData::playing("Heroes of the Storm")));
@@ -215,7 +215,7 @@ impl ShardMessenger {
     ///
     /// ```rust,no_run
     /// # use tokio::sync::Mutex;
-    /// # use serenity::gateway::{Shard};
+    /// # use serenity::gateway::{Shard, TransportCompression};
     /// # use serenity::model::id::ShardId;
     /// # use serenity::model::gateway::{GatewayIntents, ShardInfo};
     /// # use std::sync::Arc;
@@ -227,7 +227,7 @@ impl ShardMessenger {
     /// #         total: 1,
     /// #     };
     /// #
-    /// #     let mut shard = Shard::new(mutex.clone(), "", shard_info, GatewayIntents::all(), None).await?;
+    /// #     let mut shard = Shard::new(mutex.clone(), "", shard_info, GatewayIntents::all(), None, TransportCompression::Payload).await?;
     /// #
     /// use serenity::model::user::OnlineStatus;
     ///
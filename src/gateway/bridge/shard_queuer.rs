@@ -1,4 +1,5 @@
 use std::collections::{HashMap, VecDeque};
+use std::num::NonZeroU16;
 use std::sync::Arc;
 #[cfg(feature = "framework")]
 use std::sync::OnceLock;
@@ -6,13 +7,14 @@ use std::sync::OnceLock;
 use futures::channel::mpsc::UnboundedReceiver as Receiver;
 use futures::StreamExt;
 use tokio::sync::{Mutex, RwLock};
-use tokio::time::{sleep, timeout, Duration, Instant};
+use tokio::time::{timeout_at, Duration, Instant};
 use tracing::{debug, info, instrument, warn};
 use typemap_rev::TypeMap;
 
 #[cfg(feature = "voice")]
 use super::VoiceGatewayManager;
 use super::{
+    IdentifyQueue,
     ShardId,
     ShardManager,
     ShardMessenger,
@@ -26,18 +28,30 @@ use crate::cache::Cache;
 use crate::client::{EventHandler, RawEventHandler};
 #[cfg(feature = "framework")]
 use crate::framework::Framework;
-use crate::gateway::{ConnectionStage, PresenceData, Shard, ShardRunnerMessage};
+use crate::gateway::{
+    ConnectionStage,
+    PresenceData,
+    Shard,
+    ShardRunnerMessage,
+    ShardSessionState,
+    TransportCompression,
+};
 use crate::http::Http;
 use crate::internal::prelude::*;
 use crate::internal::tokio::spawn_named;
 use crate::model::gateway::{GatewayIntents, ShardInfo};
 
-const WAIT_BETWEEN_BOOTS_IN_SECONDS: u64 = 5;
+pub(super) const WAIT_BETWEEN_BOOTS_IN_SECONDS: u64 = 5;
 
 /// The shard queuer is a simple loop that runs indefinitely to manage the startup of shards.
 ///
+/// Shards are started in batches of up to [`max_concurrency`] shards at a time, with a 5 second
+/// sleep between each batch.
+///
 /// A shard queuer instance _should_ be run in its own thread, due to the blocking nature of the
 /// loop itself as well as a 5 second thread sleep between shard starts.
+///
+/// [`max_concurrency`]: crate::model::gateway::SessionStartLimit::max_concurrency
 pub struct ShardQueuer {
     /// A copy of [`Client::data`] to be given to runners for contextual dispatching.
     ///
@@ -54,16 +68,19 @@ pub struct ShardQueuer {
     /// A copy of the framework
     #[cfg(feature = "framework")]
     pub framework: Arc<OnceLock<Arc<dyn Framework>>>,
-    /// The instant that a shard was last started.
+    /// The instant that a batch of shards was last started.
     ///
     /// This is used to determine how long to wait between shard IDENTIFYs.
     pub last_start: Option<Instant>,
+    /// The queue deciding when each shard may identify.
+    pub identify_queue: Arc<dyn IdentifyQueue>,
     /// A copy of the [`ShardManager`] to communicate with it.
     pub manager: Arc<ShardManager>,
-    /// The shards that are queued for booting.
+    /// The shards that are queued for booting, split into identify buckets.
     ///
-    /// This will typically be filled with previously failed boots.
-    pub queue: VecDeque<ShardInfo>,
+    /// This is filled with shards that were requested to start, as well as previously failed
+    /// boots.
+    pub queue: ShardQueue,
     /// A copy of the map of shard runners.
     pub runners: Arc<Mutex<HashMap<ShardId, ShardRunnerInfo>>>,
     /// A receiver channel for the shard queuer to be told to start shards.
@@ -78,6 +95,12 @@ pub struct ShardQueuer {
     pub http: Arc<Http>,
     pub intents: GatewayIntents,
     pub presence: Option<PresenceData>,
+    /// The compression to request for gateway connections.
+    pub compression: TransportCompression,
+    /// Sessions of a previous process to resume instead of identifying, by shard.
+    ///
+    /// A session is removed once its shard is started.
+    pub session_states: HashMap<ShardId, ShardSessionState>,
 }
 
 impl ShardQueuer {
@@ -86,12 +109,14 @@ impl ShardQueuer {
     /// This will loop over the internal [`Self::rx`] for [`ShardQueuerMessage`]s, blocking for
     /// messages on what to do.
     ///
-    /// If a [`ShardQueuerMessage::Start`] is received, this will:
+    /// If a [`ShardQueuerMessage::Start`] is received, the shard is put into its identify bucket
+    /// within [`Self::queue`]. Once no more messages are immediately available, this will:
     ///
-    /// 1. Check how much time has passed since the last shard was started
-    /// 2. If the amount of time is less than the ratelimit, it will sleep until that time has
-    ///    passed
-    /// 3. Start the shard by ID
+    /// 1. Check how much time has passed since the last batch of shards was started
+    /// 2. If the amount of time is less than the ratelimit, it will keep receiving messages until
+    ///    that time has passed
+    /// 3. Take one shard from every non-empty bucket, and start each of them once the
+    ///    [`Self::identify_queue`] allows it to identify
     ///
     /// If a [`ShardQueuerMessage::Shutdown`] is received, this will return and the loop will be
     /// over.
@@ -99,13 +124,18 @@ impl ShardQueuer {
     /// **Note**: This should be run in its own thread due to the blocking nature of the loop.
     #[instrument(skip(self))]
     pub async fn run(&mut self) {
-        // The duration to timeout from reads over the Rx channel. This can be done in a loop, and
-        // if the read times out then a shard can be started if one is presently waiting in the
-        // queue.
+        // The duration to timeout from reads over the Rx channel when no shard is waiting in the
+        // queue. This can be done in a loop, and if the read times out then a shard can be started
+        // if one has been queued in the meantime.
         const TIMEOUT: Duration = Duration::from_secs(WAIT_BETWEEN_BOOTS_IN_SECONDS);
 
         loop {
-            match timeout(TIMEOUT, self.rx.next()).await {
+            // If shards are waiting to be started, only receive messages until the next batch is
+            // allowed to identify, so that all shards requested until then share the same batch.
+            let deadline =
+                if self.queue.is_empty() { Instant::now() + TIMEOUT } else { self.next_start() };
+
+            match timeout_at(deadline, self.rx.next()).await {
                 Ok(Some(ShardQueuerMessage::Shutdown)) => {
                     debug!("[Shard Queuer] Received to shutdown.");
                     self.shutdown_runners().await;
@@ -118,52 +148,85 @@ impl ShardQueuer {
                 },
                 Ok(Some(ShardQueuerMessage::Start(id, total))) => {
                     debug!("[Shard Queuer] Received to start shard {} of {}.", id.0, total.0);
-                    self.checked_start(id, total.0).await;
+
+                    if let Some(session_state) = self.session_states.remove(&id) {
+                        // Resuming does not count towards the identify ratelimit, so the shard
+                        // does not need to be queued.
+                        self.checked_resume(id, total.0, session_state).await;
+                    } else {
+                        self.queue.push_back(ShardInfo::new(id, total.0));
+                    }
                 },
                 Ok(None) => break,
                 Err(_) => {
-                    if let Some(shard) = self.queue.pop_front() {
-                        self.checked_start(shard.id, shard.total).await;
-                    }
+                    let batch = self.queue.pop_batch();
+                    self.checked_start_batch(batch).await;
                 },
             }
         }
     }
 
-    #[instrument(skip(self))]
-    async fn check_last_start(&mut self) {
-        let Some(instant) = self.last_start else { return };
-
+    /// Returns the instant at which the next batch of shards may be started.
+    fn next_start(&self) -> Instant {
         // We must wait 5 seconds between IDENTIFYs to avoid session invalidations.
         let duration = Duration::from_secs(WAIT_BETWEEN_BOOTS_IN_SECONDS);
-        let elapsed = instant.elapsed();
 
-        if elapsed >= duration {
+        match self.last_start {
+            Some(instant) => instant + duration,
+            None => Instant::now(),
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn checked_start_batch(&mut self, batch: Vec<ShardInfo>) {
+        if batch.is_empty() {
             return;
         }
 
-        let to_sleep = duration - elapsed;
+        debug!("[Shard Queuer] Checked start for batch of {} shards", batch.len());
+
+        for shard in batch {
+            if let Err(why) = self.identify_queue.wait_for_identify(shard).await {
+                warn!("[Shard Queuer] Err waiting for identify of shard {}: {:?}", shard.id, why);
+                info!("[Shard Queuer] Re-queueing start of shard {}", shard.id);
+
+                self.queue.push_front(shard);
+                continue;
+            }
+
+            debug!("[Shard Queuer] Starting shard {} out of {}", shard.id, shard.total);
+
+            if let Err(why) = self.start(shard.id, shard.total, None).await {
+                warn!("[Shard Queuer] Err starting shard {}: {:?}", shard.id, why);
+                info!("[Shard Queuer] Re-queueing start of shard {}", shard.id);
+
+                // Try again in the next batch of shards.
+                self.queue.push_front(shard);
+            }
+        }
 
-        sleep(to_sleep).await;
+        self.last_start = Some(Instant::now());
     }
 
-    #[instrument(skip(self))]
-    async fn checked_start(&mut self, id: ShardId, total: u32) {
-        debug!("[Shard Queuer] Checked start for shard {} out of {}", id, total);
-        self.check_last_start().await;
+    #[instrument(skip(self, session_state))]
+    async fn checked_resume(&mut self, id: ShardId, total: u32, session_state: ShardSessionState) {
+        debug!("[Shard Queuer] Resuming session of shard {} out of {}", id, total);
 
-        if let Err(why) = self.start(id, total).await {
-            warn!("[Shard Queuer] Err starting shard {}: {:?}", id, why);
+        if let Err(why) = self.start(id, total, Some(session_state)).await {
+            warn!("[Shard Queuer] Err resuming shard {}: {:?}", id, why);
             info!("[Shard Queuer] Re-queueing start of shard {}", id);
 
             self.queue.push_back(ShardInfo::new(id, total));
         }
-
-        self.last_start = Some(Instant::now());
     }
 
-    #[instrument(skip(self))]
-    async fn start(&mut self, id: ShardId, total: u32) -> Result<()> {
+    #[instrument(skip(self, session_state))]
+    async fn start(
+        &mut self,
+        id: ShardId,
+        total: u32,
+        session_state: Option<ShardSessionState>,
+    ) -> Result<()> {
         let shard_info = ShardInfo::new(id, total);
 
         let mut shard = Shard::new(
@@ -172,9 +235,14 @@ impl ShardQueuer {
             shard_info,
             self.intents,
             self.presence.clone(),
+            self.compression,
         )
         .await?;
 
+        if let Some(session_state) = session_state {
+            shard.restore_session(session_state).await?;
+        }
+
         let cloned_http = Arc::clone(&self.http);
         shard.set_application_id_callback(move |id| cloned_http.set_application_id(id));
 
@@ -250,3 +318,106 @@ impl ShardQueuer {
         }
     }
 }
+
+/// A queue of shards to be started, split into identify buckets according to the value of
+/// [`max_concurrency`].
+///
+/// Discord allows one shard of every bucket to identify at the same time, where the bucket of a
+/// shard is `shard_id % max_concurrency`.
+///
+/// [`max_concurrency`]: crate::model::gateway::SessionStartLimit::max_concurrency
+#[derive(Debug)]
+#[must_use]
+pub struct ShardQueue {
+    buckets: Vec<VecDeque<ShardInfo>>,
+}
+
+impl ShardQueue {
+    /// Creates a new queue with `max_concurrency` empty buckets.
+    pub fn new(max_concurrency: NonZeroU16) -> Self {
+        let buckets = vec![VecDeque::new(); max_concurrency.get() as usize];
+
+        Self {
+            buckets,
+        }
+    }
+
+    /// Returns the maximum number of shards that will be started in a single batch.
+    #[must_use]
+    pub fn max_concurrency(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /// Returns whether no shards are queued.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.buckets.iter().all(VecDeque::is_empty)
+    }
+
+    /// Returns the total number of queued shards.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.buckets.iter().map(VecDeque::len).sum()
+    }
+
+    fn bucket_mut(&mut self, shard_id: ShardId) -> &mut VecDeque<ShardInfo> {
+        let index = shard_id.0 as usize % self.buckets.len();
+        &mut self.buckets[index]
+    }
+
+    /// Queues a shard at the back of its bucket.
+    pub fn push_back(&mut self, shard: ShardInfo) {
+        self.bucket_mut(shard.id).push_back(shard);
+    }
+
+    /// Queues a shard at the front of its bucket, so that it is started in the next batch.
+    pub fn push_front(&mut self, shard: ShardInfo) {
+        self.bucket_mut(shard.id).push_front(shard);
+    }
+
+    /// Pops the first shard of every non-empty bucket and returns them as one batch.
+    #[must_use]
+    pub fn pop_batch(&mut self) -> Vec<ShardInfo> {
+        self.buckets.iter_mut().filter_map(VecDeque::pop_front).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU16;
+
+    use super::ShardQueue;
+    use crate::model::gateway::ShardInfo;
+    use crate::model::id::ShardId;
+
+    fn ids(batch: &[ShardInfo]) -> Vec<u32> {
+        batch.iter().map(|shard| shard.id.0).collect()
+    }
+
+    #[test]
+    fn queue_batches_by_bucket() {
+        let mut queue = ShardQueue::new(NonZeroU16::new(4).unwrap());
+        for id in 0..10 {
+            queue.push_back(ShardInfo::new(ShardId(id), 10));
+        }
+        assert_eq!(queue.len(), 10);
+
+        assert_eq!(ids(&queue.pop_batch()), [0, 1, 2, 3]);
+        assert_eq!(ids(&queue.pop_batch()), [4, 5, 6, 7]);
+        assert_eq!(ids(&queue.pop_batch()), [8, 9]);
+        assert!(queue.is_empty());
+        assert!(queue.pop_batch().is_empty());
+    }
+
+    #[test]
+    fn queue_requeues_at_front() {
+        let mut queue = ShardQueue::new(NonZeroU16::MIN);
+        queue.push_back(ShardInfo::new(ShardId(1), 3));
+        queue.push_back(ShardInfo::new(ShardId(2), 3));
+        queue.push_front(ShardInfo::new(ShardId(0), 3));
+
+        assert_eq!(ids(&queue.pop_batch()), [0]);
+        assert_eq!(ids(&queue.pop_batch()), [1]);
+        assert_eq!(ids(&queue.pop_batch()), [2]);
+    }
+}
@@ -247,6 +247,9 @@ impl ShardRunner {
             }
         }
 
+        let session_state = self.shard.session_state_after_close(close_code);
+        self.manager.set_session_state(id, session_state).await;
+
         // Inform the manager that shutdown for this shard has finished.
         self.manager.shutdown_finished(id);
         false
@@ -50,6 +50,10 @@ pub enum Error {
     /// If an connection has been established but privileged gateway intents were provided without
     /// enabling them prior.
     DisallowedGatewayIntents,
+    /// When a message of a zlib-stream compressed connection could not be decompressed.
+    ///
+    /// The inflate context can't recover from this, so the connection must be re-established.
+    DecompressingStream,
 }
 
 impl fmt::Display for Error {
@@ -70,6 +74,7 @@ impl fmt::Display for Error {
             Self::DisallowedGatewayIntents => {
                 f.write_str("Disallowed gateway intents were provided")
             },
+            Self::DecompressingStream => f.write_str("Failed to decompress the gateway stream"),
         }
     }
 }
@@ -53,7 +53,7 @@ use reqwest::Url;
 
 pub use self::bridge::*;
 pub use self::error::Error as GatewayError;
-pub use self::shard::Shard;
+pub use self::shard::{Shard, ShardSessionState};
 pub use self::ws::WsClient;
 #[cfg(feature = "http")]
 use crate::internal::prelude::*;
@@ -255,6 +255,22 @@ pub enum ReconnectType {
     Resume,
 }
 
+/// The compression used for messages received over the gateway connection.
+///
+/// [Discord docs](https://discord.com/developers/docs/topics/gateway#encoding-and-compression).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum TransportCompression {
+    /// Discord compresses large payloads individually, by sending `compress: true` in the
+    /// IDENTIFY.
+    #[default]
+    Payload,
+    /// Discord compresses the whole connection as a single zlib stream, requested with the
+    /// `compress=zlib-stream` query parameter. This considerably reduces bandwidth, at the cost of
+    /// keeping an inflate context per connection.
+    ZlibStream,
+}
+
 /// [Discord docs](https://discord.com/developers/docs/topics/gateway-events#request-guild-members).
 #[derive(Clone, Debug)]
 pub enum ChunkGuildFilter {
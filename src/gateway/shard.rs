@@ -15,6 +15,7 @@ use super::{
     PresenceData,
     ReconnectType,
     ShardAction,
+    TransportCompression,
     WsClient,
 };
 use crate::constants::{self, close_codes};
@@ -24,6 +25,30 @@ use crate::model::gateway::{GatewayIntents, ShardInfo};
 use crate::model::id::{ApplicationId, GuildId};
 use crate::model::user::OnlineStatus;
 
+/// The state of a [`Shard`]'s gateway session, which can be used to resume the session from
+/// another process.
+///
+/// This is retrieved with [`Shard::session_state`] or [`ShardManager::shutdown_all_resumable`],
+/// and given back to [`Shard::restore_session`] or [`ShardManagerOptions::session_states`].
+///
+/// **Note**: A session can only be resumed if its connection was not closed with the close codes
+/// `1000` or `1001`, and only for a short time after it was closed.
+///
+/// [`ShardManager::shutdown_all_resumable`]: super::ShardManager::shutdown_all_resumable
+/// [`ShardManagerOptions::session_states`]: super::ShardManagerOptions::session_states
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct ShardSessionState {
+    /// The ID of the session.
+    pub session_id: String,
+    /// The sequence number of the last event received in the session.
+    pub seq: u64,
+    /// The URL to connect to when resuming the session.
+    pub resume_ws_url: Option<String>,
+    /// The shard that the session belongs to.
+    pub shard_info: ShardInfo,
+}
+
 /// A Shard is a higher-level handler for a websocket connection to Discord's gateway. The shard
 /// allows for sending and receiving messages over the websocket, such as setting the active
 /// activity, reconnecting, syncing guilds, and more.
@@ -74,6 +99,11 @@ pub struct Shard {
     pub started: Instant,
     pub token: String,
     ws_url: Arc<Mutex<String>>,
+    /// The URL to connect to when resuming, as given by the [`Ready`] event.
+    ///
+    /// [`Ready`]: crate::model::gateway::Ready
+    resume_ws_url: Option<String>,
+    compression: TransportCompression,
     pub intents: GatewayIntents,
 }
 
@@ -82,6 +112,9 @@ impl Shard {
     ///
     /// **Note**: You should likely never need to do this yourself.
     ///
+    /// To resume a session of another process instead of identifying, call
+    /// [`Self::restore_session`] on the new shard.
+    ///
     /// # Examples
     ///
     /// Instantiating a new Shard manually for a bot with no shards, and then listening for events:
@@ -89,7 +122,7 @@ impl Shard {
     /// ```rust,no_run
     /// use std::sync::Arc;
     ///
-    /// use serenity::gateway::Shard;
+    /// use serenity::gateway::{Shard, TransportCompression};
     /// use serenity::model::gateway::{GatewayIntents, ShardInfo};
     /// use serenity::model::id::ShardId;
     /// use tokio::sync::Mutex;
@@ -106,7 +139,15 @@ impl Shard {
     ///
     /// // retrieve the gateway response, which contains the URL to connect to
     /// let gateway = Arc::new(Mutex::new(http.get_gateway().await?.url));
-    /// let shard = Shard::new(gateway, &token, shard_info, GatewayIntents::all(), None).await?;
+    /// let shard = Shard::new(
+    ///     gateway,
+    ///     &token,
+    ///     shard_info,
+    ///     GatewayIntents::all(),
+    ///     None,
+    ///     TransportCompression::Payload,
+    /// )
+    /// .await?;
     ///
     /// // at this point, you can create a `loop`, and receive events and match
     /// // their variants
@@ -124,9 +165,10 @@ impl Shard {
         shard_info: ShardInfo,
         intents: GatewayIntents,
         presence: Option<PresenceData>,
+        compression: TransportCompression,
     ) -> Result<Shard> {
         let url = ws_url.lock().await.clone();
-        let client = connect(&url).await?;
+        let client = connect(&url, compression).await?;
 
         let presence = presence.unwrap_or_default();
         let last_heartbeat_sent = None;
@@ -152,10 +194,44 @@ impl Shard {
             session_id,
             shard_info,
             ws_url,
+            resume_ws_url: None,
+            compression,
             intents,
         })
     }
 
+    /// Resumes a session exported with [`Self::session_state`], usually by another process,
+    /// instead of identifying a new session.
+    ///
+    /// This reconnects to the session's resume URL and sends a RESUME. If the session belongs to
+    /// another shard, it is ignored and the shard identifies as usual.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if connecting to the resume URL or sending the RESUME fails.
+    #[instrument(skip(self, session_state))]
+    pub async fn restore_session(&mut self, session_state: ShardSessionState) -> Result<()> {
+        // A session can only be resumed by the shard it belongs to.
+        if session_state.shard_info.id != self.shard_info.id
+            || session_state.shard_info.total != self.shard_info.total
+        {
+            warn!(
+                "[{:?}] Ignoring session of shard {:?}",
+                self.shard_info, session_state.shard_info
+            );
+
+            return Ok(());
+        }
+
+        debug!("[{:?}] Resuming restored session", self.shard_info);
+
+        self.session_id = Some(session_state.session_id);
+        self.seq = session_state.seq;
+        self.resume_ws_url = session_state.resume_ws_url;
+
+        self.resume().await
+    }
+
     /// Sets a callback to be called when the gateway receives the application's ID from Discord.
     ///
     /// Used internally by serenity to set the Http's internal application ID automatically.
@@ -239,6 +315,39 @@ impl Shard {
         self.session_id.as_ref()
     }
 
+    /// Returns the state of the current session, if there is one.
+    ///
+    /// This can be passed to [`Self::restore_session`] to resume the session in another process.
+    pub fn session_state(&self) -> Option<ShardSessionState> {
+        Some(ShardSessionState {
+            session_id: self.session_id.clone()?,
+            seq: self.seq,
+            resume_ws_url: self.resume_ws_url.clone(),
+            shard_info: self.shard_info,
+        })
+    }
+
+    /// Returns the state of the current session if it can still be resumed after closing the
+    /// connection with the given close code.
+    pub(crate) fn session_state_after_close(&self, close_code: u16) -> Option<ShardSessionState> {
+        // Discord invalidates sessions closed with 1000 or 1001, any other session can still be
+        // resumed.
+        if close_code == 1000 || close_code == 1001 {
+            None
+        } else {
+            self.session_state()
+        }
+    }
+
+    /// Returns the URL that the shard will connect to when resuming, if the [`Ready`] event has
+    /// been received.
+    ///
+    /// [`Ready`]: crate::model::gateway::Ready
+    #[inline]
+    pub fn resume_ws_url(&self) -> Option<&str> {
+        self.resume_ws_url.as_deref()
+    }
+
     #[inline]
     #[instrument(skip(self))]
     pub fn set_activity(&mut self, activity: Option<ActivityData>) {
@@ -286,6 +395,7 @@ impl Shard {
                 debug!("[{:?}] Received Ready", self.shard_info);
 
                 self.session_id = Some(ready.ready.session_id.clone());
+                self.resume_ws_url = Some(ready.ready.resume_gateway_url.clone());
                 self.stage = ConnectionStage::Connected;
 
                 if let Some(callback) = self.application_id_callback.take() {
@@ -453,12 +563,12 @@ impl Shard {
             &Ok(GatewayEvent::Hello(interval)) => {
                 debug!("[{:?}] Received a Hello; interval: {}", self.shard_info, interval);
 
+                self.heartbeat_interval = Some(std::time::Duration::from_millis(interval));
+
                 if self.stage == ConnectionStage::Resuming {
                     return Ok(None);
                 }
 
-                self.heartbeat_interval = Some(std::time::Duration::from_millis(interval));
-
                 Ok(Some(if self.stage == ConnectionStage::Handshake {
                     ShardAction::Identify
                 } else {
@@ -480,6 +590,12 @@ impl Shard {
             Err(Error::Gateway(GatewayError::Closed(data))) => {
                 self.handle_gateway_closed(data.as_ref())
             },
+            Err(Error::Gateway(GatewayError::DecompressingStream)) => {
+                warn!("[{:?}] Gateway stream corrupted", self.shard_info);
+                info!("[{:?}] Will attempt to auto-reconnect", self.shard_info);
+
+                Ok(Some(ShardAction::Reconnect(self.reconnection_type())))
+            },
             Err(Error::Tungstenite(why)) => {
                 warn!("[{:?}] Websocket error: {:?}", self.shard_info, why);
                 info!("[{:?}] Will attempt to auto-reconnect", self.shard_info);
@@ -596,7 +712,7 @@ impl Shard {
     ///
     /// ```rust,no_run
     /// # use tokio::sync::Mutex;
-    /// # use serenity::gateway::{ChunkGuildFilter, Shard};
+    /// # use serenity::gateway::{ChunkGuildFilter, Shard, TransportCompression};
     /// # use serenity::model::gateway::{GatewayIntents, ShardInfo};
     /// # use serenity::model::id::ShardId;
     /// # use std::sync::Arc;
@@ -608,7 +724,7 @@ impl Shard {
     /// #          total: 1,
     /// #     };
     /// #
-    /// #     let mut shard = Shard::new(mutex.clone(), "", shard_info, GatewayIntents::all(), None).await?;
+    /// #     let mut shard = Shard::new(mutex.clone(), "", shard_info, GatewayIntents::all(), None, TransportCompression::Payload).await?;
     /// #
     /// use serenity::model::id::GuildId;
     ///
@@ -623,7 +739,7 @@ impl Shard {
     /// ```rust,no_run
     /// # use tokio::sync::Mutex;
     /// # use serenity::model::gateway::{GatewayIntents, ShardInfo};
-    /// # use serenity::gateway::{ChunkGuildFilter, Shard};
+    /// # use serenity::gateway::{ChunkGuildFilter, Shard, TransportCompression};
     /// # use serenity::model::id::ShardId;
     /// # use std::error::Error;
     /// # use std::sync::Arc;
@@ -635,7 +751,7 @@ impl Shard {
     /// #          id: ShardId(0),
     /// #          total: 1,
     /// #     };
-    /// #     let mut shard = Shard::new(mutex.clone(), "", shard_info, GatewayIntents::all(), None).await?;
+    /// #     let mut shard = Shard::new(mutex.clone(), "", shard_info, GatewayIntents::all(), None, TransportCompression::Payload).await?;
     /// #
     /// use serenity::model::id::GuildId;
     ///
@@ -689,6 +805,9 @@ impl Shard {
     /// Initializes a new WebSocket client.
     ///
     /// This will set the stage of the shard before and after instantiation of the client.
+    ///
+    /// If the shard has a session to resume, the client connects to [`Self::resume_ws_url`].
+    /// Otherwise, it connects to the base gateway URL.
     #[instrument(skip(self))]
     pub async fn initialize(&mut self) -> Result<WsClient> {
         debug!("[{:?}] Initializing.", self.shard_info);
@@ -702,8 +821,13 @@ impl Shard {
         // Hello is received.
         self.stage = ConnectionStage::Connecting;
         self.started = Instant::now();
-        let url = &self.ws_url.lock().await.clone();
-        let client = connect(url).await?;
+        // Discord requires resuming on the URL given in the Ready event, while new sessions are
+        // identified on the base URL.
+        let url = match &self.resume_ws_url {
+            Some(resume_ws_url) if self.session_id.is_some() => resume_ws_url.clone(),
+            _ => self.ws_url.lock().await.clone(),
+        };
+        let client = connect(&url, self.compression).await?;
         self.stage = ConnectionStage::Handshake;
 
         Ok(client)
@@ -716,6 +840,7 @@ impl Shard {
         self.heartbeat_interval = None;
         self.last_heartbeat_acknowledged = true;
         self.session_id = None;
+        self.resume_ws_url = None;
         self.stage = ConnectionStage::Disconnected;
         self.seq = 0;
     }
@@ -751,7 +876,7 @@ impl Shard {
     }
 }
 
-async fn connect(base_url: &str) -> Result<WsClient> {
+async fn connect(base_url: &str, compression: TransportCompression) -> Result<WsClient> {
     let url =
         Url::parse(&format!("{base_url}?v={}", constants::GATEWAY_VERSION)).map_err(|why| {
             warn!("Error building gateway URL with base `{}`: {:?}", base_url, why);
@@ -759,5 +884,149 @@ async fn connect(base_url: &str) -> Result<WsClient> {
             Error::Gateway(GatewayError::BuildingUrl)
         })?;
 
-    WsClient::connect(url).await
+    WsClient::connect(url, compression).await
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use std::sync::Arc;
+
+    use futures::StreamExt;
+    use tokio::net::TcpListener;
+    use tokio::sync::Mutex;
+    use tokio::task::JoinHandle;
+    use tokio_tungstenite::tungstenite::Message;
+
+    use super::{Shard, ShardSessionState};
+    use crate::gateway::TransportCompression;
+    use crate::json::{from_value, json, to_value, Value};
+    use crate::model::event::{Event, GatewayEvent, ReadyEvent};
+    use crate::model::gateway::{GatewayIntents, ShardInfo};
+    use crate::model::id::ShardId;
+
+    /// Accepts `count` connections on a new local listener, and returns the first text message
+    /// sent over each of them.
+    async fn gateway(count: usize) -> (String, JoinHandle<Vec<Option<Value>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/", listener.local_addr().unwrap());
+
+        let server = tokio::spawn(async move {
+            let mut messages = Vec::new();
+            for _ in 0..count {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+
+                let mut message = None;
+                while let Some(Ok(frame)) = ws.next().await {
+                    if let Message::Text(text) = frame {
+                        message = Some(crate::json::from_str(&text).unwrap());
+                        break;
+                    }
+                }
+                messages.push(message);
+            }
+            messages
+        });
+
+        (url, server)
+    }
+
+    fn ready(resume_gateway_url: &str) -> Event {
+        let ready: ReadyEvent = from_value(json!({
+            "v": 10,
+            "user": {
+                "id": "1",
+                "username": "bot",
+                "discriminator": "0000",
+                "avatar": null,
+                "bot": true,
+            },
+            "guilds": [],
+            "session_id": "abc",
+            "resume_gateway_url": resume_gateway_url,
+            "shard": [0, 1],
+            "application": {"id": "1", "flags": 0},
+        }))
+        .unwrap();
+
+        Event::Ready(ready)
+    }
+
+    async fn connect_shard(ws_url: &str) -> Shard {
+        Shard::new(
+            Arc::new(Mutex::new(ws_url.to_string())),
+            "token",
+            ShardInfo::new(ShardId(0), 1),
+            GatewayIntents::empty(),
+            None,
+            TransportCompression::Payload,
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn resume_uses_resume_gateway_url() {
+        let (ws_url, base) = gateway(2).await;
+        let (resume_ws_url, resume) = gateway(1).await;
+
+        let mut shard = connect_shard(&ws_url).await;
+        shard.handle_event(&Ok(GatewayEvent::Dispatch(1, ready(&resume_ws_url)))).unwrap();
+        assert_eq!(shard.resume_ws_url(), Some(resume_ws_url.as_str()));
+
+        shard.resume().await.unwrap();
+        drop(shard.client.close(None).await);
+        let resumed = resume.await.unwrap();
+        assert_eq!(resumed[0].as_ref().unwrap()["op"], 6);
+        assert_eq!(resumed[0].as_ref().unwrap()["d"]["session_id"], "abc");
+
+        // Reconnecting drops the session, so the new session is identified on the base URL.
+        shard.reconnect().await.unwrap();
+        assert!(shard.resume_ws_url().is_none());
+        shard.identify().await.unwrap();
+        drop(shard.client.close(None).await);
+        let identified = base.await.unwrap();
+        assert!(identified[0].is_none());
+        assert_eq!(identified[1].as_ref().unwrap()["op"], 2);
+    }
+
+    #[tokio::test]
+    async fn restore_exported_session() {
+        let (ws_url, base) = gateway(3).await;
+        let (resume_ws_url, resume) = gateway(1).await;
+
+        let mut shard = connect_shard(&ws_url).await;
+        shard.handle_event(&Ok(GatewayEvent::Dispatch(42, ready(&resume_ws_url)))).unwrap();
+
+        // Closing with 1000 or 1001 invalidates the session, while 4000 keeps it resumable.
+        assert!(shard.session_state_after_close(1000).is_none());
+        assert!(shard.session_state_after_close(1001).is_none());
+        let state = shard.session_state_after_close(4000).unwrap();
+        drop(shard);
+
+        let state: ShardSessionState = from_value(to_value(state).unwrap()).unwrap();
+        assert_eq!(state.session_id, "abc");
+        assert_eq!(state.seq, 42);
+        assert_eq!(state.resume_ws_url.as_deref(), Some(resume_ws_url.as_str()));
+
+        // A session of another shard is ignored.
+        let mut other = connect_shard(&ws_url).await;
+        let mut other_state = state.clone();
+        other_state.shard_info = ShardInfo::new(ShardId(1), 2);
+        other.restore_session(other_state).await.unwrap();
+        assert!(other.session_id().is_none());
+        drop(other);
+
+        let mut restored = connect_shard(&ws_url).await;
+        restored.restore_session(state).await.unwrap();
+        assert_eq!(restored.session_id().map(String::as_str), Some("abc"));
+        drop(restored.client.close(None).await);
+
+        let resumed = resume.await.unwrap();
+        let resumed = resumed[0].as_ref().unwrap();
+        assert_eq!(resumed["op"], 6);
+        assert_eq!(resumed["d"]["session_id"], "abc");
+        assert_eq!(resumed["d"]["seq"], 42);
+        assert!(base.await.unwrap().iter().all(Option::is_none));
+    }
 }
@@ -5,6 +5,8 @@ use std::time::SystemTime;
 
 #[cfg(feature = "client")]
 use flate2::read::ZlibDecoder;
+#[cfg(feature = "client")]
+use flate2::{Decompress, FlushDecompress};
 use futures::SinkExt;
 #[cfg(feature = "client")]
 use futures::StreamExt;
@@ -23,14 +25,14 @@ use tracing::warn;
 use tracing::{debug, instrument, trace};
 use url::Url;
 
-use super::{ActivityData, ChunkGuildFilter, PresenceData};
+use super::{ActivityData, ChunkGuildFilter, PresenceData, TransportCompression};
 use crate::constants::{self, Opcode};
 #[cfg(feature = "client")]
 use crate::gateway::GatewayError;
-#[cfg(feature = "client")]
-use crate::json::from_str;
 use crate::json::to_string;
 #[cfg(feature = "client")]
+use crate::json::{from_slice, from_str};
+#[cfg(feature = "client")]
 use crate::model::event::GatewayEvent;
 use crate::model::gateway::{GatewayIntents, ShardInfo};
 use crate::model::id::{GuildId, UserId};
@@ -93,15 +95,109 @@ struct WebSocketMessage<'a> {
     d: WebSocketMessageData<'a>,
 }
 
-pub struct WsClient(WebSocketStream<MaybeTlsStream<TcpStream>>);
+pub struct WsClient {
+    stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    compression: TransportCompression,
+    #[cfg(feature = "client")]
+    inflater: ZlibStreamInflater,
+}
 
 #[cfg(feature = "client")]
 const TIMEOUT: Duration = Duration::from_millis(500);
 #[cfg(feature = "client")]
 const DECOMPRESSION_MULTIPLIER: usize = 3;
+/// The suffix of every message which completes a payload in a zlib-stream connection.
+#[cfg(feature = "client")]
+const ZLIB_SUFFIX: [u8; 4] = [0x00, 0x00, 0xff, 0xff];
+
+/// The inflate context of a zlib-stream connection.
+///
+/// Discord may split a payload over several messages, so these are buffered until one ends with
+/// the `Z_SYNC_FLUSH` suffix. The context must be kept for the whole connection, as later payloads
+/// refer back to data of previous ones.
+#[cfg(feature = "client")]
+struct ZlibStreamInflater {
+    decompress: Decompress,
+    compressed: Vec<u8>,
+    decompressed: Vec<u8>,
+}
+
+#[cfg(feature = "client")]
+impl ZlibStreamInflater {
+    fn new() -> Self {
+        Self {
+            decompress: Decompress::new(true),
+            compressed: Vec::new(),
+            decompressed: Vec::new(),
+        }
+    }
+
+    /// Buffers the given message, returning the decompressed payload if it was completed by it.
+    fn inflate(&mut self, message: &[u8]) -> Result<Option<&[u8]>, GatewayError> {
+        self.compressed.extend_from_slice(message);
+
+        if !self.compressed.ends_with(&ZLIB_SUFFIX) {
+            return Ok(None);
+        }
+
+        self.decompressed.clear();
+        self.decompressed.reserve(self.compressed.len() * DECOMPRESSION_MULTIPLIER);
+
+        let mut offset = 0;
+        loop {
+            let total_in = self.decompress.total_in();
+            let total_out = self.decompress.total_out();
+
+            let status = self.decompress.decompress_vec(
+                &self.compressed[offset..],
+                &mut self.decompressed,
+                FlushDecompress::Sync,
+            );
+
+            if let Err(why) = status {
+                warn!("Err decompressing stream: {why:?}");
+                self.compressed.clear();
+
+                return Err(GatewayError::DecompressingStream);
+            }
+
+            // The counters can't advance by more than the buffer lengths, so this can't truncate.
+            #[allow(clippy::cast_possible_truncation)]
+            let read = (self.decompress.total_in() - total_in) as usize;
+            let written = self.decompress.total_out() - total_out;
+            offset += read;
+
+            // All input was read and the output buffer did not fill up, so the flushed payload has
+            // been fully decompressed.
+            if offset == self.compressed.len()
+                && self.decompressed.len() < self.decompressed.capacity()
+            {
+                break;
+            }
+
+            if read == 0 && written == 0 {
+                if self.decompressed.len() < self.decompressed.capacity() {
+                    self.compressed.clear();
+
+                    return Err(GatewayError::DecompressingStream);
+                }
+
+                self.decompressed.reserve(self.compressed.len() * DECOMPRESSION_MULTIPLIER);
+            }
+        }
+
+        self.compressed.clear();
+
+        Ok(Some(&self.decompressed))
+    }
+}
 
 impl WsClient {
-    pub(crate) async fn connect(url: Url) -> Result<Self> {
+    pub(crate) async fn connect(mut url: Url, compression: TransportCompression) -> Result<Self> {
+        if compression == TransportCompression::ZlibStream {
+            url.query_pairs_mut().append_pair("compress", "zlib-stream");
+        }
+
         let config = WebSocketConfig {
             max_message_size: None,
             max_frame_size: None,
@@ -109,18 +205,40 @@ impl WsClient {
         };
         let (stream, _) = connect_async_with_config(url, Some(config), false).await?;
 
-        Ok(Self(stream))
+        Ok(Self {
+            stream,
+            compression,
+            #[cfg(feature = "client")]
+            inflater: ZlibStreamInflater::new(),
+        })
+    }
+
+    /// Returns the compression used for this connection.
+    pub fn compression(&self) -> TransportCompression {
+        self.compression
     }
 
     #[cfg(feature = "client")]
     pub(crate) async fn recv_json(&mut self) -> Result<Option<GatewayEvent>> {
-        let message = match timeout(TIMEOUT, self.0.next()).await {
+        let message = match timeout(TIMEOUT, self.stream.next()).await {
             Ok(Some(Ok(msg))) => msg,
             Ok(Some(Err(e))) => return Err(e.into()),
             Ok(None) | Err(_) => return Ok(None),
         };
 
         let value = match message {
+            Message::Binary(bytes) if self.compression == TransportCompression::ZlibStream => {
+                let Some(decompressed) = self.inflater.inflate(&bytes)? else {
+                    return Ok(None);
+                };
+
+                from_slice(decompressed).map_err(|why| {
+                    warn!("Err deserializing bytes: {why:?}");
+                    debug!("Failing bytes: {bytes:?}");
+
+                    why
+                })?
+            },
             Message::Binary(bytes) => {
                 let mut decompressed =
                     String::with_capacity(bytes.len() * DECOMPRESSION_MULTIPLIER);
@@ -156,27 +274,27 @@ impl WsClient {
     pub(crate) async fn send_json(&mut self, value: &impl serde::Serialize) -> Result<()> {
         let message = to_string(value).map(Message::Text)?;
 
-        self.0.send(message).await?;
+        self.stream.send(message).await?;
         Ok(())
     }
 
     /// Delegate to `StreamExt::next`
     #[cfg(feature = "client")]
     pub(crate) async fn next(&mut self) -> Option<std::result::Result<Message, WsError>> {
-        self.0.next().await
+        self.stream.next().await
     }
 
     /// Delegate to `SinkExt::send`
     #[cfg(feature = "client")]
     pub(crate) async fn send(&mut self, message: Message) -> Result<()> {
-        self.0.send(message).await?;
+        self.stream.send(message).await?;
         Ok(())
     }
 
     /// Delegate to `WebSocketStream::close`
     #[cfg(feature = "client")]
     pub(crate) async fn close(&mut self, msg: Option<CloseFrame<'_>>) -> Result<()> {
-        self.0.close(msg).await?;
+        self.stream.close(msg).await?;
         Ok(())
     }
 
@@ -242,7 +360,7 @@ impl WsClient {
                 token,
                 shard,
                 intents,
-                compress: true,
+                compress: self.compression == TransportCompression::Payload,
                 large_threshold: constants::LARGE_THRESHOLD,
                 properties: IdentifyProperties {
                     browser: "serenity",
@@ -305,3 +423,103 @@ impl WsClient {
         .await
     }
 }
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use flate2::{Compress, Compression, FlushCompress};
+    use futures::{SinkExt, StreamExt};
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+    use tokio_tungstenite::tungstenite::Message;
+    use url::Url;
+
+    use super::{WsClient, ZlibStreamInflater, ZLIB_SUFFIX};
+    use crate::gateway::TransportCompression;
+    use crate::model::event::GatewayEvent;
+
+    fn compress(compress: &mut Compress, payload: &str) -> Vec<u8> {
+        let mut output = Vec::with_capacity(payload.len() + 64);
+        compress.compress_vec(payload.as_bytes(), &mut output, FlushCompress::Sync).unwrap();
+        assert!(output.ends_with(&ZLIB_SUFFIX));
+
+        output
+    }
+
+    #[test]
+    fn inflater_keeps_context_between_payloads() {
+        let mut compressor = Compress::new(Compression::default(), true);
+        let mut inflater = ZlibStreamInflater::new();
+
+        let first = compress(&mut compressor, r#"{"op":10,"d":{"heartbeat_interval":41250}}"#);
+        let (start, end) = first.split_at(first.len() / 2);
+        assert_eq!(inflater.inflate(start).unwrap(), None);
+        assert_eq!(
+            inflater.inflate(end).unwrap().unwrap(),
+            br#"{"op":10,"d":{"heartbeat_interval":41250}}"#,
+        );
+
+        // The second payload refers back to the first one, so it can only be decompressed with
+        // the same context.
+        let second = compress(&mut compressor, r#"{"op":10,"d":{"heartbeat_interval":41250}}"#);
+        assert!(ZlibStreamInflater::new().inflate(&second).is_err());
+        assert_eq!(
+            inflater.inflate(&second).unwrap().unwrap(),
+            br#"{"op":10,"d":{"heartbeat_interval":41250}}"#,
+        );
+    }
+
+    #[test]
+    fn inflater_grows_output() {
+        let payload = format!(r#"{{"op":0,"d":"{}"}}"#, "a".repeat(100_000));
+
+        let mut compressor = Compress::new(Compression::best(), true);
+        let mut inflater = ZlibStreamInflater::new();
+        let compressed = compress(&mut compressor, &payload);
+
+        assert_eq!(inflater.inflate(&compressed).unwrap().unwrap(), payload.as_bytes());
+    }
+
+    #[tokio::test]
+    #[allow(clippy::result_large_err)]
+    async fn zlib_stream_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("ws://{}/?v=10", listener.local_addr().unwrap())).unwrap();
+
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut query = None;
+            let mut ws = tokio_tungstenite::accept_hdr_async(stream, |req: &Request, res| {
+                query = req.uri().query().map(ToString::to_string);
+                Ok::<Response, _>(res)
+            })
+            .await
+            .unwrap();
+
+            let mut compressor = Compress::new(Compression::default(), true);
+            let hello = compress(&mut compressor, r#"{"op":10,"d":{"heartbeat_interval":41250}}"#);
+            let (start, end) = hello.split_at(hello.len() / 2);
+            ws.send(Message::Binary(start.to_vec())).await.unwrap();
+            ws.send(Message::Binary(end.to_vec())).await.unwrap();
+            ws.send(Message::Binary(compress(&mut compressor, r#"{"op":11}"#))).await.unwrap();
+
+            // Wait for the client to hang up.
+            while ws.next().await.is_some() {}
+
+            query
+        });
+
+        let mut client = WsClient::connect(url, TransportCompression::ZlibStream).await.unwrap();
+
+        let mut events = Vec::new();
+        while events.len() < 2 {
+            if let Some(event) = client.recv_json().await.unwrap() {
+                events.push(event);
+            }
+        }
+        assert!(matches!(events[0], GatewayEvent::Hello(41250)));
+        assert!(matches!(events[1], GatewayEvent::HeartbeatAck));
+
+        client.close(None).await.unwrap();
+        assert_eq!(server.await.unwrap().as_deref(), Some("v=10&compress=zlib-stream"));
+    }
+}
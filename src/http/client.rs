@@ -1,6 +1,7 @@
 #![allow(clippy::missing_errors_doc)]
 
 use std::borrow::Cow;
+use std::fmt;
 use std::num::NonZeroU64;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
@@ -12,12 +13,17 @@ use reqwest::Url;
 use reqwest::{Client, ClientBuilder, Response as ReqwestResponse, StatusCode};
 use secrecy::{ExposeSecret, SecretString};
 use serde::de::DeserializeOwned;
-use tracing::{debug, instrument, trace};
+use tokio::time::sleep;
+use tracing::{debug, info_span, instrument, trace, warn, Instrument};
 
 use super::multipart::{Multipart, MultipartUpload};
+use super::proxy::TrustedProxy;
+use super::ratelimit_store::RatelimitStore;
 use super::ratelimiting::Ratelimiter;
 use super::request::Request;
+use super::retry::RetryPolicy;
 use super::routing::Route;
+use super::transport::{execute, HttpTransport};
 use super::typing::Typing;
 use super::{
     ErrorResponse,
@@ -53,8 +59,12 @@ pub struct HttpBuilder {
     client: Option<Client>,
     ratelimiter: Option<Ratelimiter>,
     ratelimiter_disabled: bool,
+    ratelimit_store: Option<Arc<dyn RatelimitStore>>,
     token: SecretString,
     proxy: Option<String>,
+    trusted_proxy: Option<TrustedProxy>,
+    retry_policy: Option<RetryPolicy>,
+    transport: Option<Arc<dyn HttpTransport>>,
     application_id: Option<ApplicationId>,
     default_allowed_mentions: Option<CreateAllowedMentions>,
 }
@@ -67,8 +77,12 @@ impl HttpBuilder {
             client: None,
             ratelimiter: None,
             ratelimiter_disabled: false,
+            ratelimit_store: None,
             token: SecretString::new(parse_token(token)),
             proxy: None,
+            trusted_proxy: None,
+            retry_policy: None,
+            transport: None,
             application_id: None,
             default_allowed_mentions: None,
         }
@@ -110,6 +124,19 @@ impl HttpBuilder {
         self
     }
 
+    /// Sets the [`RatelimitStore`] used by the ratelimiter. If one isn't provided, ratelimits are
+    /// kept in memory by an [`InMemoryRatelimitStore`].
+    ///
+    /// This is useful to share ratelimits between several processes using the same token, such as
+    /// through a [`RemoteRatelimitStore`].
+    ///
+    /// [`InMemoryRatelimitStore`]: super::InMemoryRatelimitStore
+    /// [`RemoteRatelimitStore`]: super::RemoteRatelimitStore
+    pub fn ratelimit_store(mut self, ratelimit_store: Arc<dyn RatelimitStore>) -> Self {
+        self.ratelimit_store = Some(ratelimit_store);
+        self
+    }
+
     /// Sets the proxy that Discord HTTP API requests will be passed to. This is mainly intended
     /// for something like [`twilight-http-proxy`] where multiple processes can make API requests
     /// while sharing a single ratelimiter.
@@ -122,10 +149,42 @@ impl HttpBuilder {
     /// proxy's behavior where it will tunnel requests that use TLS via [`HTTP CONNECT`] method
     /// (e.g. using [`reqwest::Proxy`]).
     ///
+    /// To also delegate ratelimiting to the proxy, use [`Self::trusted_proxy`] instead.
+    ///
     /// [`twilight-http-proxy`]: https://github.com/twilight-rs/http-proxy
     /// [`HTTP CONNECT`]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Methods/CONNECT
     pub fn proxy(mut self, proxy: impl Into<String>) -> Self {
         self.proxy = Some(proxy.into());
+        self.trusted_proxy = None;
+        self
+    }
+
+    /// Sets a proxy that Discord HTTP API requests will be passed to, and which is trusted to
+    /// ratelimit them. The ratelimiter then doesn't keep track of buckets locally, and only waits
+    /// when the proxy responds with a 429.
+    ///
+    /// See [`TrustedProxy`] for how the token and ratelimit headers can be configured.
+    pub fn trusted_proxy(mut self, trusted_proxy: TrustedProxy) -> Self {
+        self.proxy = Some(trusted_proxy.url().to_string());
+        self.trusted_proxy = Some(trusted_proxy);
+        self
+    }
+
+    /// Sets the [`RetryPolicy`] used to retry requests failing for transient reasons, such as
+    /// connection resets or a 503 from Discord. By default, failed requests are not retried.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Sets the [`HttpTransport`] requests are sent through, instead of the [`reqwest::Client`].
+    ///
+    /// This is mainly useful to test code performing requests without reaching Discord, through a
+    /// [`MockTransport`].
+    ///
+    /// [`MockTransport`]: super::MockTransport
+    pub fn transport(mut self, transport: Arc<dyn HttpTransport>) -> Self {
+        self.transport = Some(transport);
         self
     }
 
@@ -149,14 +208,32 @@ impl HttpBuilder {
         });
 
         let ratelimiter = (!self.ratelimiter_disabled).then(|| {
-            self.ratelimiter
-                .unwrap_or_else(|| Ratelimiter::new(client.clone(), self.token.expose_secret()))
+            let mut ratelimiter = self
+                .ratelimiter
+                .unwrap_or_else(|| Ratelimiter::new(client.clone(), self.token.expose_secret()));
+
+            if let Some(ratelimit_store) = self.ratelimit_store {
+                ratelimiter.set_store(ratelimit_store);
+            }
+
+            if self.trusted_proxy.is_some() {
+                ratelimiter.set_trusted_proxy(self.trusted_proxy.clone());
+            }
+
+            if self.transport.is_some() {
+                ratelimiter.set_transport(self.transport.clone());
+            }
+
+            ratelimiter
         });
 
         Http {
             client,
             ratelimiter,
             proxy: self.proxy,
+            trusted_proxy: self.trusted_proxy,
+            retry_policy: self.retry_policy,
+            transport: self.transport,
             token: self.token,
             application_id,
             default_allowed_mentions: self.default_allowed_mentions,
@@ -191,16 +268,34 @@ fn reason_into_header(reason: &str) -> Headers {
 
 /// **Note**: For all member functions that return a [`Result`], the Error kind will be either
 /// [`Error::Http`] or [`Error::Json`].
-#[derive(Debug)]
 pub struct Http {
     pub(crate) client: Client,
     pub ratelimiter: Option<Ratelimiter>,
     pub proxy: Option<String>,
+    pub trusted_proxy: Option<TrustedProxy>,
+    pub retry_policy: Option<RetryPolicy>,
+    transport: Option<Arc<dyn HttpTransport>>,
     token: SecretString,
     application_id: AtomicU64,
     pub default_allowed_mentions: Option<CreateAllowedMentions>,
 }
 
+impl fmt::Debug for Http {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Http")
+            .field("client", &self.client)
+            .field("ratelimiter", &self.ratelimiter)
+            .field("proxy", &self.proxy)
+            .field("trusted_proxy", &self.trusted_proxy)
+            .field("retry_policy", &self.retry_policy)
+            .field("transport", &self.transport.as_ref().map(|_| "dyn HttpTransport"))
+            .field("token", &self.token)
+            .field("application_id", &self.application_id)
+            .field("default_allowed_mentions", &self.default_allowed_mentions)
+            .finish()
+    }
+}
+
 impl Http {
     #[must_use]
     pub fn new(token: &str) -> Self {
@@ -511,6 +606,29 @@ impl Http {
         .await
     }
 
+    /// Creates an emoji owned by an application, usable everywhere by its bot user.
+    ///
+    /// Refer to Discord's [docs] for field information.
+    ///
+    /// [docs]: https://discord.com/developers/docs/resources/emoji#create-application-emoji
+    pub async fn create_application_emoji(
+        &self,
+        application_id: ApplicationId,
+        map: &impl serde::Serialize,
+    ) -> Result<Emoji> {
+        self.fire(Request {
+            body: Some(to_vec(map)?),
+            multipart: None,
+            headers: None,
+            method: LightMethod::Post,
+            route: Route::ApplicationEmojis {
+                application_id,
+            },
+            params: None,
+        })
+        .await
+    }
+
     /// Create a follow-up message for an Interaction.
     ///
     /// Functions the same as [`Self::execute_webhook`]
@@ -891,6 +1009,30 @@ impl Http {
         .await
     }
 
+    /// Creates a soundboard sound in a guild.
+    ///
+    /// **Note**: Requires the [Create Guild Expressions] permission.
+    ///
+    /// [Create Guild Expressions]: Permissions::CREATE_GUILD_EXPRESSIONS
+    pub async fn create_guild_soundboard_sound(
+        &self,
+        guild_id: GuildId,
+        map: &impl serde::Serialize,
+        audit_log_reason: Option<&str>,
+    ) -> Result<SoundboardSound> {
+        self.fire(Request {
+            body: Some(to_vec(map)?),
+            multipart: None,
+            headers: audit_log_reason.map(reason_into_header),
+            method: LightMethod::Post,
+            route: Route::GuildSoundboardSounds {
+                guild_id,
+            },
+            params: None,
+        })
+        .await
+    }
+
     /// Creates a sticker.
     ///
     /// **Note**: Requires the [Create Guild Expressions] permission.
@@ -1058,6 +1200,26 @@ impl Http {
         .await
     }
 
+    /// Deletes an emoji owned by an application.
+    pub async fn delete_application_emoji(
+        &self,
+        application_id: ApplicationId,
+        emoji_id: EmojiId,
+    ) -> Result<()> {
+        self.wind(204, Request {
+            body: None,
+            multipart: None,
+            headers: None,
+            method: LightMethod::Delete,
+            route: Route::ApplicationEmoji {
+                application_id,
+                emoji_id,
+            },
+            params: None,
+        })
+        .await
+    }
+
     /// Deletes a follow-up message for an interaction.
     pub async fn delete_followup_message(
         &self,
@@ -1403,6 +1565,29 @@ impl Http {
         .await
     }
 
+    /// Deletes a soundboard sound from a guild.
+    ///
+    /// See [`GuildId::delete_soundboard_sound`] for permissions requirements.
+    pub async fn delete_guild_soundboard_sound(
+        &self,
+        guild_id: GuildId,
+        sound_id: SoundId,
+        audit_log_reason: Option<&str>,
+    ) -> Result<()> {
+        self.wind(204, Request {
+            body: None,
+            multipart: None,
+            headers: audit_log_reason.map(reason_into_header),
+            method: LightMethod::Delete,
+            route: Route::GuildSoundboardSound {
+                guild_id,
+                sound_id,
+            },
+            params: None,
+        })
+        .await
+    }
+
     /// Deletes a sticker from a server.
     ///
     /// See [`GuildId::delete_sticker`] for permissions requirements.
@@ -1589,6 +1774,31 @@ impl Http {
         .await
     }
 
+    /// Changes the name of an emoji owned by an application.
+    ///
+    /// Refer to Discord's [docs] for field information.
+    ///
+    /// [docs]: https://discord.com/developers/docs/resources/emoji#modify-application-emoji
+    pub async fn edit_application_emoji(
+        &self,
+        application_id: ApplicationId,
+        emoji_id: EmojiId,
+        map: &impl serde::Serialize,
+    ) -> Result<Emoji> {
+        self.fire(Request {
+            body: Some(to_vec(map)?),
+            multipart: None,
+            headers: None,
+            method: LightMethod::Patch,
+            route: Route::ApplicationEmoji {
+                application_id,
+                emoji_id,
+            },
+            params: None,
+        })
+        .await
+    }
+
     /// Edits a follow-up message for an interaction.
     ///
     /// Refer to Discord's [docs] for Edit Webhook Message for field information.
@@ -1827,6 +2037,28 @@ impl Http {
         .await
     }
 
+    /// Edits the onboarding flow of a guild.
+    pub async fn edit_guild_onboarding(
+        &self,
+        guild_id: GuildId,
+        map: &impl serde::Serialize,
+        audit_log_reason: Option<&str>,
+    ) -> Result<Onboarding> {
+        let body = to_vec(map)?;
+
+        self.fire(Request {
+            body: Some(body),
+            multipart: None,
+            headers: audit_log_reason.map(reason_into_header),
+            method: LightMethod::Put,
+            route: Route::GuildOnboarding {
+                guild_id,
+            },
+            params: None,
+        })
+        .await
+    }
+
     /// Edits a guild welcome screen.
     pub async fn edit_guild_welcome_screen(
         &self,
@@ -2170,6 +2402,30 @@ impl Http {
         .await
     }
 
+    /// Changes a soundboard sound in a guild.
+    ///
+    /// See [`GuildId::edit_soundboard_sound`] for permissions requirements.
+    pub async fn edit_guild_soundboard_sound(
+        &self,
+        guild_id: GuildId,
+        sound_id: SoundId,
+        map: &impl serde::Serialize,
+        audit_log_reason: Option<&str>,
+    ) -> Result<SoundboardSound> {
+        self.fire(Request {
+            body: Some(to_vec(map)?),
+            multipart: None,
+            headers: audit_log_reason.map(reason_into_header),
+            method: LightMethod::Patch,
+            route: Route::GuildSoundboardSound {
+                guild_id,
+                sound_id,
+            },
+            params: None,
+        })
+        .await
+    }
+
     /// Changes a sticker in a guild.
     ///
     /// See [`GuildId::edit_sticker`] for permissions requirements.
@@ -2600,6 +2856,26 @@ impl Http {
         .await
     }
 
+    /// Immediately ends the poll of a message sent by the current user, returning the message.
+    pub async fn expire_poll(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+    ) -> Result<Message> {
+        self.fire(Request {
+            body: None,
+            multipart: None,
+            headers: None,
+            method: LightMethod::Post,
+            route: Route::ChannelPollExpire {
+                channel_id,
+                message_id,
+            },
+            params: None,
+        })
+        .await
+    }
+
     /// Gets the active maintenances from Discord's Status API.
     ///
     /// Does not require authentication.
@@ -3167,6 +3443,50 @@ impl Http {
         .await
     }
 
+    /// Gets all emojis owned by an application.
+    pub async fn get_application_emojis(
+        &self,
+        application_id: ApplicationId,
+    ) -> Result<Vec<Emoji>> {
+        #[derive(Deserialize)]
+        struct EmojisResponse {
+            items: Vec<Emoji>,
+        }
+
+        self.fire::<EmojisResponse>(Request {
+            body: None,
+            multipart: None,
+            headers: None,
+            method: LightMethod::Get,
+            route: Route::ApplicationEmojis {
+                application_id,
+            },
+            params: None,
+        })
+        .await
+        .map(|r| r.items)
+    }
+
+    /// Gets information about an emoji owned by an application.
+    pub async fn get_application_emoji(
+        &self,
+        application_id: ApplicationId,
+        emoji_id: EmojiId,
+    ) -> Result<Emoji> {
+        self.fire(Request {
+            body: None,
+            multipart: None,
+            headers: None,
+            method: LightMethod::Get,
+            route: Route::ApplicationEmoji {
+                application_id,
+                emoji_id,
+            },
+            params: None,
+        })
+        .await
+    }
+
     /// Gets information about an emoji in a guild.
     pub async fn get_emoji(&self, guild_id: GuildId, emoji_id: EmojiId) -> Result<Emoji> {
         self.fire(Request {
@@ -3452,6 +3772,21 @@ impl Http {
         .await
     }
 
+    /// Gets the onboarding flow of a guild.
+    pub async fn get_guild_onboarding(&self, guild_id: GuildId) -> Result<Onboarding> {
+        self.fire(Request {
+            body: None,
+            multipart: None,
+            headers: None,
+            method: LightMethod::Get,
+            route: Route::GuildOnboarding {
+                guild_id,
+            },
+            params: None,
+        })
+        .await
+    }
+
     /// Gets a guild welcome screen information.
     pub async fn get_guild_welcome_screen(&self, guild_id: GuildId) -> Result<GuildWelcomeScreen> {
         self.fire(Request {
@@ -3719,6 +4054,63 @@ impl Http {
         .await
     }
 
+    /// Retrieves the soundboard sounds of a [`Guild`].
+    pub async fn get_guild_soundboard_sounds(
+        &self,
+        guild_id: GuildId,
+    ) -> Result<Vec<SoundboardSound>> {
+        #[derive(Deserialize)]
+        struct SoundsResponse {
+            items: Vec<SoundboardSound>,
+        }
+
+        self.fire::<SoundsResponse>(Request {
+            body: None,
+            multipart: None,
+            headers: None,
+            method: LightMethod::Get,
+            route: Route::GuildSoundboardSounds {
+                guild_id,
+            },
+            params: None,
+        })
+        .await
+        .map(|r| r.items)
+    }
+
+    /// Retrieves a single soundboard sound of a [`Guild`].
+    pub async fn get_guild_soundboard_sound(
+        &self,
+        guild_id: GuildId,
+        sound_id: SoundId,
+    ) -> Result<SoundboardSound> {
+        self.fire(Request {
+            body: None,
+            multipart: None,
+            headers: None,
+            method: LightMethod::Get,
+            route: Route::GuildSoundboardSound {
+                guild_id,
+                sound_id,
+            },
+            params: None,
+        })
+        .await
+    }
+
+    /// Retrieves the default soundboard sounds, which can be used in every guild.
+    pub async fn get_default_soundboard_sounds(&self) -> Result<Vec<SoundboardSound>> {
+        self.fire(Request {
+            body: None,
+            multipart: None,
+            headers: None,
+            method: LightMethod::Get,
+            route: Route::SoundboardDefaultSounds,
+            params: None,
+        })
+        .await
+    }
+
     /// Retrieves a list of stickers in a [`Guild`].
     pub async fn get_guild_stickers(&self, guild_id: GuildId) -> Result<Vec<Sticker>> {
         let mut value: Value = self
@@ -4060,6 +4452,47 @@ impl Http {
         .await
     }
 
+    /// Gets the users who voted for an answer of a poll.
+    ///
+    /// **Note**: At most 100 users are returned, after the given `after` user, if any. The
+    /// default limit is 25.
+    pub async fn get_answer_voters(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        answer_id: AnswerId,
+        after: Option<UserId>,
+        limit: Option<u8>,
+    ) -> Result<Vec<User>> {
+        #[derive(Deserialize)]
+        struct VotersResponse {
+            users: Vec<User>,
+        }
+
+        let mut params = vec![];
+        if let Some(after) = after {
+            params.push(("after", after.to_string()));
+        }
+        if let Some(limit) = limit {
+            params.push(("limit", limit.to_string()));
+        }
+
+        self.fire::<VotersResponse>(Request {
+            body: None,
+            multipart: None,
+            headers: None,
+            method: LightMethod::Get,
+            route: Route::ChannelPollGetAnswerVoters {
+                channel_id,
+                message_id,
+                answer_id,
+            },
+            params: Some(params),
+        })
+        .await
+        .map(|r| r.users)
+    }
+
     /// Gets user Ids based on their reaction to a message. This endpoint is dumb.
     pub async fn get_reaction_users(
         &self,
@@ -4371,6 +4804,27 @@ impl Http {
         .await
     }
 
+    /// Plays a soundboard sound in the voice channel the current user is connected to.
+    ///
+    /// See [`ChannelId::send_soundboard_sound`] for permissions requirements.
+    pub async fn send_soundboard_sound(
+        &self,
+        channel_id: ChannelId,
+        map: &impl serde::Serialize,
+    ) -> Result<()> {
+        self.wind(204, Request {
+            body: Some(to_vec(map)?),
+            multipart: None,
+            headers: None,
+            method: LightMethod::Post,
+            route: Route::ChannelSendSoundboardSound {
+                channel_id,
+            },
+            params: None,
+        })
+        .await
+    }
+
     /// Sends a message to a channel.
     ///
     /// # Errors
@@ -4687,11 +5141,10 @@ impl Http {
     #[instrument]
     pub async fn request(&self, req: Request<'_>) -> Result<ReqwestResponse> {
         let method = req.method.reqwest_method();
-        let response = if let Some(ratelimiter) = &self.ratelimiter {
-            ratelimiter.perform(req).await?
+        let response = if let Some(retry_policy) = &self.retry_policy {
+            self.execute_with_retries(req, retry_policy).await?
         } else {
-            let request = req.build(&self.client, self.token(), self.proxy.as_deref())?.build()?;
-            self.client.execute(request).await?
+            self.execute(req).await?
         };
 
         if response.status().is_success() {
@@ -4703,6 +5156,57 @@ impl Http {
         }
     }
 
+    async fn execute(&self, req: Request<'_>) -> Result<ReqwestResponse> {
+        if let Some(ratelimiter) = &self.ratelimiter {
+            ratelimiter.perform(req).await
+        } else if let Some(trusted_proxy) = &self.trusted_proxy {
+            let request = trusted_proxy.build_request(&self.client, self.token(), req)?;
+            execute(&self.client, self.transport.as_ref(), request).await
+        } else {
+            let request = req.build(&self.client, self.token(), self.proxy.as_deref())?.build()?;
+            execute(&self.client, self.transport.as_ref(), request).await
+        }
+    }
+
+    async fn execute_with_retries(
+        &self,
+        req: Request<'_>,
+        retry_policy: &RetryPolicy,
+    ) -> Result<ReqwestResponse> {
+        let mut attempt = 1;
+
+        loop {
+            let result = self.execute(req.clone()).instrument(info_span!("attempt", attempt)).await;
+
+            let retry = match &result {
+                Ok(response) => retry_policy.retries_status(attempt, req.method, response.status()),
+                Err(why) => retry_policy.retries_error(attempt, req.method, why),
+            };
+
+            if !retry {
+                return result;
+            }
+
+            let backoff = retry_policy.backoff_for(attempt);
+            match &result {
+                Ok(response) => warn!(
+                    "Attempt {} on route {:?} returned {}, retrying in {:?}",
+                    attempt,
+                    req.route,
+                    response.status(),
+                    backoff
+                ),
+                Err(why) => warn!(
+                    "Attempt {} on route {:?} failed: {:?}, retrying in {:?}",
+                    attempt, req.route, why, backoff
+                ),
+            }
+
+            sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+
     /// Performs a request and then verifies that the response status code is equal to the expected
     /// value.
     ///
@@ -11,7 +11,8 @@
 //! The former require a [`Client`] to have logged in, while the latter may be made regardless of
 //! any other usage of the library.
 //!
-//! If a request spuriously fails, it will be retried once.
+//! Requests failing for transient reasons, such as a 503 from Discord, can be retried
+//! automatically by configuring a [`RetryPolicy`] through [`HttpBuilder::retry_policy`].
 //!
 //! Note that you may want to perform requests through a [model]s' instance methods where possible,
 //! as they each offer different levels of a high-level interface to the HTTP module.
@@ -22,9 +23,13 @@
 mod client;
 mod error;
 mod multipart;
+mod proxy;
+mod ratelimit_store;
 mod ratelimiting;
 mod request;
+mod retry;
 mod routing;
+mod transport;
 mod typing;
 
 use std::sync::Arc;
@@ -35,9 +40,13 @@ pub use reqwest::StatusCode;
 pub use self::client::*;
 pub use self::error::*;
 pub use self::multipart::*;
+pub use self::proxy::*;
+pub use self::ratelimit_store::*;
 pub use self::ratelimiting::*;
 pub use self::request::*;
+pub use self::retry::*;
 pub use self::routing::*;
+pub use self::transport::*;
 pub use self::typing::*;
 #[cfg(feature = "cache")]
 use crate::cache::Cache;
@@ -0,0 +1,258 @@
+use std::time::Duration;
+
+use reqwest::header::{HeaderValue, AUTHORIZATION};
+use reqwest::{Client, Request as ReqwestRequest, Response, StatusCode};
+use secrecy::{ExposeSecret, SecretString};
+
+use super::ratelimiting::parse_header;
+use super::{HttpError, Request};
+use crate::internal::prelude::*;
+
+/// A proxy which is trusted to ratelimit the requests sent through it, such as
+/// [`twilight-http-proxy`] or [`nirn-proxy`].
+///
+/// Unlike [`HttpBuilder::proxy`], which only rewrites the base URL of requests, the [`Ratelimiter`]
+/// then doesn't keep track of any buckets, and only waits when the proxy responds with a 429. The
+/// proxy may also be sent a different authorization than the bot's token, or none at all if it
+/// adds the token itself.
+///
+/// # Examples
+///
+/// Send requests through a proxy which holds the bot's token, and reports its ratelimits through
+/// custom headers:
+///
+/// ```rust
+/// use serenity::http::{HttpBuilder, TrustedProxy};
+///
+/// let proxy = TrustedProxy::new("http://127.0.0.1:3000")
+///     .omit_token()
+///     .retry_after_header("x-proxy-retry-after")
+///     .global_header("x-proxy-global");
+///
+/// let http = HttpBuilder::new("token").trusted_proxy(proxy).build();
+/// ```
+///
+/// [`twilight-http-proxy`]: https://github.com/twilight-rs/http-proxy
+/// [`nirn-proxy`]: https://github.com/germanoeich/nirn-proxy
+/// [`HttpBuilder::proxy`]: super::HttpBuilder::proxy
+/// [`Ratelimiter`]: super::Ratelimiter
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct TrustedProxy {
+    url: String,
+    authorization: ProxyAuthorization,
+    retry_after_header: String,
+    global_header: String,
+}
+
+#[derive(Clone, Debug)]
+enum ProxyAuthorization {
+    Token,
+    Omit,
+    Replace(SecretString),
+}
+
+/// A ratelimit reported by a [`TrustedProxy`].
+pub(super) struct ProxyRatelimit {
+    pub(super) retry_after: Duration,
+    pub(super) global: bool,
+}
+
+impl TrustedProxy {
+    /// Creates a trusted proxy with the given base URL, in the form of the protocol and hostname,
+    /// e.g. `http://127.0.0.1:3000`.
+    ///
+    /// By default, the bot's token is sent to the proxy, and ratelimits are read from the
+    /// `Retry-After` and `X-RateLimit-Global` headers, like for Discord.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            authorization: ProxyAuthorization::Token,
+            retry_after_header: "retry-after".into(),
+            global_header: "x-ratelimit-global".into(),
+        }
+    }
+
+    /// Doesn't send any `Authorization` header, for proxies which add the token themselves.
+    pub fn omit_token(mut self) -> Self {
+        self.authorization = ProxyAuthorization::Omit;
+        self
+    }
+
+    /// Sends the given `Authorization` header instead of the bot's token, for proxies which
+    /// authenticate their clients separately.
+    pub fn authorization(mut self, authorization: impl Into<String>) -> Self {
+        self.authorization = ProxyAuthorization::Replace(SecretString::new(authorization.into()));
+        self
+    }
+
+    /// Sets the header from which the number of seconds to wait after a 429 is read.
+    pub fn retry_after_header(mut self, header: impl Into<String>) -> Self {
+        self.retry_after_header = header.into();
+        self
+    }
+
+    /// Sets the header whose presence on a 429 indicates that the global ratelimit was hit.
+    pub fn global_header(mut self, header: impl Into<String>) -> Self {
+        self.global_header = header.into();
+        self
+    }
+
+    /// The base URL of the proxy.
+    #[must_use]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Builds a request to the proxy, with the configured authorization.
+    pub(super) fn build_request(
+        &self,
+        client: &Client,
+        token: &str,
+        req: Request<'_>,
+    ) -> Result<ReqwestRequest> {
+        let mut request = req.build(client, token, Some(&self.url))?.build()?;
+
+        match &self.authorization {
+            ProxyAuthorization::Token => {},
+            ProxyAuthorization::Omit => {
+                request.headers_mut().remove(AUTHORIZATION);
+            },
+            ProxyAuthorization::Replace(authorization) => {
+                let value = HeaderValue::from_str(authorization.expose_secret())
+                    .map_err(HttpError::InvalidHeader)?;
+                request.headers_mut().insert(AUTHORIZATION, value);
+            },
+        }
+
+        Ok(request)
+    }
+
+    /// Reads the ratelimit the proxy reported, if the request was ratelimited.
+    pub(super) fn ratelimit(&self, response: &Response) -> Result<Option<ProxyRatelimit>> {
+        if response.status() != StatusCode::TOO_MANY_REQUESTS {
+            return Ok(None);
+        }
+
+        let headers = response.headers();
+        let Some(retry_after) = parse_header::<f64>(headers, &self.retry_after_header)? else {
+            return Ok(None);
+        };
+
+        Ok(Some(ProxyRatelimit {
+            retry_after: Duration::from_secs_f64(retry_after),
+            global: headers.contains_key(self.global_header.as_str()),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::sync::{Arc, Mutex};
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::task::JoinHandle;
+
+    use super::*;
+    use crate::http::{HttpBuilder, InMemoryRatelimitStore, LightMethod, Ratelimiter, Route};
+    use crate::model::id::ChannelId;
+
+    /// Starts a mock proxy replying with the given responses in order, returning the requests it
+    /// received.
+    async fn mock_proxy(responses: Vec<&'static str>) -> (SocketAddr, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let handle = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+
+                let mut request = Vec::new();
+                let mut buf = [0; 4096];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let read = stream.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..read]);
+                }
+                requests.push(String::from_utf8(request).unwrap().to_lowercase());
+
+                stream.write_all(response.as_bytes()).await.unwrap();
+                stream.shutdown().await.unwrap();
+            }
+            requests
+        });
+
+        (addr, handle)
+    }
+
+    fn request() -> Request<'static> {
+        Request::new(
+            Route::Channel {
+                channel_id: ChannelId::new(7),
+            },
+            LightMethod::Get,
+        )
+    }
+
+    #[tokio::test]
+    async fn token_is_omitted_or_replaced() {
+        let ok = "HTTP/1.1 200 OK\r\nconnection: close\r\ncontent-length: 0\r\n\r\n";
+        let (addr, requests) = mock_proxy(vec![ok, ok]).await;
+
+        let omitting = HttpBuilder::new("token")
+            .trusted_proxy(TrustedProxy::new(format!("http://{addr}")).omit_token())
+            .build();
+        omitting.request(request()).await.unwrap();
+
+        let replacing = HttpBuilder::new("token")
+            .trusted_proxy(TrustedProxy::new(format!("http://{addr}")).authorization("Proxy abc"))
+            .build();
+        replacing.request(request()).await.unwrap();
+
+        let requests = requests.await.unwrap();
+        assert!(requests[0].starts_with("get /api/v10/channels/7 "));
+        assert!(!requests[0].contains("authorization"));
+        assert!(requests[1].contains("authorization: proxy abc\r\n"));
+    }
+
+    #[tokio::test]
+    async fn proxy_ratelimits_are_waited_without_local_buckets() {
+        let (addr, requests) = mock_proxy(vec![
+            "HTTP/1.1 429 Too Many Requests\r\nconnection: close\r\nx-proxy-retry-after: 0.01\r\n\
+             x-proxy-global: true\r\ncontent-length: 0\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nconnection: close\r\nx-ratelimit-limit: 5\r\n\
+             x-ratelimit-remaining: 0\r\nx-ratelimit-reset-after: 60\r\ncontent-length: 0\r\n\r\n",
+        ])
+        .await;
+
+        let ratelimits = Arc::new(Mutex::new(Vec::new()));
+        let mut ratelimiter = Ratelimiter::new(reqwest::Client::new(), "Bot token");
+        let ratelimits_clone = Arc::clone(&ratelimits);
+        ratelimiter.set_ratelimit_callback(Box::new(move |info| {
+            ratelimits_clone.lock().unwrap().push(info);
+        }));
+
+        let store = Arc::new(InMemoryRatelimitStore::new());
+        let proxy = TrustedProxy::new(format!("http://{addr}"))
+            .retry_after_header("x-proxy-retry-after")
+            .global_header("x-proxy-global");
+        let http = HttpBuilder::new("token")
+            .ratelimiter(ratelimiter)
+            .ratelimit_store(Arc::<InMemoryRatelimitStore>::clone(&store))
+            .trusted_proxy(proxy)
+            .build();
+
+        http.request(request()).await.unwrap();
+        assert_eq!(requests.await.unwrap().len(), 2);
+
+        // The bucket reported by the proxy was not tracked locally.
+        assert!(store.routes().read().await.is_empty());
+
+        let ratelimits = ratelimits.lock().unwrap();
+        assert_eq!(ratelimits.len(), 1);
+        assert!(ratelimits[0].global);
+        assert_eq!(ratelimits[0].timeout, Duration::from_millis(10));
+    }
+}
@@ -0,0 +1,732 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{Error as IoError, ErrorKind};
+use std::num::NonZeroU64;
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::{Mutex, RwLock};
+use tracing::{debug, instrument, warn};
+
+use super::ratelimiting::{Ratelimit, RatelimitingBucket};
+use crate::internal::prelude::*;
+use crate::internal::tokio::spawn_named;
+
+/// A store keeping track of the ratelimits used by a [`Ratelimiter`].
+///
+/// By default, an [`InMemoryRatelimitStore`] is used, which only knows about the requests of the
+/// current process. If several processes use the same token, they should share one store to avoid
+/// tripping each other's ratelimits, for example through a [`RatelimitStoreServer`] and
+/// [`RemoteRatelimitStore`]s, or a custom implementation backed by an external database.
+///
+/// [`Ratelimiter`]: super::Ratelimiter
+#[async_trait]
+pub trait RatelimitStore: Send + Sync {
+    /// Takes a ticket for a request in the given bucket.
+    ///
+    /// Returns [`None`] if the request may be performed right away. Otherwise, the request waits
+    /// for the returned time, and then tries to acquire a ticket again. Implementations must also
+    /// account for the global ratelimit, including for [`RatelimitingBucket`]s which
+    /// [are none].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store could not be reached. The request then fails with it.
+    ///
+    /// [are none]: RatelimitingBucket::is_none
+    async fn acquire(&self, bucket: RatelimitingBucket) -> Result<Option<RatelimitWait>>;
+
+    /// Updates the ratelimit of the given bucket, or the global ratelimit if
+    /// [`RatelimitUpdate::global`] is set, with the information of a response.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store could not be reached. The request then fails with it.
+    async fn update(&self, bucket: RatelimitingBucket, update: &RatelimitUpdate) -> Result<()>;
+}
+
+/// The time a request has to wait before it may be performed, as returned by
+/// [`RatelimitStore::acquire`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RatelimitWait {
+    /// How long to wait before trying to acquire a ticket again.
+    pub timeout: Duration,
+    /// The number of requests that can be made in the ratelimit's period of time.
+    pub limit: i64,
+    /// Whether the wait is caused by the global ratelimit.
+    pub global: bool,
+}
+
+/// The ratelimit information of a response, passed to [`RatelimitStore::update`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RatelimitUpdate {
+    /// The value of the `X-RateLimit-Limit` header.
+    pub limit: Option<i64>,
+    /// The value of the `X-RateLimit-Remaining` header.
+    pub remaining: Option<i64>,
+    /// The absolute time when the ratelimit resets.
+    pub reset: Option<SystemTime>,
+    /// The value of the `X-RateLimit-Reset-After` header.
+    pub reset_after: Option<Duration>,
+    /// The value of the `Retry-After` header, if the request was ratelimited.
+    pub retry_after: Option<Duration>,
+    /// Whether the `X-RateLimit-Global` header was set, in which case the global ratelimit was
+    /// hit.
+    pub global: bool,
+    /// The value of the `X-RateLimit-Bucket` header, a hash identifying the ratelimit shared by
+    /// all routes with that hash and the same major parameter.
+    pub bucket: Option<String>,
+}
+
+/// The default [`RatelimitStore`], keeping the ratelimits of the current process in memory.
+///
+/// The store learns which routes Discord puts in the same bucket from the
+/// [`RatelimitUpdate::bucket`] hashes, and from then on shares one [`Ratelimit`] between those
+/// routes for each major parameter.
+#[derive(Debug, Default)]
+pub struct InMemoryRatelimitStore {
+    global_reset: Mutex<Option<SystemTime>>,
+    // When futures is implemented, make tasks clear out their respective entry when the 'reset'
+    // passes.
+    routes: Arc<RwLock<HashMap<RatelimitingBucket, Arc<Mutex<Ratelimit>>>>>,
+    bucket_hashes: RwLock<HashMap<&'static str, String>>,
+    hashed: RwLock<HashMap<HashedBucket, Arc<Mutex<Ratelimit>>>>,
+}
+
+/// A bucket hash and major parameter.
+type HashedBucket = (String, Option<NonZeroU64>);
+
+impl InMemoryRatelimitStore {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The routes mutex is a HashMap of each [`RatelimitingBucket`] and their respective ratelimit
+    /// information.
+    ///
+    /// # Examples
+    ///
+    /// View the `reset` time of the route for `ChannelsId(7)`:
+    ///
+    /// ```rust,no_run
+    /// use serenity::http::{InMemoryRatelimitStore, Route};
+    /// # use serenity::model::prelude::*;
+    ///
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let store: InMemoryRatelimitStore = unimplemented!();
+    /// let routes = store.routes();
+    /// let reader = routes.read().await;
+    ///
+    /// let channel_id = ChannelId::new(7);
+    /// let route = Route::Channel {
+    ///     channel_id,
+    /// };
+    /// if let Some(route) = reader.get(&route.ratelimiting_bucket()) {
+    ///     if let Some(reset) = route.lock().await.reset() {
+    ///         println!("Reset time at: {:?}", reset);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn routes(&self) -> Arc<RwLock<HashMap<RatelimitingBucket, Arc<Mutex<Ratelimit>>>>> {
+        Arc::clone(&self.routes)
+    }
+
+    /// The bucket hash Discord reported for each route, by the name of its [`Route`] variant.
+    ///
+    /// This is mainly useful for debugging. Routes with the same hash share their ratelimits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use std::sync::Arc;
+    ///
+    /// use serenity::http::{HttpBuilder, InMemoryRatelimitStore};
+    ///
+    /// # async fn run() {
+    /// let store = Arc::new(InMemoryRatelimitStore::new());
+    /// let http = HttpBuilder::new("token").ratelimit_store(Arc::clone(&store) as _).build();
+    ///
+    /// // Perform some requests...
+    ///
+    /// for (route, hash) in store.bucket_hashes().await {
+    ///     println!("{route} is in bucket {hash}");
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [`Route`]: super::Route
+    pub async fn bucket_hashes(&self) -> HashMap<&'static str, String> {
+        self.bucket_hashes.read().await.clone()
+    }
+
+    async fn ratelimit(&self, bucket: RatelimitingBucket) -> Arc<Mutex<Ratelimit>> {
+        if let Some(ratelimit) = self.routes.read().await.get(&bucket) {
+            return Arc::clone(ratelimit);
+        }
+
+        // If the route's bucket hash is known, share the ratelimit of the other routes with the
+        // same hash and major parameter.
+        let shared = match bucket.route() {
+            Some(route) => match self.bucket_hashes.read().await.get(route) {
+                Some(hash) => self
+                    .hashed
+                    .read()
+                    .await
+                    .get(&(hash.clone(), bucket.major_parameter()))
+                    .map(Arc::clone),
+                None => None,
+            },
+            None => None,
+        };
+
+        Arc::clone(
+            self.routes.write().await.entry(bucket).or_insert_with(|| shared.unwrap_or_default()),
+        )
+    }
+
+    /// Records the bucket hash of the route, returning the ratelimit shared by its bucket.
+    async fn learn_hash(&self, bucket: RatelimitingBucket, hash: &str) -> Arc<Mutex<Ratelimit>> {
+        let ratelimit = self.ratelimit(bucket).await;
+        let Some(route) = bucket.route() else { return ratelimit };
+
+        let mut bucket_hashes = self.bucket_hashes.write().await;
+        if bucket_hashes.get(route).map(String::as_str) != Some(hash) {
+            debug!("Route {} is in bucket {}", route, hash);
+            bucket_hashes.insert(route, hash.to_string());
+        }
+        drop(bucket_hashes);
+
+        let key = (hash.to_string(), bucket.major_parameter());
+        let mut hashed = self.hashed.write().await;
+        match hashed.get(&key) {
+            Some(shared) if !Arc::ptr_eq(shared, &ratelimit) => {
+                let shared = Arc::clone(shared);
+                self.routes.write().await.insert(bucket, Arc::clone(&shared));
+                shared
+            },
+            Some(_) => ratelimit,
+            None => {
+                hashed.insert(key, Arc::clone(&ratelimit));
+                ratelimit
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl RatelimitStore for InMemoryRatelimitStore {
+    async fn acquire(&self, bucket: RatelimitingBucket) -> Result<Option<RatelimitWait>> {
+        if let Some(reset) = *self.global_reset.lock().await {
+            if let Ok(timeout) = reset.duration_since(SystemTime::now()) {
+                return Ok(Some(RatelimitWait {
+                    timeout,
+                    limit: 50,
+                    global: true,
+                }));
+            }
+        }
+
+        if bucket.is_none() {
+            return Ok(None);
+        }
+
+        let ratelimit = self.ratelimit(bucket).await;
+        let mut ratelimit = ratelimit.lock().await;
+
+        Ok(ratelimit.acquire().map(|timeout| RatelimitWait {
+            timeout,
+            limit: ratelimit.limit(),
+            global: false,
+        }))
+    }
+
+    async fn update(&self, bucket: RatelimitingBucket, update: &RatelimitUpdate) -> Result<()> {
+        if update.global {
+            if let Some(retry_after) = update.retry_after {
+                *self.global_reset.lock().await = Some(SystemTime::now() + retry_after);
+            }
+        } else if !bucket.is_none() {
+            let ratelimit = match &update.bucket {
+                Some(hash) => self.learn_hash(bucket, hash).await,
+                None => self.ratelimit(bucket).await,
+            };
+
+            ratelimit.lock().await.update(update);
+        }
+
+        Ok(())
+    }
+}
+
+/// Serves a [`RatelimitStore`] over a local TCP or Unix socket, to be used by
+/// [`RemoteRatelimitStore`]s in other processes.
+///
+/// This allows a process, such as one dedicated to proxying requests or running the shards, to
+/// own the ratelimits of a token, while workers performing requests ask it for tickets.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+///
+/// use serenity::http::{InMemoryRatelimitStore, RatelimitStoreServer};
+/// use tokio::net::TcpListener;
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let store = Arc::new(InMemoryRatelimitStore::new());
+/// let listener = TcpListener::bind("127.0.0.1:7655").await?;
+///
+/// RatelimitStoreServer::new(store).serve_tcp(listener).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct RatelimitStoreServer {
+    store: Arc<dyn RatelimitStore>,
+}
+
+impl RatelimitStoreServer {
+    /// Creates a server handing out tickets from the given store.
+    #[must_use]
+    pub fn new(store: Arc<dyn RatelimitStore>) -> Self {
+        Self {
+            store,
+        }
+    }
+
+    /// Accepts connections on the given TCP listener until an error occurs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if accepting a connection failed.
+    pub async fn serve_tcp(&self, listener: TcpListener) -> Result<()> {
+        loop {
+            let (stream, addr) = listener.accept().await?;
+            debug!("[Ratelimit Store] Accepted connection from {}", addr);
+
+            self.spawn_connection(stream);
+        }
+    }
+
+    /// Accepts connections on the given Unix listener until an error occurs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if accepting a connection failed.
+    #[cfg(unix)]
+    pub async fn serve_unix(&self, listener: UnixListener) -> Result<()> {
+        loop {
+            let (stream, _) = listener.accept().await?;
+            debug!("[Ratelimit Store] Accepted connection");
+
+            self.spawn_connection(stream);
+        }
+    }
+
+    fn spawn_connection<S>(&self, stream: S)
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let store = Arc::clone(&self.store);
+
+        spawn_named("ratelimit_store::connection", async move {
+            if let Err(why) = handle_connection(&*store, stream).await {
+                warn!("[Ratelimit Store] Err handling connection: {:?}", why);
+            }
+        });
+    }
+}
+
+impl fmt::Debug for RatelimitStoreServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RatelimitStoreServer").finish_non_exhaustive()
+    }
+}
+
+/// A [`RatelimitStore`] which asks a [`RatelimitStoreServer`] over a local TCP or Unix socket for
+/// tickets.
+///
+/// A single connection is kept open and reused for all requests, and reopened if it fails.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+///
+/// use serenity::http::{HttpBuilder, RemoteRatelimitStore};
+///
+/// let http = HttpBuilder::new("token")
+///     .ratelimit_store(Arc::new(RemoteRatelimitStore::tcp("127.0.0.1:7655")))
+///     .build();
+/// ```
+pub struct RemoteRatelimitStore {
+    addr: RemoteAddr,
+    connection: Mutex<Option<BufReader<Box<dyn Connection>>>>,
+}
+
+#[derive(Clone, Debug)]
+enum RemoteAddr {
+    Tcp(String),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+trait Connection: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<S: AsyncRead + AsyncWrite + Unpin + Send> Connection for S {}
+
+impl RemoteRatelimitStore {
+    /// Creates a store connecting to a [`RatelimitStoreServer`] on the given TCP address.
+    #[must_use]
+    pub fn tcp(addr: impl Into<String>) -> Self {
+        Self::_new(RemoteAddr::Tcp(addr.into()))
+    }
+
+    /// Creates a store connecting to a [`RatelimitStoreServer`] on the given Unix socket path.
+    #[cfg(unix)]
+    #[must_use]
+    pub fn unix(path: impl Into<PathBuf>) -> Self {
+        Self::_new(RemoteAddr::Unix(path.into()))
+    }
+
+    fn _new(addr: RemoteAddr) -> Self {
+        Self {
+            addr,
+            connection: Mutex::new(None),
+        }
+    }
+
+    async fn connect(&self) -> Result<Box<dyn Connection>> {
+        Ok(match &self.addr {
+            RemoteAddr::Tcp(addr) => Box::new(TcpStream::connect(addr).await?),
+            #[cfg(unix)]
+            RemoteAddr::Unix(path) => Box::new(UnixStream::connect(path).await?),
+        })
+    }
+
+    /// Sends a request line and returns the reply, without its trailing newline.
+    async fn send(&self, request: &str) -> Result<String> {
+        let mut connection = self.connection.lock().await;
+
+        let stream = match &mut *connection {
+            Some(stream) => stream,
+            None => connection.insert(BufReader::new(self.connect().await?)),
+        };
+
+        let result = send_request(stream, request).await;
+        if result.is_err() {
+            // Reconnect on the next request, as the connection may be in an unknown state.
+            *connection = None;
+        }
+
+        result
+    }
+}
+
+impl fmt::Debug for RemoteRatelimitStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteRatelimitStore").field("addr", &self.addr).finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl RatelimitStore for RemoteRatelimitStore {
+    #[instrument(skip(self))]
+    async fn acquire(&self, bucket: RatelimitingBucket) -> Result<Option<RatelimitWait>> {
+        let reply = self.send(&format!("acquire {bucket}\n")).await?;
+
+        if reply == "-" {
+            return Ok(None);
+        }
+
+        parse_wait(&reply).map(Some).ok_or_else(|| invalid_data("reply", &reply))
+    }
+
+    #[instrument(skip(self))]
+    async fn update(&self, bucket: RatelimitingBucket, update: &RatelimitUpdate) -> Result<()> {
+        self.send(&update_request(bucket, update)).await?;
+
+        Ok(())
+    }
+}
+
+// The protocol is line based. The client either sends:
+// - `acquire <bucket>\n`, to which the server replies with `-\n` if the request may be performed,
+//   or `<timeout in ms> <limit> <global>\n` otherwise;
+// - `update <bucket> <limit> <remaining> <reset in ms since the epoch> <reset after in ms> <retry
+//   after in ms> <global> <bucket hash>\n`, to which the server replies with `\n`. The bucket hash
+//   is the `X-RateLimit-Bucket` header of the response.
+// Missing values, such as the bucket hash of a response without that header, are sent as `-`, and
+// booleans as `0` or `1`.
+async fn send_request<S>(stream: &mut BufReader<S>, request: &str) -> Result<String>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    stream.write_all(request.as_bytes()).await?;
+    stream.flush().await?;
+
+    let mut reply = String::new();
+    if stream.read_line(&mut reply).await? == 0 {
+        return Err(IoError::from(ErrorKind::UnexpectedEof).into());
+    }
+
+    reply.truncate(reply.trim_end().len());
+    Ok(reply)
+}
+
+async fn handle_connection<S>(store: &dyn RatelimitStore, stream: S) -> Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut stream = BufReader::new(stream);
+    let mut request = String::new();
+
+    while stream.read_line(&mut request).await? != 0 {
+        let reply = handle_request(store, request.trim_end())
+            .await?
+            .ok_or_else(|| invalid_data("request", &request))?;
+
+        stream.write_all(reply.as_bytes()).await?;
+        stream.flush().await?;
+
+        request.clear();
+    }
+
+    Ok(())
+}
+
+async fn handle_request(store: &dyn RatelimitStore, request: &str) -> Result<Option<String>> {
+    let mut parts = request.split(' ');
+    let (Some(command), Some(Ok(bucket))) = (parts.next(), parts.next().map(str::parse)) else {
+        return Ok(None);
+    };
+
+    match command {
+        "acquire" => {
+            let reply = match store.acquire(bucket).await? {
+                Some(wait) => format!(
+                    "{} {} {}\n",
+                    wait.timeout.as_millis(),
+                    wait.limit,
+                    u8::from(wait.global)
+                ),
+                None => "-\n".to_string(),
+            };
+
+            Ok(Some(reply))
+        },
+        "update" => {
+            let Some(update) = parse_update(parts) else { return Ok(None) };
+            store.update(bucket, &update).await?;
+
+            Ok(Some("\n".to_string()))
+        },
+        _ => Ok(None),
+    }
+}
+
+fn invalid_data(kind: &str, line: &str) -> Error {
+    IoError::new(ErrorKind::InvalidData, format!("invalid ratelimit store {kind}: {line:?}")).into()
+}
+
+fn update_request(bucket: RatelimitingBucket, update: &RatelimitUpdate) -> String {
+    format!("update {bucket} {}\n", format_update(update))
+}
+
+fn format_update(update: &RatelimitUpdate) -> String {
+    fn field<T: ToString>(value: Option<T>) -> String {
+        value.map_or_else(|| "-".to_string(), |value| value.to_string())
+    }
+
+    let reset = update.reset.and_then(|reset| reset.duration_since(UNIX_EPOCH).ok());
+
+    format!(
+        "{} {} {} {} {} {} {}",
+        field(update.limit),
+        field(update.remaining),
+        field(reset.map(|reset| reset.as_millis())),
+        field(update.reset_after.map(|reset_after| reset_after.as_millis())),
+        field(update.retry_after.map(|retry_after| retry_after.as_millis())),
+        u8::from(update.global),
+        field(update.bucket.as_deref()),
+    )
+}
+
+fn parse_update<'a>(mut parts: impl Iterator<Item = &'a str>) -> Option<RatelimitUpdate> {
+    fn field<T: std::str::FromStr>(part: Option<&str>) -> Result<Option<T>, ()> {
+        match part.ok_or(())? {
+            "-" => Ok(None),
+            part => part.parse().map(Some).map_err(|_| ()),
+        }
+    }
+
+    Some(RatelimitUpdate {
+        limit: field(parts.next()).ok()?,
+        remaining: field(parts.next()).ok()?,
+        reset: field(parts.next()).ok()?.map(|reset| UNIX_EPOCH + Duration::from_millis(reset)),
+        reset_after: field(parts.next()).ok()?.map(Duration::from_millis),
+        retry_after: field(parts.next()).ok()?.map(Duration::from_millis),
+        global: parts.next()? == "1",
+        bucket: field(parts.next()).ok()?,
+    })
+}
+
+fn parse_wait(reply: &str) -> Option<RatelimitWait> {
+    let mut parts = reply.split(' ');
+
+    Some(RatelimitWait {
+        timeout: Duration::from_millis(parts.next()?.parse().ok()?),
+        limit: parts.next()?.parse().ok()?,
+        global: parts.next()? == "1",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::http::Route;
+    use crate::model::id::{ChannelId, MessageId};
+
+    fn bucket() -> RatelimitingBucket {
+        Route::ChannelMessages {
+            channel_id: ChannelId::new(7),
+        }
+        .ratelimiting_bucket()
+    }
+
+    #[test]
+    fn update_roundtrips_through_protocol() {
+        let update = RatelimitUpdate {
+            limit: Some(5),
+            remaining: Some(0),
+            reset: Some(UNIX_EPOCH + Duration::from_millis(1_560_704_880_423)),
+            reset_after: Some(Duration::from_millis(1500)),
+            retry_after: None,
+            global: false,
+            bucket: Some("abcd1234".into()),
+        };
+
+        let line = update_request(bucket(), &update);
+        assert_eq!(line, "update ChannelMessages/7 5 0 1560704880423 1500 - 0 abcd1234\n");
+        assert_eq!(parse_update(line.trim_end().split(' ').skip(2)), Some(update));
+
+        let update = RatelimitUpdate {
+            retry_after: Some(Duration::from_millis(250)),
+            global: true,
+            bucket: None,
+            ..RatelimitUpdate::default()
+        };
+
+        let line = update_request(bucket(), &update);
+        assert_eq!(line, "update ChannelMessages/7 - - - - 250 1 -\n");
+        assert_eq!(parse_update(line.trim_end().split(' ').skip(2)), Some(update));
+    }
+
+    #[test]
+    fn bucket_key_roundtrips() {
+        let bucket = bucket();
+        assert_eq!(bucket.to_string(), "ChannelMessages/7");
+        assert_eq!(bucket.to_string().parse(), Ok(bucket));
+
+        let unlimited = Route::Gateway.ratelimiting_bucket();
+        assert_eq!(unlimited.to_string().parse(), Ok(unlimited));
+        assert!("NotARoute/7".parse::<RatelimitingBucket>().is_err());
+    }
+
+    #[tokio::test]
+    async fn in_memory_store_waits_for_reset() {
+        let store = InMemoryRatelimitStore::new();
+        assert_eq!(store.acquire(bucket()).await.unwrap(), None);
+
+        let update = RatelimitUpdate {
+            limit: Some(5),
+            remaining: Some(0),
+            reset: Some(SystemTime::now() + Duration::from_secs(10)),
+            ..Default::default()
+        };
+        store.update(bucket(), &update).await.unwrap();
+
+        let wait = store.acquire(bucket()).await.unwrap().unwrap();
+        assert!(!wait.global);
+        assert_eq!(wait.limit, 5);
+        assert!(wait.timeout > Duration::from_secs(9));
+    }
+
+    #[tokio::test]
+    async fn routes_with_same_bucket_hash_share_ratelimit() {
+        let store = InMemoryRatelimitStore::new();
+        let messages = bucket();
+        let message = Route::ChannelMessage {
+            channel_id: ChannelId::new(7),
+            message_id: MessageId::new(1),
+        }
+        .ratelimiting_bucket();
+        let other_channel = Route::ChannelMessage {
+            channel_id: ChannelId::new(8),
+            message_id: MessageId::new(1),
+        }
+        .ratelimiting_bucket();
+
+        let hashed = RatelimitUpdate {
+            limit: Some(5),
+            remaining: Some(5),
+            bucket: Some("abcd1234".into()),
+            ..Default::default()
+        };
+        store.update(messages, &hashed).await.unwrap();
+        store.update(message, &hashed).await.unwrap();
+
+        let exhausted = RatelimitUpdate {
+            remaining: Some(0),
+            reset: Some(SystemTime::now() + Duration::from_secs(10)),
+            ..hashed
+        };
+        store.update(messages, &exhausted).await.unwrap();
+
+        assert!(store.acquire(message).await.unwrap().is_some());
+        assert_eq!(store.acquire(other_channel).await.unwrap(), None);
+
+        let bucket_hashes = store.bucket_hashes().await;
+        assert_eq!(bucket_hashes["ChannelMessages"], "abcd1234");
+        assert_eq!(bucket_hashes["ChannelMessage"], "abcd1234");
+    }
+
+    #[tokio::test]
+    async fn remote_store_shares_global_ratelimit() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let server = RatelimitStoreServer::new(Arc::new(InMemoryRatelimitStore::new()));
+        tokio::spawn(async move { server.serve_tcp(listener).await });
+
+        let first = RemoteRatelimitStore::tcp(addr.to_string());
+        let second = RemoteRatelimitStore::tcp(addr.to_string());
+        assert_eq!(second.acquire(bucket()).await.unwrap(), None);
+
+        let update = RatelimitUpdate {
+            retry_after: Some(Duration::from_secs(10)),
+            global: true,
+            ..Default::default()
+        };
+        first.update(bucket(), &update).await.unwrap();
+
+        let wait = second.acquire(Route::Gateway.ratelimiting_bucket()).await.unwrap().unwrap();
+        assert!(wait.global);
+        assert!(wait.timeout > Duration::from_secs(9));
+    }
+}
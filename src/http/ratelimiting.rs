@@ -33,6 +33,10 @@
 //! associated u64 as data. This is the Id of the parameter, differentiating between different
 //! ratelimits.
 //!
+//! Discord may also put several routes in the same bucket, which it identifies through the
+//! `X-RateLimit-Bucket` header. The [`InMemoryRatelimitStore`] learns this mapping from responses,
+//! after which routes with the same bucket hash and major parameter share one [`Ratelimit`].
+//!
 //! [Taken from]: https://discord.com/developers/docs/topics/rate-limits#rate-limits
 
 use std::collections::HashMap;
@@ -48,7 +52,10 @@ use tokio::sync::{Mutex, RwLock};
 use tokio::time::{sleep, Duration};
 use tracing::{debug, instrument};
 
+use super::proxy::TrustedProxy;
+use super::ratelimit_store::{InMemoryRatelimitStore, RatelimitStore, RatelimitUpdate};
 pub use super::routing::RatelimitingBucket;
+use super::transport::{execute, HttpTransport};
 use super::{HttpError, LightMethod, Request};
 use crate::internal::prelude::*;
 
@@ -66,9 +73,10 @@ pub struct RatelimitInfo {
 
 /// Ratelimiter for requests to the Discord API.
 ///
-/// This keeps track of ratelimit data for known routes through the [`Ratelimit`] implementation
-/// for each route: how many tickets are [`remaining`] until the user needs to wait for the known
-/// [`reset`] time, and the [`limit`] of requests that can be made within that time.
+/// This keeps track of ratelimit data for known routes through a [`RatelimitStore`]: how many
+/// tickets are [`remaining`] until the user needs to wait for the known [`reset`] time, and the
+/// [`limit`] of requests that can be made within that time. By default, this data is kept in
+/// memory by an [`InMemoryRatelimitStore`].
 ///
 /// When no tickets are available for some time, then the thread sleeps until that time passes. The
 /// mechanism is known as "pre-emptive ratelimiting".
@@ -83,10 +91,11 @@ pub struct RatelimitInfo {
 /// [`reset`]: Ratelimit::reset
 pub struct Ratelimiter {
     client: Client,
-    global: Arc<Mutex<()>>,
-    // When futures is implemented, make tasks clear out their respective entry when the 'reset'
-    // passes.
+    store: Arc<dyn RatelimitStore>,
+    // The routes of the default store, kept for the deprecated `Ratelimiter::routes`.
     routes: Arc<RwLock<HashMap<RatelimitingBucket, Arc<Mutex<Ratelimit>>>>>,
+    trusted_proxy: Option<TrustedProxy>,
+    transport: Option<Arc<dyn HttpTransport>>,
     token: SecretString,
     absolute_ratelimits: bool,
     ratelimit_callback: Box<dyn Fn(RatelimitInfo) + Send + Sync>,
@@ -96,8 +105,10 @@ impl fmt::Debug for Ratelimiter {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Ratelimiter")
             .field("client", &self.client)
-            .field("global", &self.global)
+            .field("store", &"dyn RatelimitStore")
             .field("routes", &self.routes)
+            .field("trusted_proxy", &self.trusted_proxy)
+            .field("transport", &self.transport.as_ref().map(|_| "dyn HttpTransport"))
             .field("token", &self.token)
             .field("absolute_ratelimits", &self.absolute_ratelimits)
             .field("ratelimit_callback", &"Fn(RatelimitInfo)")
@@ -115,10 +126,14 @@ impl Ratelimiter {
     }
 
     fn _new(client: Client, token: String) -> Self {
+        let store = InMemoryRatelimitStore::new();
+
         Self {
             client,
-            global: Arc::default(),
-            routes: Arc::default(),
+            routes: store.routes(),
+            store: Arc::new(store),
+            trusted_proxy: None,
+            transport: None,
             token: SecretString::new(token),
             ratelimit_callback: Box::new(|_| {}),
             absolute_ratelimits: false,
@@ -138,38 +153,43 @@ impl Ratelimiter {
         self.absolute_ratelimits = absolute_ratelimits;
     }
 
-    /// The routes mutex is a HashMap of each [`RatelimitingBucket`] and their respective ratelimit
-    /// information.
+    /// Sets the store keeping track of the ratelimits. This can be used to share ratelimits
+    /// between several processes using the same token, such as through a
+    /// [`RemoteRatelimitStore`].
+    ///
+    /// [`RemoteRatelimitStore`]: super::RemoteRatelimitStore
+    pub fn set_store(&mut self, store: Arc<dyn RatelimitStore>) {
+        self.store = store;
+        self.routes = Arc::default();
+    }
+
+    /// Sets a proxy which is trusted to ratelimit requests. When set, requests are sent to the
+    /// proxy without taking tickets from the [`RatelimitStore`], and are only delayed when the
+    /// proxy responds with a 429.
+    pub fn set_trusted_proxy(&mut self, trusted_proxy: Option<TrustedProxy>) {
+        self.trusted_proxy = trusted_proxy;
+    }
+
+    /// Sets the [`HttpTransport`] requests are sent through, instead of the [`reqwest`] client.
+    pub fn set_transport(&mut self, transport: Option<Arc<dyn HttpTransport>>) {
+        self.transport = transport;
+    }
+
+    /// The store keeping track of the ratelimit information of each [`RatelimitingBucket`].
     ///
     /// See the documentation for [`Ratelimit`] for more information on how the library handles
     /// ratelimiting.
+    #[must_use]
+    pub fn store(&self) -> Arc<dyn RatelimitStore> {
+        Arc::clone(&self.store)
+    }
+
+    /// The routes mutex is a HashMap of each [`RatelimitingBucket`] and their respective ratelimit
+    /// information.
     ///
-    /// # Examples
-    ///
-    /// View the `reset` time of the route for `ChannelsId(7)`:
-    ///
-    /// ```rust,no_run
-    /// use serenity::http::Route;
-    /// # use serenity::http::Http;
-    /// # use serenity::model::prelude::*;
-    ///
-    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let http: Http = unimplemented!();
-    /// let routes = http.ratelimiter.unwrap().routes();
-    /// let reader = routes.read().await;
-    ///
-    /// let channel_id = ChannelId::new(7);
-    /// let route = Route::Channel {
-    ///     channel_id,
-    /// };
-    /// if let Some(route) = reader.get(&route.ratelimiting_bucket()) {
-    ///     if let Some(reset) = route.lock().await.reset() {
-    ///         println!("Reset time at: {:?}", reset);
-    ///     }
-    /// }
-    /// # Ok(())
-    /// # }
-    /// ```
+    /// **Note**: This only contains the routes of the default [`InMemoryRatelimitStore`], and is
+    /// empty once another store is set with [`Self::set_store`].
+    #[deprecated = "Use InMemoryRatelimitStore::routes instead"]
     #[must_use]
     pub fn routes(&self) -> Arc<RwLock<HashMap<RatelimitingBucket, Arc<Mutex<Ratelimit>>>>> {
         Arc::clone(&self.routes)
@@ -177,92 +197,109 @@ impl Ratelimiter {
 
     /// # Errors
     ///
-    /// Only error kind that may be returned is [`Error::Http`].
+    /// Only error kind that may be returned is [`Error::Http`], unless the [`RatelimitStore`]
+    /// fails.
     #[instrument]
     pub async fn perform(&self, req: Request<'_>) -> Result<Response> {
-        loop {
-            // This will block if another thread hit the global ratelimit.
-            drop(self.global.lock().await);
+        if let Some(trusted_proxy) = &self.trusted_proxy {
+            return self.perform_proxied(req, trusted_proxy).await;
+        }
 
-            // Perform pre-checking here:
-            // - get the route's relevant rate
-            // - sleep if that route's already rate-limited until the end of the 'reset' time;
-            // - get the global rate;
-            // - sleep if there is 0 remaining
-            // - then, perform the request
-            let ratelimiting_bucket = req.route.ratelimiting_bucket();
-            let bucket =
-                Arc::clone(self.routes.write().await.entry(ratelimiting_bucket).or_default());
+        let ratelimiting_bucket = req.route.ratelimiting_bucket();
 
-            bucket.lock().await.pre_hook(&req, &self.ratelimit_callback).await;
+        loop {
+            // Perform pre-checking here: take a ticket from the store, which tells how long to
+            // wait if the global ratelimit was hit or if the route's ratelimit has 0 remaining,
+            // and sleep until the end of the 'reset' time before trying again.
+            while let Some(wait) = self.store.acquire(ratelimiting_bucket).await? {
+                debug!(
+                    "Pre-emptive ratelimit on route {:?} for {}ms",
+                    ratelimiting_bucket,
+                    wait.timeout.as_millis(),
+                );
+                (self.ratelimit_callback)(RatelimitInfo {
+                    timeout: wait.timeout,
+                    limit: wait.limit,
+                    method: req.method,
+                    path: req.route.path().to_string(),
+                    global: wait.global,
+                });
+
+                sleep(wait.timeout).await;
+            }
 
             let request = req.clone().build(&self.client, self.token.expose_secret(), None)?;
-            let response = self.client.execute(request.build()?).await?;
+            let response = execute(&self.client, self.transport.as_ref(), request.build()?).await?;
 
-            // Check if the request got ratelimited by checking for status 429, and if so, sleep
-            // for the value of the header 'retry-after' - which is in milliseconds - and then
-            // `continue` to try again
+            // Report the ratelimit headers to the store, which updates the 'limit', 'remaining',
+            // 'reset' and 'reset-after' of the route, or the global ratelimit.
             //
-            // If it didn't ratelimit, subtract one from the Ratelimit's 'remaining'.
-            //
-            // Update `reset` with the value of 'x-ratelimit-reset' header. Similarly, update
-            // `reset-after` with the 'x-ratelimit-reset-after' header.
-            //
-            // It _may_ be possible for the limit to be raised at any time, so check if it did from
-            // the value of the 'x-ratelimit-limit' header. If the limit was 5 and is now 7, add 2
-            // to the 'remaining'
+            // Check if the request got ratelimited by checking for status 429, and if so, sleep
+            // for the value of the header 'retry-after' and then `continue` to try again.
             if ratelimiting_bucket.is_none() {
                 return Ok(response);
             }
 
-            let redo = if response.headers().get("x-ratelimit-global").is_some() {
-                drop(self.global.lock().await);
-
-                Ok(
-                    if let Some(retry_after) =
-                        parse_header::<f64>(response.headers(), "retry-after")?
-                    {
-                        debug!(
-                            "Ratelimited on route {:?} for {:?}s",
-                            ratelimiting_bucket, retry_after
-                        );
-                        (self.ratelimit_callback)(RatelimitInfo {
-                            timeout: Duration::from_secs_f64(retry_after),
-                            limit: 50,
-                            method: req.method,
-                            path: req.route.path().to_string(),
-                            global: true,
-                        });
-                        sleep(Duration::from_secs_f64(retry_after)).await;
-
-                        true
-                    } else {
-                        false
-                    },
-                )
-            } else {
-                bucket
-                    .lock()
-                    .await
-                    .post_hook(&response, &req, &self.ratelimit_callback, self.absolute_ratelimits)
-                    .await
-            };
+            let update = RatelimitUpdate::from_response(&response, self.absolute_ratelimits)?;
+            self.store.update(ratelimiting_bucket, &update).await?;
+
+            let Some(retry_after) = update.retry_after else { return Ok(response) };
+
+            debug!("Ratelimited on route {:?} for {:?}", ratelimiting_bucket, retry_after);
+            (self.ratelimit_callback)(RatelimitInfo {
+                timeout: retry_after,
+                limit: if update.global { 50 } else { update.limit.unwrap_or_default() },
+                method: req.method,
+                path: req.route.path().to_string(),
+                global: update.global,
+            });
+
+            sleep(retry_after).await;
+        }
+    }
 
-            if !redo.unwrap_or(true) {
+    async fn perform_proxied(
+        &self,
+        req: Request<'_>,
+        trusted_proxy: &TrustedProxy,
+    ) -> Result<Response> {
+        loop {
+            let request = trusted_proxy.build_request(
+                &self.client,
+                self.token.expose_secret(),
+                req.clone(),
+            )?;
+            let response = execute(&self.client, self.transport.as_ref(), request).await?;
+
+            let Some(ratelimit) = trusted_proxy.ratelimit(&response)? else {
                 return Ok(response);
-            }
+            };
+
+            debug!(
+                "Ratelimited by proxy on route {:?} for {:?}",
+                req.route.ratelimiting_bucket(),
+                ratelimit.retry_after
+            );
+            (self.ratelimit_callback)(RatelimitInfo {
+                timeout: ratelimit.retry_after,
+                limit: parse_header(response.headers(), "x-ratelimit-limit")?.unwrap_or_default(),
+                method: req.method,
+                path: req.route.path().to_string(),
+                global: ratelimit.global,
+            });
+
+            sleep(ratelimit.retry_after).await;
         }
     }
 }
 
 /// A set of data containing information about the ratelimits for a particular
-/// [`RatelimitingBucket`], which is stored in [`Http`].
+/// [`RatelimitingBucket`], which is kept by the [`InMemoryRatelimitStore`].
 ///
 /// See the [Discord docs] on ratelimits for more information.
 ///
 /// **Note**: You should _not_ mutate any of the fields, as this can help cause 429s.
 ///
-/// [`Http`]: super::Http
 /// [Discord docs]: https://discord.com/developers/docs/topics/rate-limits
 #[derive(Debug)]
 pub struct Ratelimit {
@@ -277,20 +314,17 @@ pub struct Ratelimit {
 }
 
 impl Ratelimit {
-    #[instrument(skip(ratelimit_callback))]
-    pub async fn pre_hook(
-        &mut self,
-        req: &Request<'_>,
-        ratelimit_callback: &(dyn Fn(RatelimitInfo) + Send + Sync),
-    ) {
+    /// Takes a ticket for a request, returning how long to wait first if there are none
+    /// remaining.
+    pub fn acquire(&mut self) -> Option<Duration> {
         if self.limit() == 0 {
-            return;
+            return None;
         }
 
         let Some(reset) = self.reset else {
             // We're probably in the past.
             self.remaining = self.limit;
-            return;
+            return None;
         };
 
         let Ok(delay) = reset.duration_since(SystemTime::now()) else {
@@ -298,31 +332,65 @@ impl Ratelimit {
             if self.remaining() != 0 {
                 self.remaining -= 1;
             }
-            return;
+            return None;
         };
 
         if self.remaining() == 0 {
-            debug!(
-                "Pre-emptive ratelimit on route {:?} for {}ms",
-                req.route.ratelimiting_bucket(),
-                delay.as_millis(),
-            );
-            ratelimit_callback(RatelimitInfo {
-                timeout: delay,
-                limit: self.limit,
-                method: req.method,
-                path: req.route.path().to_string(),
-                global: false,
-            });
+            return Some(delay);
+        }
+
+        self.remaining -= 1;
+        None
+    }
 
-            sleep(delay).await;
+    /// Updates the ratelimit with the information from a response.
+    pub fn update(&mut self, update: &RatelimitUpdate) {
+        if let Some(limit) = update.limit {
+            self.limit = limit;
+        }
 
-            return;
+        if let Some(remaining) = update.remaining {
+            self.remaining = remaining;
         }
 
-        self.remaining -= 1;
+        if let Some(reset) = update.reset {
+            self.reset = Some(reset);
+        }
+
+        if let Some(reset_after) = update.reset_after {
+            self.reset_after = Some(reset_after);
+        }
+    }
+
+    #[deprecated = "Use Ratelimit::acquire instead"]
+    #[instrument(skip(ratelimit_callback))]
+    pub async fn pre_hook(
+        &mut self,
+        req: &Request<'_>,
+        ratelimit_callback: &(dyn Fn(RatelimitInfo) + Send + Sync),
+    ) {
+        let Some(delay) = self.acquire() else { return };
+
+        debug!(
+            "Pre-emptive ratelimit on route {:?} for {}ms",
+            req.route.ratelimiting_bucket(),
+            delay.as_millis(),
+        );
+        ratelimit_callback(RatelimitInfo {
+            timeout: delay,
+            limit: self.limit,
+            method: req.method,
+            path: req.route.path().to_string(),
+            global: false,
+        });
+
+        sleep(delay).await;
     }
 
+    /// # Errors
+    ///
+    /// Returns an [`Error::Http`] if the ratelimit headers of the response are invalid.
+    #[deprecated = "Use Ratelimit::update instead"]
     #[instrument(skip(ratelimit_callback))]
     pub async fn post_hook(
         &mut self,
@@ -331,52 +399,23 @@ impl Ratelimit {
         ratelimit_callback: &(dyn Fn(RatelimitInfo) + Send + Sync),
         absolute_ratelimits: bool,
     ) -> Result<bool> {
-        if let Some(limit) = parse_header(response.headers(), "x-ratelimit-limit")? {
-            self.limit = limit;
-        }
+        let update = RatelimitUpdate::from_response(response, absolute_ratelimits)?;
+        self.update(&update);
 
-        if let Some(remaining) = parse_header(response.headers(), "x-ratelimit-remaining")? {
-            self.remaining = remaining;
-        }
-
-        if absolute_ratelimits {
-            if let Some(reset) = parse_header::<f64>(response.headers(), "x-ratelimit-reset")? {
-                self.reset = Some(std::time::UNIX_EPOCH + Duration::from_secs_f64(reset));
-            }
-        }
-
-        if let Some(reset_after) =
-            parse_header::<f64>(response.headers(), "x-ratelimit-reset-after")?
-        {
-            if !absolute_ratelimits {
-                self.reset = Some(SystemTime::now() + Duration::from_secs_f64(reset_after));
-            }
-
-            self.reset_after = Some(Duration::from_secs_f64(reset_after));
-        }
+        let Some(retry_after) = update.retry_after else { return Ok(false) };
 
-        Ok(if response.status() != StatusCode::TOO_MANY_REQUESTS {
-            false
-        } else if let Some(retry_after) = parse_header::<f64>(response.headers(), "retry-after")? {
-            debug!(
-                "Ratelimited on route {:?} for {:?}s",
-                req.route.ratelimiting_bucket(),
-                retry_after
-            );
-            ratelimit_callback(RatelimitInfo {
-                timeout: Duration::from_secs_f64(retry_after),
-                limit: self.limit,
-                method: req.method,
-                path: req.route.path().to_string(),
-                global: false,
-            });
+        debug!("Ratelimited on route {:?} for {:?}", req.route.ratelimiting_bucket(), retry_after);
+        ratelimit_callback(RatelimitInfo {
+            timeout: retry_after,
+            limit: self.limit,
+            method: req.method,
+            path: req.route.path().to_string(),
+            global: false,
+        });
 
-            sleep(Duration::from_secs_f64(retry_after)).await;
+        sleep(retry_after).await;
 
-            true
-        } else {
-            false
-        })
+        Ok(true)
     }
 
     /// The total number of requests that can be made in a period of time.
@@ -419,7 +458,39 @@ impl Default for Ratelimit {
     }
 }
 
-fn parse_header<T: FromStr>(headers: &HeaderMap, header: &str) -> Result<Option<T>> {
+impl RatelimitUpdate {
+    /// Reads the ratelimit headers of a response.
+    fn from_response(response: &Response, absolute_ratelimits: bool) -> Result<Self> {
+        let headers = response.headers();
+        let reset_after =
+            parse_header::<f64>(headers, "x-ratelimit-reset-after")?.map(Duration::from_secs_f64);
+
+        let reset = if absolute_ratelimits {
+            parse_header::<f64>(headers, "x-ratelimit-reset")?
+                .map(|reset| std::time::UNIX_EPOCH + Duration::from_secs_f64(reset))
+        } else {
+            reset_after.map(|reset_after| SystemTime::now() + reset_after)
+        };
+
+        let retry_after = if response.status() == StatusCode::TOO_MANY_REQUESTS {
+            parse_header::<f64>(headers, "retry-after")?.map(Duration::from_secs_f64)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            limit: parse_header(headers, "x-ratelimit-limit")?,
+            remaining: parse_header(headers, "x-ratelimit-remaining")?,
+            reset,
+            reset_after,
+            retry_after,
+            global: headers.contains_key("x-ratelimit-global"),
+            bucket: parse_header(headers, "x-ratelimit-bucket")?,
+        })
+    }
+}
+
+pub(super) fn parse_header<T: FromStr>(headers: &HeaderMap, header: &str) -> Result<Option<T>> {
     let Some(header) = headers.get(header) else { return Ok(None) };
 
     let unicode =
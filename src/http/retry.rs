@@ -0,0 +1,224 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+use super::{HttpError, LightMethod};
+use crate::internal::prelude::*;
+
+/// Configures how [`Http`] retries requests that failed for transient reasons.
+///
+/// A request is retried when:
+/// - the connection to Discord could not be established, for any method;
+/// - the connection was reset or timed out, for [idempotent methods];
+/// - Discord responded with one of the [retryable status codes], for [idempotent methods].
+///
+/// Between attempts, the request waits with an exponential backoff: the first retry waits around
+/// the [initial backoff], and each following retry doubles it, up to the [maximum backoff]. A
+/// random jitter of up to half of the backoff is applied, so that many requests failing at once
+/// do not all retry at the same moment.
+///
+/// Ratelimited requests (429s) are handled by the [`Ratelimiter`], and don't count as attempts.
+///
+/// Every attempt is performed in its own `attempt` tracing span.
+///
+/// # Examples
+///
+/// Retry idempotent requests up to 5 times on 500s as well as the default 502, 503 and 504:
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use serenity::http::{HttpBuilder, RetryPolicy, StatusCode};
+///
+/// let policy = RetryPolicy::new()
+///     .max_attempts(5)
+///     .backoff(Duration::from_millis(250), Duration::from_secs(5))
+///     .status_codes([
+///         StatusCode::INTERNAL_SERVER_ERROR,
+///         StatusCode::BAD_GATEWAY,
+///         StatusCode::SERVICE_UNAVAILABLE,
+///         StatusCode::GATEWAY_TIMEOUT,
+///     ]);
+///
+/// let http = HttpBuilder::new("token").retry_policy(policy).build();
+/// ```
+///
+/// [`Http`]: super::Http
+/// [`Ratelimiter`]: super::Ratelimiter
+/// [idempotent methods]: Self::idempotent_methods
+/// [retryable status codes]: Self::status_codes
+/// [initial backoff]: Self::backoff
+/// [maximum backoff]: Self::backoff
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    status_codes: Vec<StatusCode>,
+    idempotent_methods: Vec<LightMethod>,
+}
+
+impl RetryPolicy {
+    /// Creates a policy making at most 3 attempts, with a backoff starting at 500ms and capped at
+    /// 10 seconds. Responses with a 502, 503 or 504 status are retried for [`LightMethod::Get`],
+    /// [`LightMethod::Put`] and [`LightMethod::Delete`] requests.
+    pub fn new() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+            status_codes: vec![
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            idempotent_methods: vec![LightMethod::Get, LightMethod::Put, LightMethod::Delete],
+        }
+    }
+
+    /// Sets the maximum number of attempts made for a request, including the first one. A value
+    /// of `0` or `1` disables retries.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Sets the backoff before the first retry, and the maximum backoff between two attempts.
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Sets the response status codes which cause a request to be retried.
+    pub fn status_codes(mut self, status_codes: impl IntoIterator<Item = StatusCode>) -> Self {
+        self.status_codes = status_codes.into_iter().collect();
+        self
+    }
+
+    /// Sets the methods which are safe to retry after the request may have reached Discord.
+    ///
+    /// Requests with other methods are only retried if the connection could not be established.
+    pub fn idempotent_methods(mut self, methods: impl IntoIterator<Item = LightMethod>) -> Self {
+        self.idempotent_methods = methods.into_iter().collect();
+        self
+    }
+
+    /// Whether a request which made `attempt` attempts and received a response with the given
+    /// status should be retried.
+    pub(super) fn retries_status(
+        &self,
+        attempt: u32,
+        method: LightMethod,
+        status: StatusCode,
+    ) -> bool {
+        attempt < self.max_attempts
+            && self.idempotent_methods.contains(&method)
+            && self.status_codes.contains(&status)
+    }
+
+    /// Whether a request which made `attempt` attempts and failed with the given error should be
+    /// retried.
+    pub(super) fn retries_error(&self, attempt: u32, method: LightMethod, error: &Error) -> bool {
+        let Error::Http(HttpError::Request(error)) = error else { return false };
+
+        attempt < self.max_attempts
+            && (error.is_connect()
+                || (self.idempotent_methods.contains(&method)
+                    && (error.is_timeout() || error.is_request())))
+    }
+
+    /// The time to wait before the next attempt, after `attempt` attempts were made.
+    pub(super) fn backoff_for(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        let backoff = self.initial_backoff.saturating_mul(1 << exponent).min(self.max_backoff);
+
+        let half = backoff / 2;
+        let max_jitter = u64::try_from(half.as_millis()).unwrap_or(u64::MAX);
+        let jitter = RandomState::new().build_hasher().finish() % max_jitter.saturating_add(1);
+
+        half + Duration::from_millis(jitter)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::http::{HttpBuilder, Request, Route};
+
+    #[test]
+    fn backoff_grows_exponentially_with_jitter() {
+        let policy = RetryPolicy::new().backoff(Duration::from_millis(100), Duration::from_secs(1));
+
+        for _ in 0..100 {
+            let first = policy.backoff_for(1);
+            assert!(first >= Duration::from_millis(50) && first <= Duration::from_millis(100));
+
+            let third = policy.backoff_for(3);
+            assert!(third >= Duration::from_millis(200) && third <= Duration::from_millis(400));
+
+            let capped = policy.backoff_for(10);
+            assert!(capped >= Duration::from_millis(500) && capped <= Duration::from_secs(1));
+        }
+    }
+
+    #[test]
+    fn status_retries_depend_on_method_and_attempts() {
+        let policy = RetryPolicy::new();
+
+        assert!(policy.retries_status(1, LightMethod::Get, StatusCode::BAD_GATEWAY));
+        assert!(policy.retries_status(2, LightMethod::Delete, StatusCode::GATEWAY_TIMEOUT));
+        assert!(!policy.retries_status(3, LightMethod::Get, StatusCode::BAD_GATEWAY));
+        assert!(!policy.retries_status(1, LightMethod::Post, StatusCode::BAD_GATEWAY));
+        assert!(!policy.retries_status(1, LightMethod::Get, StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(!policy.max_attempts(1).retries_status(
+            1,
+            LightMethod::Get,
+            StatusCode::BAD_GATEWAY
+        ));
+    }
+
+    #[tokio::test]
+    async fn request_is_retried_on_service_unavailable() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let responses = [
+                "HTTP/1.1 503 Service Unavailable\r\nconnection: close\r\ncontent-length: 0\r\n\r\n",
+                "HTTP/1.1 200 OK\r\nconnection: close\r\ncontent-type: application/json\r\ncontent-length: 2\r\n\r\n{}",
+            ];
+            for response in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = [0; 4096];
+                let _ = stream.read(&mut buf).await.unwrap();
+                stream.write_all(response.as_bytes()).await.unwrap();
+                stream.shutdown().await.unwrap();
+            }
+        });
+
+        let http = HttpBuilder::new("token")
+            .proxy(format!("http://{addr}"))
+            .ratelimiter_disabled(true)
+            .retry_policy(RetryPolicy::new().backoff(Duration::ZERO, Duration::ZERO))
+            .build();
+
+        let request = Request::new(Route::Gateway, LightMethod::Get);
+        let response = http.request(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
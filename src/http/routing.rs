@@ -1,18 +1,63 @@
 use std::borrow::Cow;
-use std::mem::Discriminant;
+use std::fmt;
 use std::num::NonZeroU64;
+use std::str::FromStr;
 
 use crate::model::id::*;
 
 /// Used to group requests together for ratelimiting.
+///
+/// A bucket is displayed as a stable key, such as `ChannelMessages/381880193700069377`, which can
+/// be used to share ratelimits across processes, and be parsed back through [`FromStr`]. Requests
+/// which are not ratelimited by route use the key `none`.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-pub struct RatelimitingBucket(Option<(std::mem::Discriminant<Route<'static>>, Option<NonZeroU64>)>);
+pub struct RatelimitingBucket(Option<(&'static str, Option<NonZeroU64>)>);
 
 impl RatelimitingBucket {
     #[must_use]
     pub fn is_none(&self) -> bool {
         self.0.is_none()
     }
+
+    /// The name of the [`Route`] variant the bucket was created from, if ratelimited.
+    #[must_use]
+    pub fn route(&self) -> Option<&'static str> {
+        self.0.map(|(route, _)| route)
+    }
+
+    /// The major parameter of the bucket, such as the channel or guild Id, if any.
+    #[must_use]
+    pub fn major_parameter(&self) -> Option<NonZeroU64> {
+        self.0.and_then(|(_, id)| id)
+    }
+}
+
+impl fmt::Display for RatelimitingBucket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some((route, Some(id))) => write!(f, "{route}/{id}"),
+            Some((route, None)) => f.write_str(route),
+            None => f.write_str("none"),
+        }
+    }
+}
+
+impl FromStr for RatelimitingBucket {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "none" {
+            return Ok(Self(None));
+        }
+
+        let (route, id) = match s.split_once('/') {
+            Some((route, id)) => (route, Some(id.parse().map_err(|_| ())?)),
+            None => (s, None),
+        };
+
+        let route = ROUTE_NAMES.iter().find(|name| **name == route).ok_or(())?;
+        Ok(Self(Some((route, id))))
+    }
 }
 
 enum RatelimitingKind {
@@ -34,6 +79,8 @@ macro_rules! routes {
             $ratelimiting_kind:expr;
         )+
     }) => {
+        const ROUTE_NAMES: &[&str] = &[$(stringify!($name)),+];
+
         #[derive(Clone, Copy, Debug)]
         pub enum Route<$lt> {
             $(
@@ -60,12 +107,10 @@ macro_rules! routes {
                     )+
                 };
 
-                // This avoids adding a lifetime on RatelimitingBucket and causing lifetime infection
-                // SAFETY: std::mem::discriminant erases lifetimes.
-                let discriminant = unsafe {
-                    std::mem::transmute::<Discriminant<Route<'a>>, Discriminant<Route<'static>>>(
-                        std::mem::discriminant(self),
-                    )
+                let name = match *self {
+                    $(
+                        Self::$name { .. } => stringify!($name),
+                    )+
                 };
 
                 RatelimitingBucket(ratelimiting_kind.map(|r| {
@@ -73,7 +118,7 @@ macro_rules! routes {
                         RatelimitingKind::PathAndId(id) => Some(id),
                         RatelimitingKind::Path => None,
                     };
-                    (discriminant, id)
+                    (name, id)
                 }))
             }
 
@@ -142,6 +187,14 @@ routes! ('a, {
     api!("/channels/{}/pins", channel_id),
     Some(RatelimitingKind::PathAndId(channel_id.into()));
 
+    ChannelPollGetAnswerVoters { channel_id: ChannelId, message_id: MessageId, answer_id: AnswerId },
+    api!("/channels/{}/polls/{}/answers/{}", channel_id, message_id, answer_id),
+    Some(RatelimitingKind::PathAndId(channel_id.into()));
+
+    ChannelPollExpire { channel_id: ChannelId, message_id: MessageId },
+    api!("/channels/{}/polls/{}/expire", channel_id, message_id),
+    Some(RatelimitingKind::PathAndId(channel_id.into()));
+
     ChannelTyping { channel_id: ChannelId },
     api!("/channels/{}/typing", channel_id),
     Some(RatelimitingKind::PathAndId(channel_id.into()));
@@ -154,6 +207,10 @@ routes! ('a, {
     api!("/channels/{}/messages/{}/threads", channel_id, message_id),
     Some(RatelimitingKind::PathAndId(channel_id.into()));
 
+    ChannelSendSoundboardSound { channel_id: ChannelId },
+    api!("/channels/{}/send-soundboard-sound", channel_id),
+    Some(RatelimitingKind::PathAndId(channel_id.into()));
+
     ChannelThreads { channel_id: ChannelId },
     api!("/channels/{}/threads", channel_id),
     Some(RatelimitingKind::PathAndId(channel_id.into()));
@@ -310,6 +367,14 @@ routes! ('a, {
     api!("/guilds/{}/scheduled-events/{}/users", guild_id, event_id),
     Some(RatelimitingKind::PathAndId(guild_id.into()));
 
+    GuildSoundboardSound { guild_id: GuildId, sound_id: SoundId },
+    api!("/guilds/{}/soundboard-sounds/{}", guild_id, sound_id),
+    Some(RatelimitingKind::PathAndId(guild_id.into()));
+
+    GuildSoundboardSounds { guild_id: GuildId },
+    api!("/guilds/{}/soundboard-sounds", guild_id),
+    Some(RatelimitingKind::PathAndId(guild_id.into()));
+
     GuildSticker { guild_id: GuildId, sticker_id: StickerId },
     api!("/guilds/{}/stickers/{}", guild_id, sticker_id),
     Some(RatelimitingKind::PathAndId(guild_id.into()));
@@ -334,6 +399,10 @@ routes! ('a, {
     api!("/guilds/{}/webhooks", guild_id),
     Some(RatelimitingKind::PathAndId(guild_id.into()));
 
+    GuildOnboarding { guild_id: GuildId },
+    api!("/guilds/{}/onboarding", guild_id),
+    Some(RatelimitingKind::PathAndId(guild_id.into()));
+
     GuildWelcomeScreen { guild_id: GuildId },
     api!("/guilds/{}/welcome-screen", guild_id),
     Some(RatelimitingKind::PathAndId(guild_id.into()));
@@ -366,6 +435,10 @@ routes! ('a, {
     status!("/scheduled-maintenances/upcoming.json"),
     None;
 
+    SoundboardDefaultSounds,
+    api!("/soundboard-default-sounds"),
+    Some(RatelimitingKind::Path);
+
     Sticker { sticker_id: StickerId },
     api!("/stickers/{}", sticker_id),
     Some(RatelimitingKind::Path);
@@ -434,6 +507,14 @@ routes! ('a, {
     api!("/interactions/{}/{}/callback", interaction_id, token),
     Some(RatelimitingKind::PathAndId(interaction_id.into()));
 
+    ApplicationEmoji { application_id: ApplicationId, emoji_id: EmojiId },
+    api!("/applications/{}/emojis/{}", application_id, emoji_id),
+    Some(RatelimitingKind::PathAndId(application_id.into()));
+
+    ApplicationEmojis { application_id: ApplicationId },
+    api!("/applications/{}/emojis", application_id),
+    Some(RatelimitingKind::PathAndId(application_id.into()));
+
     Command { application_id: ApplicationId, command_id: CommandId },
     api!("/applications/{}/commands/{}", application_id, command_id),
     Some(RatelimitingKind::PathAndId(application_id.into()));
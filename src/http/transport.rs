@@ -0,0 +1,376 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use http_crate::response::Builder as ResponseBuilder;
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use reqwest::{
+    Client,
+    Method,
+    Request as ReqwestRequest,
+    Response,
+    ResponseBuilderExt,
+    StatusCode,
+};
+use url::Url;
+
+use super::{LightMethod, Route};
+use crate::internal::prelude::*;
+use crate::json::{self, Value};
+
+/// Sends the requests built by [`Http`] and returns the responses, instead of a
+/// [`reqwest::Client`].
+///
+/// Requests are still ratelimited and retried before reaching the transport. This is mainly
+/// intended for testing code which performs requests without reaching Discord, using a
+/// [`MockTransport`].
+///
+/// [`Http`]: super::Http
+#[async_trait]
+pub trait HttpTransport: Send + Sync {
+    /// Sends the request and returns the response.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no response could be obtained for the request.
+    async fn execute(&self, request: ReqwestRequest) -> Result<TransportResponse>;
+}
+
+/// A response returned by an [`HttpTransport`].
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct TransportResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl TransportResponse {
+    /// Creates a response with the given status, and no headers or body.
+    pub fn new(status: StatusCode) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: Vec::new(),
+        }
+    }
+
+    /// Creates a response with the given status and JSON body.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Json`] if the body fails to serialize.
+    pub fn json(status: StatusCode, body: &impl serde::Serialize) -> Result<Self> {
+        let mut response = Self::new(status);
+        response.headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        response.body = json::to_vec(body)?;
+        Ok(response)
+    }
+
+    fn into_response(self, url: Url) -> Result<Response> {
+        let mut builder = ResponseBuilder::new().status(self.status).url(url);
+        if let Some(headers) = builder.headers_mut() {
+            headers.extend(self.headers);
+        }
+
+        let response = builder
+            .body(self.body)
+            .map_err(|_| Error::Other("Failed to build the transport response"))?;
+        Ok(response.into())
+    }
+}
+
+/// Sends the request through the transport if there is one, or the client otherwise.
+pub(super) async fn execute(
+    client: &Client,
+    transport: Option<&Arc<dyn HttpTransport>>,
+    request: ReqwestRequest,
+) -> Result<Response> {
+    match transport {
+        Some(transport) => {
+            let url = request.url().clone();
+            transport.execute(request).await?.into_response(url)
+        },
+        None => Ok(client.execute(request).await?),
+    }
+}
+
+/// An [`HttpTransport`] replaying expected requests in order, and recording the requests it
+/// received.
+///
+/// Each request is checked against the next [`ExpectedRequest`], panicking if it doesn't match,
+/// and is responded to with the response given alongside it.
+///
+/// # Examples
+///
+/// Test that a message is sent without reaching Discord:
+///
+/// ```rust
+/// # #[cfg(feature = "model")]
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::sync::Arc;
+///
+/// use serenity::builder::CreateMessage;
+/// use serenity::http::{
+///     ExpectedRequest,
+///     HttpBuilder,
+///     LightMethod,
+///     MockTransport,
+///     Route,
+///     StatusCode,
+///     TransportResponse,
+/// };
+/// use serenity::json::json;
+/// use serenity::model::prelude::*;
+///
+/// let channel_id = ChannelId::new(7);
+/// let transport = Arc::new(MockTransport::new());
+/// transport.expect(
+///     ExpectedRequest::new(Route::ChannelMessages { channel_id }, LightMethod::Post)
+///         .json(json!({
+///             "content": "hello",
+///             "tts": false,
+///             "embeds": [],
+///             "sticker_ids": [],
+///             "attachments": [],
+///             "enforce_nonce": false,
+///         })),
+///     TransportResponse::json(StatusCode::OK, &Message::default())?,
+/// );
+///
+/// let http = HttpBuilder::new("token").transport(Arc::clone(&transport) as _).build();
+/// channel_id.send_message(&http, CreateMessage::new().content("hello")).await?;
+///
+/// transport.assert_done();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct MockTransport {
+    expected: Mutex<VecDeque<(ExpectedRequest, TransportResponse)>>,
+    recorded: Mutex<Vec<RecordedRequest>>,
+}
+
+impl MockTransport {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expects the next request to match `request`, replying to it with `response`.
+    pub fn expect(&self, request: ExpectedRequest, response: TransportResponse) {
+        self.expected.lock().expect("poisoned").push_back((request, response));
+    }
+
+    /// The requests received so far, in order.
+    #[must_use]
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.recorded.lock().expect("poisoned").clone()
+    }
+
+    /// Asserts that every expected request was received.
+    ///
+    /// # Panics
+    ///
+    /// Panics if some expected requests were not received.
+    pub fn assert_done(&self) {
+        let expected = self.expected.lock().expect("poisoned");
+        let remaining: Vec<_> = expected.iter().map(|(request, _)| request).collect();
+        assert!(remaining.is_empty(), "Expected requests were not received: {remaining:#?}");
+    }
+}
+
+impl fmt::Debug for MockTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockTransport")
+            .field("expected", &self.expected)
+            .field("recorded", &self.recorded)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl HttpTransport for MockTransport {
+    async fn execute(&self, request: ReqwestRequest) -> Result<TransportResponse> {
+        let request = RecordedRequest::new(&request);
+        self.recorded.lock().expect("poisoned").push(request.clone());
+
+        let next = self.expected.lock().expect("poisoned").pop_front();
+        let Some((expected, response)) = next else {
+            panic!("Received an unexpected request: {request:#?}");
+        };
+
+        expected.assert_matches(&request);
+        Ok(response)
+    }
+}
+
+/// A request expected by a [`MockTransport`].
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct ExpectedRequest {
+    method: Method,
+    path: String,
+    params: Vec<(String, String)>,
+    json: Option<Value>,
+}
+
+impl ExpectedRequest {
+    /// Expects a request to the given route, with the given method.
+    pub fn new(route: Route<'_>, method: LightMethod) -> Self {
+        let url = Url::parse(&route.path()).expect("Routes are valid URLs");
+
+        Self {
+            method: method.reqwest_method(),
+            path: url.path().to_string(),
+            params: Vec::new(),
+            json: None,
+        }
+    }
+
+    /// Expects the request to have the given query parameter.
+    pub fn param(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.params.push((name.into(), value.into()));
+        self
+    }
+
+    /// Expects the request to have exactly the given JSON body.
+    pub fn json(mut self, json: Value) -> Self {
+        self.json = Some(json);
+        self
+    }
+
+    fn assert_matches(&self, request: &RecordedRequest) {
+        assert_eq!(
+            (&self.method, self.path.as_str()),
+            (&request.method, request.url.path()),
+            "Request does not match the expected route"
+        );
+
+        for (name, value) in &self.params {
+            let found = request.url.query_pairs().any(|(n, v)| n == *name && v == *value);
+            assert!(found, "Request {} is missing the parameter {name}={value}", request.url);
+        }
+
+        if let Some(json) = &self.json {
+            assert_eq!(Some(json), request.json.as_ref(), "Request body does not match");
+        }
+    }
+}
+
+/// A request received by a [`MockTransport`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct RecordedRequest {
+    pub method: Method,
+    pub url: Url,
+    pub headers: HeaderMap,
+    /// The body of the request, if it was JSON.
+    pub json: Option<Value>,
+}
+
+impl RecordedRequest {
+    fn new(request: &ReqwestRequest) -> Self {
+        let json = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .and_then(|bytes| json::from_slice(bytes).ok());
+
+        Self {
+            method: request.method().clone(),
+            url: request.url().clone(),
+            headers: request.headers().clone(),
+            json,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "model"))]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::builder::CreateMessage;
+    use crate::http::{HttpBuilder, HttpError};
+    use crate::json::json;
+    use crate::model::prelude::*;
+
+    #[tokio::test]
+    async fn replays_model_requests() {
+        let channel_id = ChannelId::new(7);
+        let guild_id = GuildId::new(8);
+        let user_id = UserId::new(9);
+
+        let transport = Arc::new(MockTransport::new());
+        transport.expect(
+            ExpectedRequest::new(
+                Route::ChannelMessages {
+                    channel_id,
+                },
+                LightMethod::Post,
+            )
+            .json(json!({
+                "content": "hello",
+                "tts": false,
+                "embeds": [],
+                "sticker_ids": [],
+                "attachments": [],
+                "enforce_nonce": false,
+            })),
+            TransportResponse::json(StatusCode::OK, &Message {
+                content: "hello".into(),
+                ..Default::default()
+            })
+            .unwrap(),
+        );
+        transport.expect(
+            ExpectedRequest::new(
+                Route::GuildBan {
+                    guild_id,
+                    user_id,
+                },
+                LightMethod::Put,
+            )
+            .param("delete_message_seconds", "86400"),
+            TransportResponse::new(StatusCode::NO_CONTENT),
+        );
+
+        let http = HttpBuilder::new("token").transport(Arc::clone(&transport) as _).build();
+
+        let message =
+            channel_id.send_message(&http, CreateMessage::new().content("hello")).await.unwrap();
+        assert_eq!(message.content, "hello");
+
+        guild_id.ban(&http, user_id, 1).await.unwrap();
+
+        transport.assert_done();
+        assert_eq!(transport.requests()[1].headers["authorization"], "Bot token");
+    }
+
+    #[tokio::test]
+    async fn error_responses_are_decoded() {
+        let transport = Arc::new(MockTransport::new());
+        transport.expect(
+            ExpectedRequest::new(Route::Gateway, LightMethod::Get),
+            TransportResponse::json(
+                StatusCode::FORBIDDEN,
+                &json!({
+                    "code": 50001,
+                    "message": "Missing Access",
+                }),
+            )
+            .unwrap(),
+        );
+
+        let http = HttpBuilder::new("token").transport(Arc::clone(&transport) as _).build();
+        let Err(Error::Http(HttpError::UnsuccessfulRequest(error))) = http.get_gateway().await
+        else {
+            panic!("Expected an unsuccessful request");
+        };
+
+        assert_eq!(error.status_code, StatusCode::FORBIDDEN);
+        assert_eq!(error.error.code, 50001);
+        assert_eq!(error.url, "https://discord.com/api/v10/gateway");
+    }
+}
@@ -143,6 +143,20 @@ where
     Ok(result)
 }
 
+/// Serialize the given data structure as JSON into the I/O stream.
+#[allow(clippy::missing_errors_doc)]
+pub fn to_writer<W, T>(writer: W, value: &T) -> Result<()>
+where
+    W: std::io::Write,
+    T: ?Sized + Serialize,
+{
+    #[cfg(not(feature = "simd_json"))]
+    serde_json::to_writer(writer, value)?;
+    #[cfg(feature = "simd_json")]
+    simd_json::to_writer(writer, value)?;
+    Ok(())
+}
+
 /// Serialize the given data structure as a JSON byte vector.
 #[allow(clippy::missing_errors_doc)]
 pub fn to_vec<T>(value: &T) -> Result<Vec<u8>>
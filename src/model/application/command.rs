@@ -7,6 +7,7 @@ use crate::builder::{Builder, CreateCommand};
 #[cfg(feature = "model")]
 use crate::http::{CacheHttp, Http};
 use crate::internal::prelude::*;
+use crate::model::application::{InstallationContext, InteractionContext};
 use crate::model::channel::ChannelType;
 use crate::model::id::{
     ApplicationId,
@@ -80,6 +81,12 @@ pub struct Command {
     pub nsfw: bool,
     /// An autoincremented version identifier updated during substantial record changes.
     pub version: CommandVersionId,
+    /// The installation contexts where the command is available, only for globally-scoped
+    /// commands. Defaults to the application's configured contexts.
+    pub integration_types: Option<Vec<InstallationContext>>,
+    /// The interaction contexts where the command can be used, only for globally-scoped
+    /// commands. By default, commands can be used in all contexts.
+    pub contexts: Option<Vec<InteractionContext>>,
 }
 
 #[cfg(feature = "model")]
@@ -18,7 +18,12 @@ use crate::client::Context;
 use crate::http::{CacheHttp, Http};
 use crate::internal::prelude::*;
 use crate::json::{self, JsonError};
-use crate::model::application::{CommandOptionType, CommandType};
+use crate::model::application::{
+    AuthorizingIntegrationOwners,
+    CommandOptionType,
+    CommandType,
+    InteractionContext,
+};
 use crate::model::channel::{Attachment, Message, PartialChannel};
 use crate::model::guild::{Member, PartialMember, Role};
 use crate::model::id::{
@@ -55,6 +60,10 @@ pub struct CommandInteraction {
     /// The data of the interaction which was triggered.
     pub data: CommandData,
     /// The guild Id this interaction was sent from, if there is one.
+    ///
+    /// **Note**: For applications installed to the invoking user, the bot user may not be a
+    /// member of this guild, in which case the guild is not cached, and [`Self::app_permissions`]
+    /// are the only permissions of the application there.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub guild_id: Option<GuildId>,
     /// Channel that the interaction was sent from.
@@ -81,6 +90,11 @@ pub struct CommandInteraction {
     pub guild_locale: Option<String>,
     /// For monetized applications, any entitlements of the invoking user.
     pub entitlements: Vec<Entitlement>,
+    /// The installations of the application which authorized the interaction.
+    #[serde(default)]
+    pub authorizing_integration_owners: AuthorizingIntegrationOwners,
+    /// Where the interaction was triggered from.
+    pub context: Option<InteractionContext>,
 }
 
 #[cfg(feature = "model")]
@@ -806,7 +820,8 @@ impl From<TargetId> for UserId {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::json::{assert_json, json};
+    use crate::json::{assert_json, from_value, json};
+    use crate::model::application::InstallationContext;
 
     #[test]
     fn nested_options() {
@@ -879,4 +894,43 @@ mod tests {
             ]),
         );
     }
+
+    #[test]
+    fn user_installed_command_interaction() {
+        let interaction: CommandInteraction = from_value(json!({
+            "id": "1",
+            "application_id": "2",
+            "type": 2,
+            "data": {
+                "id": "3",
+                "name": "ping",
+                "type": 1,
+            },
+            "guild_id": "4",
+            "channel_id": "5",
+            "user": {
+                "id": "6",
+                "username": "user",
+                "discriminator": "0",
+                "global_name": null,
+                "avatar": null,
+            },
+            "token": "token",
+            "version": 1,
+            "app_permissions": "2048",
+            "locale": "en-US",
+            "entitlements": [],
+            "authorizing_integration_owners": {"1": "6"},
+            "context": 0,
+        }))
+        .unwrap();
+
+        assert_eq!(interaction.guild_id, Some(GuildId::new(4)));
+        assert!(interaction.member.is_none());
+        assert_eq!(interaction.user.id, UserId::new(6));
+        assert_eq!(interaction.context, Some(InteractionContext::Guild));
+        assert_eq!(interaction.authorizing_integration_owners.user_id(), Some(UserId::new(6)));
+        assert_eq!(interaction.authorizing_integration_owners.guild_id(), None);
+        assert!(!interaction.authorizing_integration_owners.contains(InstallationContext::Guild));
+    }
 }
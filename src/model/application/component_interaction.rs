@@ -34,6 +34,9 @@ pub struct ComponentInteraction {
     /// The data of the interaction which was triggered.
     pub data: ComponentInteractionData,
     /// The guild Id this interaction was sent from, if there is one.
+    ///
+    /// **Note**: See the note on [`CommandInteraction::guild_id`] about applications installed to
+    /// the invoking user.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub guild_id: Option<GuildId>,
     /// Channel that the interaction was sent from.
@@ -62,6 +65,11 @@ pub struct ComponentInteraction {
     pub guild_locale: Option<String>,
     /// For monetized applications, any entitlements of the invoking user.
     pub entitlements: Vec<Entitlement>,
+    /// The installations of the application which authorized the interaction.
+    #[serde(default)]
+    pub authorizing_integration_owners: AuthorizingIntegrationOwners,
+    /// Where the interaction was triggered from.
+    pub context: Option<InteractionContext>,
 }
 
 #[cfg(feature = "model")]
@@ -232,6 +240,11 @@ impl<'de> Deserialize<'de> for ComponentInteraction {
             // If `member` is present, `user` wasn't sent and is still filled with default data
             interaction.user = member.user.clone();
         }
+        // The message of an interaction with an application installed to the user doesn't have
+        // a guild Id, even when sent in a guild
+        if interaction.message.guild_id.is_none() {
+            interaction.message.guild_id = interaction.guild_id;
+        }
         Ok(interaction)
     }
 }
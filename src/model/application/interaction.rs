@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+use std::num::NonZeroU64;
+
 use serde::de::{Deserialize, Deserializer, Error as DeError};
 use serde::ser::{Serialize, Serializer};
 
@@ -5,7 +8,7 @@ use super::{CommandInteraction, ComponentInteraction, ModalInteraction, PingInte
 use crate::internal::prelude::*;
 use crate::json::from_value;
 use crate::model::guild::PartialMember;
-use crate::model::id::{ApplicationId, InteractionId};
+use crate::model::id::{ApplicationId, GuildId, InteractionId, MessageId, UserId};
 use crate::model::monetization::Entitlement;
 use crate::model::user::User;
 use crate::model::utils::deserialize_val;
@@ -102,6 +105,28 @@ impl Interaction {
         }
     }
 
+    /// Gets where the interaction was triggered from.
+    #[must_use]
+    pub fn context(&self) -> Option<InteractionContext> {
+        match self {
+            Self::Ping(_) => None,
+            Self::Command(i) | Self::Autocomplete(i) => i.context,
+            Self::Component(i) => i.context,
+            Self::Modal(i) => i.context,
+        }
+    }
+
+    /// Gets the installations of the application which authorized the interaction.
+    #[must_use]
+    pub fn authorizing_integration_owners(&self) -> Option<&AuthorizingIntegrationOwners> {
+        match self {
+            Self::Ping(_) => None,
+            Self::Command(i) | Self::Autocomplete(i) => Some(&i.authorizing_integration_owners),
+            Self::Component(i) => Some(&i.authorizing_integration_owners),
+            Self::Modal(i) => Some(&i.authorizing_integration_owners),
+        }
+    }
+
     /// Converts this to a [`PingInteraction`]
     #[must_use]
     pub fn ping(self) -> Option<PingInteraction> {
@@ -313,3 +338,210 @@ pub struct MessageInteraction {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub member: Option<PartialMember>,
 }
+
+enum_number! {
+    /// Where an application can be installed, also called its supported installation contexts.
+    ///
+    /// [Discord docs](https://discord.com/developers/docs/resources/application#application-object-application-integration-types).
+    #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+    #[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+    #[serde(from = "u8", into = "u8")]
+    #[non_exhaustive]
+    pub enum InstallationContext {
+        /// The application is installed to a guild, and usable by its members.
+        Guild = 0,
+        /// The application is installed to a user, and usable by that user everywhere.
+        User = 1,
+        _ => Unknown(u8),
+    }
+}
+
+enum_number! {
+    /// Where an interaction can be used, or was triggered from.
+    ///
+    /// [Discord docs](https://discord.com/developers/docs/interactions/receiving-and-responding#interaction-object-interaction-context-types).
+    #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+    #[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+    #[serde(from = "u8", into = "u8")]
+    #[non_exhaustive]
+    pub enum InteractionContext {
+        /// The interaction can be used within guilds.
+        Guild = 0,
+        /// The interaction can be used within the DM channel with the application's bot user.
+        BotDm = 1,
+        /// The interaction can be used within group DMs and DMs other than the one with the
+        /// application's bot user.
+        PrivateChannel = 2,
+        _ => Unknown(u8),
+    }
+}
+
+/// The owner of an installation of the application which authorized an interaction.
+///
+/// [Discord docs](https://discord.com/developers/docs/interactions/receiving-and-responding#interaction-object-authorizing-integration-owners-object).
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum AuthorizingIntegrationOwner {
+    /// The application was installed to a guild. This is the Id of that guild, or [`None`] if the
+    /// interaction was triggered from the DM channel with the application's bot user.
+    GuildInstall(Option<GuildId>),
+    /// The application was installed to the user with the given Id.
+    UserInstall(UserId),
+    /// An installation context not covered by the above, along with the raw Id sent for it.
+    Unknown(InstallationContext, u64),
+}
+
+/// The installations of the application which authorized an interaction, keyed by
+/// [`InstallationContext`] in the raw payload.
+///
+/// For example, a command of an application installed both to a guild and to the invoking user
+/// contains both a [`AuthorizingIntegrationOwner::GuildInstall`] and a
+/// [`AuthorizingIntegrationOwner::UserInstall`].
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct AuthorizingIntegrationOwners(pub Vec<AuthorizingIntegrationOwner>);
+
+impl AuthorizingIntegrationOwners {
+    /// Returns the guild the application was installed to, if it was authorized by a guild
+    /// installation triggered within that guild.
+    #[must_use]
+    pub fn guild_id(&self) -> Option<GuildId> {
+        self.0.iter().find_map(|owner| match owner {
+            AuthorizingIntegrationOwner::GuildInstall(guild_id) => *guild_id,
+            _ => None,
+        })
+    }
+
+    /// Returns the user the application was installed to, if it was authorized by a user
+    /// installation.
+    #[must_use]
+    pub fn user_id(&self) -> Option<UserId> {
+        self.0.iter().find_map(|owner| match owner {
+            AuthorizingIntegrationOwner::UserInstall(user_id) => Some(*user_id),
+            _ => None,
+        })
+    }
+
+    /// Whether the interaction was authorized by an installation of the given context.
+    #[must_use]
+    pub fn contains(&self, context: InstallationContext) -> bool {
+        self.0.iter().any(|owner| {
+            let kind = match owner {
+                AuthorizingIntegrationOwner::GuildInstall(_) => InstallationContext::Guild,
+                AuthorizingIntegrationOwner::UserInstall(_) => InstallationContext::User,
+                AuthorizingIntegrationOwner::Unknown(kind, _) => *kind,
+            };
+            kind == context
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for AuthorizingIntegrationOwners {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
+        let map = HashMap::<String, String>::deserialize(deserializer)?;
+
+        let mut owners = Vec::with_capacity(map.len());
+        for (key, value) in map {
+            let kind: u8 = key.parse().map_err(DeError::custom)?;
+            // A guild installation used in the DM channel with the bot user has an Id of 0.
+            let raw_id = value.parse::<u64>().map_err(DeError::custom)?;
+            let id = NonZeroU64::new(raw_id);
+            owners.push(match InstallationContext::from(kind) {
+                InstallationContext::Guild => {
+                    AuthorizingIntegrationOwner::GuildInstall(id.map(GuildId::from))
+                },
+                InstallationContext::User => AuthorizingIntegrationOwner::UserInstall(
+                    id.map(UserId::from).ok_or_else(|| DeError::custom("invalid user Id 0"))?,
+                ),
+                kind @ InstallationContext::Unknown(_) => {
+                    AuthorizingIntegrationOwner::Unknown(kind, raw_id)
+                },
+            });
+        }
+
+        Ok(Self(owners))
+    }
+}
+
+impl Serialize for AuthorizingIntegrationOwners {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for owner in &self.0 {
+            let (kind, id) = match *owner {
+                AuthorizingIntegrationOwner::GuildInstall(guild_id) => {
+                    (InstallationContext::Guild, guild_id.map_or(0, GuildId::get))
+                },
+                AuthorizingIntegrationOwner::UserInstall(user_id) => {
+                    (InstallationContext::User, user_id.get())
+                },
+                AuthorizingIntegrationOwner::Unknown(kind, id) => (kind, id),
+            };
+            map.serialize_entry(&u8::from(kind).to_string(), &id.to_string())?;
+        }
+        map.end()
+    }
+}
+
+/// Metadata about the interaction which a [`Message`] is a response to, including the
+/// installations which authorized it.
+///
+/// [`Message`]: crate::model::channel::Message
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/channel#message-interaction-metadata-object).
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct MessageInteractionMetadata {
+    /// The Id of the interaction.
+    pub id: InteractionId,
+    /// The type of the interaction.
+    #[serde(rename = "type")]
+    pub kind: InteractionType,
+    /// The user who triggered the interaction.
+    pub user: User,
+    /// The installations of the application which authorized the interaction.
+    #[serde(default)]
+    pub authorizing_integration_owners: AuthorizingIntegrationOwners,
+    /// The Id of the original response message, present only on follow-up messages.
+    pub original_response_message_id: Option<MessageId>,
+    /// The Id of the message containing the component which triggered the interaction, present
+    /// only for component interactions.
+    pub interacted_message_id: Option<MessageId>,
+    /// The metadata of the interaction which opened the modal, present only for modal submit
+    /// interactions.
+    pub triggering_interaction_metadata: Option<Box<MessageInteractionMetadata>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::{from_value, json, to_value};
+
+    #[test]
+    fn authorizing_integration_owners() {
+        let value = json!({"0": "0", "1": "2"});
+        let owners: AuthorizingIntegrationOwners = from_value(value.clone()).unwrap();
+
+        assert!(owners.0.contains(&AuthorizingIntegrationOwner::GuildInstall(None)));
+        assert_eq!(owners.user_id(), Some(UserId::new(2)));
+        assert_eq!(owners.guild_id(), None);
+        assert!(owners.contains(InstallationContext::Guild));
+        assert_eq!(to_value(&owners).unwrap(), value);
+
+        let owners: AuthorizingIntegrationOwners = from_value(json!({"0": "3"})).unwrap();
+        assert_eq!(owners.guild_id(), Some(GuildId::new(3)));
+        assert!(!owners.contains(InstallationContext::User));
+
+        let value = json!({"7": "4"});
+        let owners: AuthorizingIntegrationOwners = from_value(value.clone()).unwrap();
+        assert_eq!(owners.0, [AuthorizingIntegrationOwner::Unknown(
+            InstallationContext::Unknown(7),
+            4
+        )]);
+        assert_eq!(to_value(&owners).unwrap(), value);
+    }
+}
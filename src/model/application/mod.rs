@@ -17,10 +17,22 @@ pub use oauth::*;
 mod ping_interaction;
 pub use ping_interaction::*;
 
+#[cfg(feature = "model")]
+use super::guild::Emoji;
+#[cfg(feature = "model")]
+use super::id::EmojiId;
 use super::id::{ApplicationId, GenericId, GuildId, SkuId, UserId};
 use super::misc::ImageHash;
 use super::user::User;
 use super::Permissions;
+#[cfg(feature = "model")]
+use crate::builder::CreateAttachment;
+#[cfg(feature = "model")]
+use crate::http::Http;
+#[cfg(feature = "model")]
+use crate::internal::prelude::*;
+#[cfg(feature = "model")]
+use crate::json::json;
 
 /// Partial information about the given application.
 ///
@@ -78,6 +90,151 @@ pub struct CurrentApplicationInfo {
     pub role_connections_verification_url: Option<String>,
 }
 
+#[cfg(feature = "model")]
+impl ApplicationId {
+    /// Gets all emojis owned by the application.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the application does not exist or is not owned by the current
+    /// user.
+    pub async fn emojis(self, http: impl AsRef<Http>) -> Result<Vec<Emoji>> {
+        http.as_ref().get_application_emojis(self).await
+    }
+
+    /// Gets an emoji owned by the application by its Id.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the emoji does not exist.
+    pub async fn emoji(self, http: impl AsRef<Http>, emoji_id: EmojiId) -> Result<Emoji> {
+        http.as_ref().get_application_emoji(self, emoji_id).await
+    }
+
+    /// Creates an emoji owned by the application, which its bot user can use in every guild and
+    /// channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the name is invalid or already taken, if the image is too big,
+    /// or if the application reached its emoji limit.
+    pub async fn create_emoji(
+        self,
+        http: impl AsRef<Http>,
+        name: &str,
+        image: &CreateAttachment,
+    ) -> Result<Emoji> {
+        let map = json!({
+            "name": name,
+            "image": image.to_base64(),
+        });
+
+        http.as_ref().create_application_emoji(self, &map).await
+    }
+
+    /// Renames an emoji owned by the application.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the name is invalid or the emoji does not exist.
+    pub async fn edit_emoji(
+        self,
+        http: impl AsRef<Http>,
+        emoji_id: EmojiId,
+        name: &str,
+    ) -> Result<Emoji> {
+        let map = json!({
+            "name": name,
+        });
+
+        http.as_ref().edit_application_emoji(self, emoji_id, &map).await
+    }
+
+    /// Deletes an emoji owned by the application.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the emoji does not exist.
+    pub async fn delete_emoji(self, http: impl AsRef<Http>, emoji_id: EmojiId) -> Result<()> {
+        http.as_ref().delete_application_emoji(self, emoji_id).await
+    }
+}
+
+#[cfg(feature = "model")]
+impl CurrentApplicationInfo {
+    /// Gets all emojis owned by the application.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the application is not owned by the current user.
+    pub async fn emojis(&self, http: impl AsRef<Http>) -> Result<Vec<Emoji>> {
+        self.id.emojis(http).await
+    }
+
+    /// Gets an emoji owned by the application by its Id.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the emoji does not exist.
+    pub async fn emoji(&self, http: impl AsRef<Http>, emoji_id: EmojiId) -> Result<Emoji> {
+        self.id.emoji(http, emoji_id).await
+    }
+
+    /// Creates an emoji owned by the application, which its bot user can use in every guild and
+    /// channel.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use serenity::http::Http;
+    /// # use serenity::builder::CreateAttachment;
+    /// #
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let http: Http = unimplemented!();
+    /// let info = http.get_current_application_info().await?;
+    /// let image = CreateAttachment::path("./ferris.png").await?;
+    /// let emoji = info.create_emoji(&http, "ferris", &image).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the name is invalid or already taken, if the image is too big,
+    /// or if the application reached its emoji limit.
+    pub async fn create_emoji(
+        &self,
+        http: impl AsRef<Http>,
+        name: &str,
+        image: &CreateAttachment,
+    ) -> Result<Emoji> {
+        self.id.create_emoji(http, name, image).await
+    }
+
+    /// Renames an emoji owned by the application.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the name is invalid or the emoji does not exist.
+    pub async fn edit_emoji(
+        &self,
+        http: impl AsRef<Http>,
+        emoji_id: EmojiId,
+        name: &str,
+    ) -> Result<Emoji> {
+        self.id.edit_emoji(http, emoji_id, name).await
+    }
+
+    /// Deletes an emoji owned by the application.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the emoji does not exist.
+    pub async fn delete_emoji(&self, http: impl AsRef<Http>, emoji_id: EmojiId) -> Result<()> {
+        self.id.delete_emoji(http, emoji_id).await
+    }
+}
+
 /// Information about the Team group of the application.
 ///
 /// [Discord docs](https://discord.com/developers/docs/topics/teams#data-models-team-object).
@@ -171,3 +328,101 @@ pub struct InstallParams {
     pub scopes: Vec<Scope>,
     pub permissions: Permissions,
 }
+
+#[cfg(all(test, feature = "model"))]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::http::{
+        ExpectedRequest,
+        HttpBuilder,
+        LightMethod,
+        MockTransport,
+        Route,
+        StatusCode,
+        TransportResponse,
+    };
+    use crate::json::from_value;
+
+    #[tokio::test]
+    async fn application_emojis() {
+        let application_id = ApplicationId::new(5);
+        let emoji_id = EmojiId::new(6);
+        let emoji = json!({"id": "6", "name": "ferris"});
+        let image = CreateAttachment::bytes(vec![1, 2, 3], "ferris.png");
+        let ok = |body| TransportResponse::json(StatusCode::OK, &body).unwrap();
+
+        let transport = Arc::new(MockTransport::new());
+        transport.expect(
+            ExpectedRequest::new(
+                Route::ApplicationEmojis {
+                    application_id,
+                },
+                LightMethod::Get,
+            ),
+            ok(json!({"items": [emoji]})),
+        );
+        transport.expect(
+            ExpectedRequest::new(
+                Route::ApplicationEmoji {
+                    application_id,
+                    emoji_id,
+                },
+                LightMethod::Get,
+            ),
+            ok(emoji.clone()),
+        );
+        transport.expect(
+            ExpectedRequest::new(
+                Route::ApplicationEmojis {
+                    application_id,
+                },
+                LightMethod::Post,
+            )
+            .json(json!({"name": "ferris", "image": image.to_base64()})),
+            ok(emoji.clone()),
+        );
+        transport.expect(
+            ExpectedRequest::new(
+                Route::ApplicationEmoji {
+                    application_id,
+                    emoji_id,
+                },
+                LightMethod::Patch,
+            )
+            .json(json!({"name": "crab"})),
+            ok(emoji.clone()),
+        );
+        transport.expect(
+            ExpectedRequest::new(
+                Route::ApplicationEmoji {
+                    application_id,
+                    emoji_id,
+                },
+                LightMethod::Delete,
+            ),
+            TransportResponse::new(StatusCode::NO_CONTENT),
+        );
+
+        // No application Id is set on the client, so requests must use the one of the info.
+        let http = HttpBuilder::new("token").transport(Arc::clone(&transport) as _).build();
+        let info: CurrentApplicationInfo = from_value(json!({
+            "id": "5",
+            "name": "app",
+            "description": "",
+            "bot_public": true,
+            "bot_require_code_grant": false,
+            "verify_key": "",
+        }))
+        .unwrap();
+
+        assert_eq!(info.emojis(&http).await.unwrap()[0].id, emoji_id);
+        assert_eq!(info.emoji(&http, emoji_id).await.unwrap().name, "ferris");
+        info.create_emoji(&http, "ferris", &image).await.unwrap();
+        application_id.edit_emoji(&http, emoji_id, "crab").await.unwrap();
+        application_id.delete_emoji(&http, emoji_id).await.unwrap();
+
+        transport.assert_done();
+    }
+}
@@ -28,6 +28,9 @@ pub struct ModalInteraction {
     /// The data of the interaction which was triggered.
     pub data: ModalInteractionData,
     /// The guild Id this interaction was sent from, if there is one.
+    ///
+    /// **Note**: See the note on [`CommandInteraction::guild_id`] about applications installed to
+    /// the invoking user.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub guild_id: Option<GuildId>,
     /// Channel that the interaction was sent from.
@@ -60,6 +63,11 @@ pub struct ModalInteraction {
     pub guild_locale: Option<String>,
     /// For monetized applications, any entitlements of the invoking user.
     pub entitlements: Vec<Entitlement>,
+    /// The installations of the application which authorized the interaction.
+    #[serde(default)]
+    pub authorizing_integration_owners: AuthorizingIntegrationOwners,
+    /// Where the interaction was triggered from.
+    pub context: Option<InteractionContext>,
 }
 
 #[cfg(feature = "model")]
@@ -201,6 +209,10 @@ impl<'de> Deserialize<'de> for ModalInteraction {
             // If `member` is present, `user` wasn't sent and is still filled with default data
             interaction.user = member.user.clone();
         }
+        // See the matching comment in `ComponentInteraction`'s `Deserialize` impl.
+        if let Some(message) = interaction.message.as_mut().filter(|m| m.guild_id.is_none()) {
+            message.guild_id = interaction.guild_id;
+        }
         Ok(interaction)
     }
 }
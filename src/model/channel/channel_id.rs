@@ -575,6 +575,107 @@ impl ChannelId {
         http.as_ref().crosspost_message(self, message_id.into()).await
     }
 
+    /// Immediately ends the poll of a [`Message`], returning the message.
+    ///
+    /// **Note**: Only the author of the message can end its poll.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the message has no poll, or if the current user is not its
+    /// author.
+    pub async fn end_poll(
+        self,
+        http: impl AsRef<Http>,
+        message_id: impl Into<MessageId>,
+    ) -> Result<Message> {
+        http.as_ref().expire_poll(self, message_id.into()).await
+    }
+
+    /// Gets the users who voted for an answer of the poll of a [`Message`].
+    ///
+    /// At most `limit` users are returned, up to 100, defaulting to 25. The optional `after` user
+    /// is used for pagination; see [`Self::poll_answer_voters_iter`] to stream over all voters.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the current user lacks permission to view the channel, or if the
+    /// message has no poll.
+    pub async fn poll_answer_voters(
+        self,
+        http: impl AsRef<Http>,
+        message_id: impl Into<MessageId>,
+        answer_id: AnswerId,
+        after: Option<UserId>,
+        limit: Option<u8>,
+    ) -> Result<Vec<User>> {
+        let limit = limit.map(|limit| limit.clamp(1, 100));
+        http.as_ref().get_answer_voters(self, message_id.into(), answer_id, after, limit).await
+    }
+
+    /// Streams over all the users who voted for an answer of the poll of a [`Message`].
+    ///
+    /// This is accomplished and equivalent to repeated calls to [`Self::poll_answer_voters`]. A
+    /// buffer of at most 100 users is used to reduce the number of calls necessary.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use serenity::model::id::{AnswerId, ChannelId, MessageId};
+    /// # use serenity::http::Http;
+    /// #
+    /// # async fn run() {
+    /// # let channel_id = ChannelId::new(1);
+    /// # let message_id = MessageId::new(2);
+    /// # let ctx: Http = unimplemented!();
+    /// use serenity::futures::StreamExt;
+    ///
+    /// let mut voters = channel_id.poll_answer_voters_iter(&ctx, message_id, AnswerId(1)).boxed();
+    /// while let Some(voter_result) = voters.next().await {
+    ///     match voter_result {
+    ///         Ok(user) => println!("{} voted for the first answer.", user.name),
+    ///         Err(error) => eprintln!("Uh oh! Error: {}", error),
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn poll_answer_voters_iter<H: AsRef<Http>>(
+        self,
+        http: H,
+        message_id: impl Into<MessageId>,
+        answer_id: AnswerId,
+    ) -> impl Stream<Item = Result<User>> {
+        PollAnswerVotersIter::<H>::stream(http, self, message_id.into(), answer_id)
+    }
+
+    /// Plays a [`SoundboardSound`] in the voice channel. Default sounds and sounds of the guild
+    /// of the channel have no `source_guild_id`, while sounds of other guilds require it.
+    ///
+    /// **Note**: The current user must be connected to the voice channel, and requires the [Speak]
+    /// and [Use Soundboard] permissions. Playing sounds of other guilds additionally requires the
+    /// [Use External Sounds] permission.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the current user lacks permission, or is not connected to the
+    /// channel.
+    ///
+    /// [Speak]: Permissions::SPEAK
+    /// [Use Soundboard]: Permissions::USE_SOUNDBOARD
+    /// [Use External Sounds]: Permissions::USE_EXTERNAL_SOUNDS
+    pub async fn send_soundboard_sound(
+        self,
+        http: impl AsRef<Http>,
+        sound_id: impl Into<SoundId>,
+        source_guild_id: Option<GuildId>,
+    ) -> Result<()> {
+        let map = json!({
+            "sound_id": sound_id.into(),
+            "source_guild_id": source_guild_id,
+        });
+
+        http.as_ref().send_soundboard_sound(self, &map).await
+    }
+
     /// Gets the list of [`Message`]s which are pinned to the channel.
     ///
     /// **Note**: Returns an empty [`Vec`] if the current user does not have the [Read Message
@@ -1230,3 +1331,93 @@ impl<H: AsRef<Http>> MessagesIter<H> {
         })
     }
 }
+
+/// A helper class returned by [`ChannelId::poll_answer_voters_iter`]
+#[derive(Clone, Debug)]
+#[cfg(feature = "model")]
+pub struct PollAnswerVotersIter<H: AsRef<Http>> {
+    http: H,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    answer_id: AnswerId,
+    buffer: Vec<User>,
+    after: Option<UserId>,
+    tried_fetch: bool,
+}
+
+#[cfg(feature = "model")]
+impl<H: AsRef<Http>> PollAnswerVotersIter<H> {
+    fn new(
+        http: H,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        answer_id: AnswerId,
+    ) -> PollAnswerVotersIter<H> {
+        PollAnswerVotersIter {
+            http,
+            channel_id,
+            message_id,
+            answer_id,
+            buffer: Vec::new(),
+            after: None,
+            tried_fetch: false,
+        }
+    }
+
+    /// Fills the `self.buffer` cache with the next voters, replacing its content.
+    ///
+    /// If fewer voters than requested are returned, `self.after` is set to [`None`], indicating
+    /// that no more calls ought to be made.
+    async fn refresh(&mut self) -> Result<()> {
+        // Number of voters to fetch.
+        let grab_size = 100;
+
+        self.buffer = self
+            .channel_id
+            .poll_answer_voters(
+                self.http.as_ref(),
+                self.message_id,
+                self.answer_id,
+                self.after,
+                Some(grab_size),
+            )
+            .await?;
+
+        self.after = if self.buffer.len() < usize::from(grab_size) {
+            None
+        } else {
+            self.buffer.last().map(|user| user.id)
+        };
+
+        // The stream pops from the end of the buffer.
+        self.buffer.reverse();
+
+        self.tried_fetch = true;
+
+        Ok(())
+    }
+
+    /// Streams over all the users who voted for an answer of a poll.
+    ///
+    /// This is accomplished and equivalent to repeated calls to
+    /// [`ChannelId::poll_answer_voters`]. A buffer of at most 100 users is used to reduce the
+    /// number of calls necessary.
+    pub fn stream(
+        http: impl AsRef<Http>,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        answer_id: AnswerId,
+    ) -> impl Stream<Item = Result<User>> {
+        let init_state = PollAnswerVotersIter::new(http, channel_id, message_id, answer_id);
+
+        futures::stream::unfold(init_state, |mut state| async {
+            if state.buffer.is_empty() && state.after.is_some() || !state.tried_fetch {
+                if let Err(error) = state.refresh().await {
+                    return Some((Err(error), state));
+                }
+            }
+
+            state.buffer.pop().map(|entry| (Ok(entry), state))
+        })
+    }
+}
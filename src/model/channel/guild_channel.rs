@@ -722,6 +722,10 @@ impl GuildChannel {
     ///
     /// The Id of the argument must be a [`Member`] of the [`Guild`] that the channel is in.
     ///
+    /// **Note**: This only applies the channel's own permission overwrites. Use
+    /// [`Cache::permissions_in`] to resolve the parent of threads and apply the permissions Discord
+    /// implicitly denies.
+    ///
     /// # Examples
     ///
     /// Calculate the permissions of a [`User`] who posted a [`Message`] in a channel:
@@ -110,6 +110,11 @@ pub struct Message {
     ///
     /// [`Interaction`]: crate::model::application::Interaction
     pub interaction: Option<Box<MessageInteraction>>,
+    /// Sent if the message is a response to an [`Interaction`], including the installations of
+    /// the application which authorized it.
+    ///
+    /// [`Interaction`]: crate::model::application::Interaction
+    pub interaction_metadata: Option<Box<MessageInteractionMetadata>>,
     /// The thread that was started from this message, includes thread member object.
     pub thread: Option<GuildChannel>,
     /// The components of this message
@@ -125,6 +130,8 @@ pub struct Message {
     /// Data of the role subscription purchase or renewal that prompted this
     /// [`MessageType::RoleSubscriptionPurchase`] message.
     pub role_subscription_data: Option<RoleSubscriptionData>,
+    /// The poll attached to the message.
+    pub poll: Option<Box<Poll>>,
     // Field omitted: stickers (it's deprecated by Discord)
     /// The Id of the [`Guild`] that the message was sent in. This value will only be present if
     /// this message was received over the gateway, therefore **do not use this to check if message
@@ -236,6 +243,18 @@ impl Message {
         self.channel_id.delete_message(cache_http.http(), self.id).await
     }
 
+    /// Immediately ends the [`Poll`] of the message, returning the updated message.
+    ///
+    /// **Note**: Only the author of the message can end its poll.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the message has no poll, or if the current user is not its
+    /// author.
+    pub async fn end_poll(&self, http: impl AsRef<Http>) -> Result<Message> {
+        self.channel_id.end_poll(http, self.id).await
+    }
+
     /// Deletes all of the [`Reaction`]s associated with the message.
     ///
     /// **Note**: Requires the [Manage Messages] permission.
@@ -944,6 +963,8 @@ enum_number! {
         StageSpeaker = 29,
         StageTopic = 31,
         GuildApplicationPremiumSubscription = 32,
+        /// The results of a poll which ended.
+        PollResult = 46,
         _ => Unknown(u8),
     }
 }
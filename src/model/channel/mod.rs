@@ -6,6 +6,7 @@ mod embed;
 mod guild_channel;
 mod message;
 mod partial_channel;
+mod poll;
 mod private_channel;
 mod reaction;
 
@@ -19,6 +20,7 @@ pub use self::embed::*;
 pub use self::guild_channel::*;
 pub use self::message::*;
 pub use self::partial_channel::*;
+pub use self::poll::*;
 pub use self::private_channel::*;
 pub use self::reaction::*;
 #[cfg(feature = "model")]
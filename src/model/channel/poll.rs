@@ -0,0 +1,133 @@
+use crate::model::prelude::*;
+
+/// A poll attached to a [`Message`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/poll#poll-object).
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct Poll {
+    /// The question of the poll. Only [`PollMedia::text`] is supported for questions.
+    pub question: PollMedia,
+    /// The answers available in the poll.
+    pub answers: Vec<PollAnswer>,
+    /// When the poll ends.
+    pub expiry: Option<Timestamp>,
+    /// Whether a user may vote for more than one answer.
+    pub allow_multiselect: bool,
+    /// The layout of the poll.
+    pub layout_type: PollLayoutType,
+    /// The results of the poll.
+    ///
+    /// This may be absent even if users voted, for example in messages fetched before the votes
+    /// were counted.
+    pub results: Option<PollResults>,
+}
+
+/// The text and emoji of a [`Poll`]'s question or answer.
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/poll#poll-media-object).
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct PollMedia {
+    /// The text of the question or answer.
+    pub text: Option<String>,
+    /// The emoji of the answer. Questions can not have an emoji.
+    pub emoji: Option<ReactionType>,
+}
+
+/// An answer of a [`Poll`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/poll#poll-answer-object).
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct PollAnswer {
+    /// The Id of the answer, unique within the poll.
+    pub answer_id: AnswerId,
+    /// The text and emoji of the answer.
+    pub poll_media: PollMedia,
+}
+
+enum_number! {
+    /// The layout of a [`Poll`].
+    ///
+    /// [Discord docs](https://discord.com/developers/docs/resources/poll#layout-type).
+    #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Deserialize, Serialize)]
+    #[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+    #[serde(from = "u8", into = "u8")]
+    #[non_exhaustive]
+    pub enum PollLayoutType {
+        /// The default layout.
+        #[default]
+        Default = 1,
+        _ => Unknown(u8),
+    }
+}
+
+/// The results of a [`Poll`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/poll#poll-results-object).
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct PollResults {
+    /// Whether the votes have been precisely counted. Until then, the counts may be inaccurate.
+    pub is_finalized: bool,
+    /// The number of votes for each answer. Answers without any vote may be omitted.
+    pub answer_counts: Vec<PollAnswerCount>,
+}
+
+/// The number of votes for an answer of a [`Poll`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/poll#poll-results-object-poll-answer-count-object-structure).
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct PollAnswerCount {
+    /// The Id of the answer.
+    pub id: AnswerId,
+    /// The number of votes for the answer.
+    pub count: u64,
+    /// Whether the current user voted for the answer.
+    pub me_voted: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::{from_value, json};
+
+    #[test]
+    fn poll_deserialize() {
+        let poll: Poll = from_value(json!({
+            "question": {"text": "Best fruit?"},
+            "answers": [
+                {"answer_id": 1, "poll_media": {"text": "Apple", "emoji": {"id": null, "name": "\u{1f34e}"}}},
+                {"answer_id": 2, "poll_media": {"text": "Banana", "emoji": {"id": "7", "name": "banana"}}},
+            ],
+            "expiry": "2024-05-01T12:00:00.000000+00:00",
+            "allow_multiselect": false,
+            "layout_type": 1,
+            "results": {
+                "is_finalized": false,
+                "answer_counts": [{"id": 2, "count": 3, "me_voted": true}],
+            },
+        }))
+        .unwrap();
+
+        assert_eq!(poll.question.text.as_deref(), Some("Best fruit?"));
+        assert_eq!(poll.answers[0].answer_id, AnswerId(1));
+        assert_eq!(
+            poll.answers[0].poll_media.emoji,
+            Some(ReactionType::Unicode("\u{1f34e}".into()))
+        );
+        assert!(matches!(poll.answers[1].poll_media.emoji, Some(ReactionType::Custom { .. })));
+        assert_eq!(poll.layout_type, PollLayoutType::Default);
+
+        let results = poll.results.unwrap();
+        assert_eq!(results.answer_counts[0].id, AnswerId(2));
+        assert_eq!(results.answer_counts[0].count, 3);
+    }
+}
@@ -17,6 +17,7 @@ use crate::model::utils::{
     members,
     remove_from_map,
     remove_from_map_opt,
+    soundboard_sounds,
     stickers,
 };
 
@@ -379,6 +380,51 @@ pub struct GuildStickersUpdateEvent {
     pub guild_id: GuildId,
 }
 
+/// Requires [`GatewayIntents::GUILD_EMOJIS_AND_STICKERS`].
+///
+/// [Discord docs](https://discord.com/developers/docs/topics/gateway-events#guild-soundboard-sound-create).
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+#[non_exhaustive]
+pub struct GuildSoundboardSoundCreateEvent {
+    pub sound: SoundboardSound,
+}
+
+/// Requires [`GatewayIntents::GUILD_EMOJIS_AND_STICKERS`].
+///
+/// [Discord docs](https://discord.com/developers/docs/topics/gateway-events#guild-soundboard-sound-update).
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+#[non_exhaustive]
+pub struct GuildSoundboardSoundUpdateEvent {
+    pub sound: SoundboardSound,
+}
+
+/// Requires [`GatewayIntents::GUILD_EMOJIS_AND_STICKERS`].
+///
+/// [Discord docs](https://discord.com/developers/docs/topics/gateway-events#guild-soundboard-sound-delete).
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct GuildSoundboardSoundDeleteEvent {
+    pub sound_id: SoundId,
+    pub guild_id: GuildId,
+}
+
+/// Requires [`GatewayIntents::GUILD_EMOJIS_AND_STICKERS`].
+///
+/// [Discord docs](https://discord.com/developers/docs/topics/gateway-events#guild-soundboard-sounds-update).
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct GuildSoundboardSoundsUpdateEvent {
+    #[serde(with = "soundboard_sounds")]
+    pub soundboard_sounds: HashMap<SoundId, SoundboardSound>,
+    pub guild_id: GuildId,
+}
+
 /// Requires [`GatewayIntents::GUILD_INVITES`] and [`Permissions::MANAGE_CHANNELS´] permission.
 ///
 /// [Discord docs](https://discord.com/developers/docs/topics/gateway-events#invite-create).
@@ -528,11 +574,15 @@ pub struct MessageUpdateEvent {
     #[serde(default, deserialize_with = "deserialize_some")]
     pub interaction: Option<Option<Box<MessageInteraction>>>,
     #[serde(default, deserialize_with = "deserialize_some")]
+    pub interaction_metadata: Option<Option<Box<MessageInteractionMetadata>>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
     pub thread: Option<Option<GuildChannel>>,
     pub components: Option<Vec<ActionRow>>,
     pub sticker_items: Option<Vec<StickerItem>>,
     pub position: Option<Option<u64>>,
     pub role_subscription_data: Option<Option<RoleSubscriptionData>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub poll: Option<Option<Box<Poll>>>,
     pub guild_id: Option<GuildId>,
     pub member: Option<Option<Box<PartialMember>>>,
 }
@@ -570,11 +620,13 @@ impl MessageUpdateEvent {
             flags,
             referenced_message,
             interaction,
+            interaction_metadata,
             thread,
             components,
             sticker_items,
             position,
             role_subscription_data,
+            poll,
             guild_id,
             member,
         } = self;
@@ -607,11 +659,13 @@ impl MessageUpdateEvent {
         if let Some(x) = flags { message.flags.clone_from(x) }
         if let Some(x) = referenced_message { message.referenced_message.clone_from(x) }
         if let Some(x) = interaction { message.interaction.clone_from(x) }
+        if let Some(x) = interaction_metadata { message.interaction_metadata.clone_from(x) }
         if let Some(x) = thread { message.thread.clone_from(x) }
         if let Some(x) = components { message.components.clone_from(x) }
         if let Some(x) = sticker_items { message.sticker_items.clone_from(x) }
         if let Some(x) = position { message.position.clone_from(x) }
         if let Some(x) = role_subscription_data { message.role_subscription_data.clone_from(x) }
+        if let Some(x) = poll { message.poll.clone_from(x) }
         message.guild_id = *guild_id;
         if let Some(x) = member { message.member.clone_from(x) }
     }
@@ -1057,6 +1111,34 @@ pub struct EntitlementDeleteEvent {
     pub entitlement: Entitlement,
 }
 
+/// Requires [`GatewayIntents::GUILD_MESSAGE_POLLS`] or [`GatewayIntents::DIRECT_MESSAGE_POLLS`].
+///
+/// [Discord docs](https://discord.com/developers/docs/topics/gateway-events#message-poll-vote-add)
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct MessagePollVoteAddEvent {
+    pub user_id: UserId,
+    pub channel_id: ChannelId,
+    pub message_id: MessageId,
+    pub guild_id: Option<GuildId>,
+    pub answer_id: AnswerId,
+}
+
+/// Requires [`GatewayIntents::GUILD_MESSAGE_POLLS`] or [`GatewayIntents::DIRECT_MESSAGE_POLLS`].
+///
+/// [Discord docs](https://discord.com/developers/docs/topics/gateway-events#message-poll-vote-remove)
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct MessagePollVoteRemoveEvent {
+    pub user_id: UserId,
+    pub channel_id: ChannelId,
+    pub message_id: MessageId,
+    pub guild_id: Option<GuildId>,
+    pub answer_id: AnswerId,
+}
+
 /// [Discord docs](https://discord.com/developers/docs/topics/gateway-events#payload-structure).
 #[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
 #[allow(clippy::large_enum_variant)]
@@ -1197,6 +1279,14 @@ pub enum Event {
     GuildRoleUpdate(GuildRoleUpdateEvent),
     /// A [`Sticker`] was created, updated, or deleted
     GuildStickersUpdate(GuildStickersUpdateEvent),
+    /// A [`SoundboardSound`] was created.
+    GuildSoundboardSoundCreate(GuildSoundboardSoundCreateEvent),
+    /// A [`SoundboardSound`] was updated.
+    GuildSoundboardSoundUpdate(GuildSoundboardSoundUpdateEvent),
+    /// A [`SoundboardSound`] was deleted.
+    GuildSoundboardSoundDelete(GuildSoundboardSoundDeleteEvent),
+    /// Multiple [`SoundboardSound`]s were updated at once.
+    GuildSoundboardSoundsUpdate(GuildSoundboardSoundsUpdateEvent),
     GuildUpdate(GuildUpdateEvent),
     /// An [`Invite`] was created.
     ///
@@ -1308,6 +1398,10 @@ pub enum Event {
     EntitlementUpdate(EntitlementUpdateEvent),
     /// A user's entitlement was deleted by Discord, or refunded.
     EntitlementDelete(EntitlementDeleteEvent),
+    /// A user voted for an answer of a poll.
+    MessagePollVoteAdd(MessagePollVoteAddEvent),
+    /// A user removed their vote for an answer of a poll.
+    MessagePollVoteRemove(MessagePollVoteRemoveEvent),
     /// An event type not covered by the above
     #[serde(untagged)]
     Unknown(UnknownEvent),
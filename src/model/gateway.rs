@@ -224,7 +224,7 @@ pub struct Gateway {
 ///
 /// [Discord docs](https://discord.com/developers/docs/topics/gateway#client-status-object).
 #[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 #[non_exhaustive]
 pub struct ClientStatus {
     pub desktop: Option<OnlineStatus>,
@@ -494,6 +494,10 @@ bitflags! {
         /// Enables the following gateway events:
         /// - GUILD_EMOJIS_UPDATE
         /// - GUILD_STICKERS_UPDATE
+        /// - GUILD_SOUNDBOARD_SOUND_CREATE
+        /// - GUILD_SOUNDBOARD_SOUND_UPDATE
+        /// - GUILD_SOUNDBOARD_SOUND_DELETE
+        /// - GUILD_SOUNDBOARD_SOUNDS_UPDATE
         const GUILD_EMOJIS_AND_STICKERS = 1 << 3;
         /// Enables the following gateway events:
         /// - GUILD_INTEGRATIONS_UPDATE
@@ -576,6 +580,14 @@ bitflags! {
         /// Enables the following gateway events:
         /// - AUTO_MODERATION_ACTION_EXECUTION
         const AUTO_MODERATION_EXECUTION = 1 << 21;
+        /// Enables the following gateway events for guilds:
+        /// - MESSAGE_POLL_VOTE_ADD
+        /// - MESSAGE_POLL_VOTE_REMOVE
+        const GUILD_MESSAGE_POLLS = 1 << 24;
+        /// Enables the following gateway events for direct messages:
+        /// - MESSAGE_POLL_VOTE_ADD
+        /// - MESSAGE_POLL_VOTE_REMOVE
+        const DIRECT_MESSAGE_POLLS = 1 << 25;
     }
 }
 
@@ -767,6 +779,22 @@ impl GatewayIntents {
     pub const fn auto_moderation_execution(self) -> bool {
         self.contains(Self::AUTO_MODERATION_EXECUTION)
     }
+
+    /// Shorthand for checking that the set of intents contains the [GUILD_MESSAGE_POLLS] intent.
+    ///
+    /// [GUILD_MESSAGE_POLLS]: Self::GUILD_MESSAGE_POLLS
+    #[must_use]
+    pub const fn guild_message_polls(self) -> bool {
+        self.contains(Self::GUILD_MESSAGE_POLLS)
+    }
+
+    /// Shorthand for checking that the set of intents contains the [DIRECT_MESSAGE_POLLS] intent.
+    ///
+    /// [DIRECT_MESSAGE_POLLS]: Self::DIRECT_MESSAGE_POLLS
+    #[must_use]
+    pub const fn direct_message_polls(self) -> bool {
+        self.contains(Self::DIRECT_MESSAGE_POLLS)
+    }
 }
 
 impl Default for GatewayIntents {
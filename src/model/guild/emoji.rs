@@ -14,8 +14,9 @@ use crate::model::utils::default_true;
 #[cfg(all(feature = "cache", feature = "model"))]
 use crate::model::ModelError;
 
-/// Represents a custom guild emoji, which can either be created using the API, or via an
-/// integration. Emojis created using the API only work within the guild it was created in.
+/// Represents a custom emoji, which can either be created using the API, or via an integration.
+/// Guild emojis created using the API only work within the guild it was created in, while
+/// application emojis can be used anywhere by the application's bot user.
 ///
 /// [Discord docs](https://discord.com/developers/docs/resources/emoji#emoji-object).
 #[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
@@ -141,9 +142,7 @@ impl Emoji {
     #[allow(deprecated)]
     #[must_use]
     pub fn find_guild_id(&self, cache: impl AsRef<Cache>) -> Option<GuildId> {
-        for guild_entry in cache.as_ref().guilds.iter() {
-            let guild = guild_entry.value();
-
+        for guild in cache.as_ref().guilds.iter() {
             if guild.emojis.contains_key(&self.id) {
                 return Some(guild.id);
             }
@@ -10,15 +10,18 @@ use crate::builder::{
     CreateChannel,
     CreateCommand,
     CreateScheduledEvent,
+    CreateSoundboardSound,
     CreateSticker,
     EditAutoModRule,
     EditCommandPermissions,
     EditGuild,
+    EditGuildOnboarding,
     EditGuildWelcomeScreen,
     EditGuildWidget,
     EditMember,
     EditRole,
     EditScheduledEvent,
+    EditSoundboardSound,
     EditSticker,
 };
 #[cfg(all(feature = "cache", feature = "model"))]
@@ -474,6 +477,41 @@ impl GuildId {
         builder.execute(cache_http, self).await
     }
 
+    /// Creates a new soundboard sound in the guild with the data set, if any.
+    ///
+    /// **Note**: Requires the [Create Guild Expressions] permission.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use serenity::http::Http;
+    /// use serenity::builder::{CreateAttachment, CreateSoundboardSound};
+    /// use serenity::model::id::GuildId;
+    ///
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let http: Http = unimplemented!();
+    /// let sound = CreateAttachment::path("./quack.mp3").await?;
+    /// let builder = CreateSoundboardSound::new("quack", &sound).volume(0.5).emoji('\u{1f986}');
+    /// let _ = GuildId::new(7).create_soundboard_sound(&http, builder).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the `cache` is enabled, returns a [`ModelError::InvalidPermissions`] if the current user
+    /// lacks permission. Otherwise returns [`Error::Http`], as well as if invalid data is given.
+    ///
+    /// [Create Guild Expressions]: Permissions::CREATE_GUILD_EXPRESSIONS
+    #[inline]
+    pub async fn create_soundboard_sound(
+        self,
+        cache_http: impl CacheHttp,
+        builder: CreateSoundboardSound<'_>,
+    ) -> Result<SoundboardSound> {
+        builder.execute(cache_http, self).await
+    }
+
     /// Creates a new sticker in the guild with the data set, if any.
     ///
     /// **Note**: Requires the [Create Guild Expressions] permission.
@@ -612,6 +650,28 @@ impl GuildId {
         http.as_ref().delete_sticker(self, sticker_id.into(), None).await
     }
 
+    /// Deletes a [`SoundboardSound`] from the guild.
+    ///
+    /// **Note**: If the sound was created by the current user, requires either the [Create Guild
+    /// Expressions] or the [Manage Guild Expressions] permission. Otherwise, the [Manage Guild
+    /// Expressions] permission is required.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the current user lacks permission, or if a sound with the given
+    /// id does not exist in the guild.
+    ///
+    /// [Create Guild Expressions]: Permissions::CREATE_GUILD_EXPRESSIONS
+    /// [Manage Guild Expressions]: Permissions::MANAGE_GUILD_EXPRESSIONS
+    #[inline]
+    pub async fn delete_soundboard_sound(
+        self,
+        http: impl AsRef<Http>,
+        sound_id: impl Into<SoundId>,
+    ) -> Result<()> {
+        http.as_ref().delete_guild_soundboard_sound(self, sound_id.into(), None).await
+    }
+
     /// Edits the current guild with new data where specified.
     ///
     /// **Note**: Requires the [Manage Guild] permission.
@@ -800,6 +860,28 @@ impl GuildId {
         builder.execute(cache_http, (self, event_id.into())).await
     }
 
+    /// Edits a soundboard sound.
+    ///
+    /// **Note**: If the sound was created by the current user, requires either the [Create Guild
+    /// Expressions] or the [Manage Guild Expressions] permission. Otherwise, the [Manage Guild
+    /// Expressions] permission is required.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the current user lacks permission, or if invalid data is given.
+    ///
+    /// [Create Guild Expressions]: Permissions::CREATE_GUILD_EXPRESSIONS
+    /// [Manage Guild Expressions]: Permissions::MANAGE_GUILD_EXPRESSIONS
+    #[inline]
+    pub async fn edit_soundboard_sound(
+        self,
+        cache_http: impl CacheHttp,
+        sound_id: impl Into<SoundId>,
+        builder: EditSoundboardSound<'_>,
+    ) -> Result<SoundboardSound> {
+        builder.execute(cache_http, (self, sound_id.into())).await
+    }
+
     /// Edits a sticker.
     ///
     /// **Note**: If the sticker was created by the current user, requires either the [Create Guild
@@ -882,6 +964,25 @@ impl GuildId {
         builder.execute(cache_http, self).await
     }
 
+    /// Edits the guild's onboarding flow.
+    ///
+    /// **Note**: Requires the [Manage Guild] and [Manage Roles] permissions.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the current user lacks permission, or if the new configuration
+    /// does not meet the requirements of onboarding.
+    ///
+    /// [Manage Guild]: Permissions::MANAGE_GUILD
+    /// [Manage Roles]: Permissions::MANAGE_ROLES
+    pub async fn edit_onboarding(
+        self,
+        cache_http: impl CacheHttp,
+        builder: EditGuildOnboarding<'_>,
+    ) -> Result<Onboarding> {
+        builder.execute(cache_http, self).await
+    }
+
     /// Edits the guild's widget.
     ///
     /// **Note**: Requires the [Manage Guild] permission.
@@ -983,6 +1084,30 @@ impl GuildId {
         http.as_ref().get_emoji(self, emoji_id).await
     }
 
+    /// Gets all [`SoundboardSound`]s of this guild via HTTP.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Http`] if the guild is unavailable.
+    #[inline]
+    pub async fn soundboard_sounds(self, http: impl AsRef<Http>) -> Result<Vec<SoundboardSound>> {
+        http.as_ref().get_guild_soundboard_sounds(self).await
+    }
+
+    /// Gets a [`SoundboardSound`] of this guild by its Id via HTTP.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Http`] if a sound with that Id does not exist.
+    #[inline]
+    pub async fn soundboard_sound(
+        self,
+        http: impl AsRef<Http>,
+        sound_id: impl Into<SoundId>,
+    ) -> Result<SoundboardSound> {
+        http.as_ref().get_guild_soundboard_sound(self, sound_id.into()).await
+    }
+
     /// Gets all [`Sticker`]s of this guild via HTTP.
     ///
     /// # Errors
@@ -1649,6 +1774,15 @@ impl GuildId {
         http.as_ref().get_guild_welcome_screen(self).await
     }
 
+    /// Gets the guild's onboarding flow.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Http`] if the current user is not in the guild.
+    pub async fn onboarding(self, http: impl AsRef<Http>) -> Result<Onboarding> {
+        http.as_ref().get_guild_onboarding(self).await
+    }
+
     /// Get the guild preview.
     ///
     /// **Note**: The bot need either to be part of the guild or the guild needs to have the
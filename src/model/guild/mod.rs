@@ -7,6 +7,7 @@ mod guild_id;
 mod guild_preview;
 mod integration;
 mod member;
+mod onboarding;
 mod partial_guild;
 mod premium_tier;
 mod role;
@@ -25,6 +26,7 @@ pub use self::guild_id::*;
 pub use self::guild_preview::*;
 pub use self::integration::*;
 pub use self::member::*;
+pub use self::onboarding::*;
 pub use self::partial_guild::*;
 pub use self::premium_tier::*;
 pub use self::role::*;
@@ -283,6 +285,9 @@ pub struct Guild {
     /// The stage instances in this guild.
     #[serde(rename = "guild_scheduled_events")]
     pub scheduled_events: Vec<ScheduledEvent>,
+    /// All of the guild's soundboard sounds.
+    #[serde(default, with = "soundboard_sounds")]
+    pub soundboard_sounds: HashMap<SoundId, SoundboardSound>,
 }
 
 #[cfg(feature = "model")]
@@ -1687,7 +1692,10 @@ impl Guild {
     /// - **username and discriminator**: "zey#5479"
     ///
     /// **Note**: This will only search members that are cached. If you want to search all members
-    /// in the guild via the Http API, use [`Self::search_members`].
+    /// in the guild via the Http API, use [`Self::search_members`]. To search the cached members
+    /// through an index, use [`Cache::member_named`].
+    ///
+    /// [`Cache::member_named`]: crate::cache::Cache::member_named
     #[must_use]
     pub fn member_named(&self, name: &str) -> Option<&Member> {
         let (username, discrim) = match crate::utils::parse_user_tag(name) {
@@ -1706,6 +1714,17 @@ impl Guild {
         self.members.values().find(|member| member.nick.as_ref().is_some_and(|nick| nick == name))
     }
 
+    /// Retrieves all [`Member`]s with the given role.
+    ///
+    /// **Note**: This will only search members that are cached. To search them through an index,
+    /// use [`Cache::members_with_role`].
+    ///
+    /// [`Cache::members_with_role`]: crate::cache::Cache::members_with_role
+    #[must_use]
+    pub fn members_with_role(&self, role_id: RoleId) -> Vec<&Member> {
+        self.members.values().filter(|member| member.roles.contains(&role_id)).collect()
+    }
+
     /// Retrieves all [`Member`] that start with a given [`String`].
     ///
     /// `sorted` decides whether the best early match of the `prefix` should be the criteria to
@@ -2587,6 +2606,51 @@ fn calculate_permissions(data: CalculatePermissions) -> Permissions {
     permissions
 }
 
+/// Removes the permissions which Discord implicitly denies in a channel, as described at
+/// https://discord.com/developers/docs/topics/permissions#implicit-permissions and
+/// https://discord.com/developers/docs/topics/permissions#permissions-for-timed-out-members
+///
+/// In threads, Send Messages is removed as well, since sending messages there is governed by Send
+/// Messages In Threads instead.
+#[cfg(all(feature = "cache", feature = "model"))]
+pub(crate) fn apply_implicit_permissions(
+    mut permissions: Permissions,
+    in_thread: bool,
+    timed_out: bool,
+) -> Permissions {
+    // Owners and administrators bypass every channel-level restriction.
+    if permissions.contains(Permissions::ADMINISTRATOR) {
+        return permissions;
+    }
+
+    // Timed out members can only read the channels they can view.
+    if timed_out {
+        permissions &= Permissions::VIEW_CHANNEL | Permissions::READ_MESSAGE_HISTORY;
+    }
+
+    // Members who can't view a channel can't do anything else in it.
+    if !permissions.contains(Permissions::VIEW_CHANNEL) {
+        return Permissions::empty();
+    }
+
+    // Members who can't send messages can't use the permissions tied to sending them either. In
+    // threads, sending is governed by Send Messages In Threads instead.
+    let send_messages = if in_thread {
+        permissions &= !Permissions::SEND_MESSAGES;
+        Permissions::SEND_MESSAGES_IN_THREADS
+    } else {
+        Permissions::SEND_MESSAGES
+    };
+    if !permissions.contains(send_messages) {
+        permissions &= !(Permissions::SEND_TTS_MESSAGES
+            | Permissions::MENTION_EVERYONE
+            | Permissions::ATTACH_FILES
+            | Permissions::EMBED_LINKS);
+    }
+
+    permissions
+}
+
 /// Checks if a `&str` contains another `&str`.
 #[cfg(feature = "model")]
 fn contains(haystack: &str, needle: &str, case_sensitive: bool) -> bool {
@@ -0,0 +1,213 @@
+use serde::{Deserialize, Deserializer};
+
+use crate::model::prelude::*;
+
+/// The onboarding flow of a guild, shown to new members before they gain access to it.
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/guild#guild-onboarding-object).
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct Onboarding {
+    /// The Id of the guild this onboarding is part of.
+    pub guild_id: GuildId,
+    /// The prompts shown during onboarding and in customize community.
+    pub prompts: Vec<OnboardingPrompt>,
+    /// The Ids of the channels that members get opted into automatically.
+    pub default_channel_ids: Vec<ChannelId>,
+    /// Whether onboarding is enabled in the guild.
+    pub enabled: bool,
+    /// The criteria used to check whether onboarding can be enabled.
+    pub mode: OnboardingMode,
+}
+
+/// A prompt shown during the onboarding of a guild.
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/guild#guild-onboarding-object-onboarding-prompt-structure).
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct OnboardingPrompt {
+    /// The Id of the prompt.
+    pub id: GenericId,
+    /// The kind of the prompt.
+    #[serde(rename = "type")]
+    pub kind: OnboardingPromptType,
+    /// The options available within the prompt.
+    pub options: Vec<OnboardingPromptOption>,
+    /// The title of the prompt.
+    pub title: String,
+    /// Whether users are limited to selecting a single option.
+    pub single_select: bool,
+    /// Whether the prompt must be answered during onboarding.
+    pub required: bool,
+    /// Whether the prompt is shown during onboarding, or only in customize community.
+    pub in_onboarding: bool,
+}
+
+/// An option of an [`OnboardingPrompt`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/guild#guild-onboarding-object-prompt-option-structure).
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct OnboardingPromptOption {
+    /// The Id of the option.
+    pub id: GenericId,
+    /// The Ids of the channels a member is added to when selecting the option.
+    pub channel_ids: Vec<ChannelId>,
+    /// The Ids of the roles assigned to a member when selecting the option.
+    pub role_ids: Vec<RoleId>,
+    /// The emoji of the option, if any.
+    #[serde(default, deserialize_with = "deserialize_emoji")]
+    pub emoji: Option<ReactionType>,
+    /// The title of the option.
+    pub title: String,
+    /// The description of the option.
+    pub description: Option<String>,
+}
+
+// Discord sends an emoji object with both `id` and `name` set to null for options without emoji.
+fn deserialize_emoji<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<ReactionType>, D::Error> {
+    #[derive(Deserialize)]
+    struct PartialEmoji {
+        #[serde(default)]
+        animated: bool,
+        id: Option<EmojiId>,
+        name: Option<String>,
+    }
+
+    Ok(Option::<PartialEmoji>::deserialize(deserializer)?.and_then(|emoji| {
+        match (emoji.id, emoji.name) {
+            (Some(id), name) => Some(ReactionType::Custom {
+                animated: emoji.animated,
+                id,
+                name,
+            }),
+            (None, Some(name)) => Some(ReactionType::Unicode(name)),
+            (None, None) => None,
+        }
+    }))
+}
+
+enum_number! {
+    /// The criteria used to check whether onboarding can be enabled.
+    ///
+    /// [Discord docs](https://discord.com/developers/docs/resources/guild#guild-onboarding-object-onboarding-mode).
+    #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+    #[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+    #[serde(from = "u8", into = "u8")]
+    #[non_exhaustive]
+    pub enum OnboardingMode {
+        /// Only default channels count towards the constraints.
+        #[default]
+        OnboardingDefault = 0,
+        /// Default channels and questions count towards the constraints.
+        OnboardingAdvanced = 1,
+        _ => Unknown(u8),
+    }
+}
+
+enum_number! {
+    /// The kind of an [`OnboardingPrompt`].
+    ///
+    /// [Discord docs](https://discord.com/developers/docs/resources/guild#guild-onboarding-object-prompt-types).
+    #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+    #[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+    #[serde(from = "u8", into = "u8")]
+    #[non_exhaustive]
+    pub enum OnboardingPromptType {
+        /// The options are shown as a list of buttons.
+        #[default]
+        MultipleChoice = 0,
+        /// The options are shown in a dropdown menu.
+        Dropdown = 1,
+        _ => Unknown(u8),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::{from_value, json};
+
+    #[test]
+    fn onboarding_deserialize() {
+        let onboarding: Onboarding = from_value(json!({
+            "guild_id": "960007075288915998",
+            "prompts": [{
+                "id": "1067461047608422473",
+                "title": "What do you want to do in this community?",
+                "options": [
+                    {
+                        "id": "1067461047608422476",
+                        "title": "Chat with Friends",
+                        "description": "",
+                        "emoji": {
+                            "id": "1070002302032826408",
+                            "name": "chat",
+                            "animated": false,
+                        },
+                        "role_ids": [],
+                        "channel_ids": ["962007075288916001"],
+                    },
+                    {
+                        "id": "1070004843541954678",
+                        "title": "Get Gud",
+                        "description": "We have excellent teachers!",
+                        "emoji": {
+                            "id": null,
+                            "name": "\u{1f9e0}",
+                            "animated": false,
+                        },
+                        "role_ids": ["982014491980083211"],
+                        "channel_ids": [],
+                    },
+                    {
+                        "id": "1070004843541954679",
+                        "title": "Lurk",
+                        "description": null,
+                        "emoji": {
+                            "id": null,
+                            "name": null,
+                            "animated": false,
+                        },
+                        "role_ids": [],
+                        "channel_ids": [],
+                    },
+                ],
+                "single_select": false,
+                "required": false,
+                "in_onboarding": true,
+                "type": 0,
+            }],
+            "default_channel_ids": [
+                "998678771706110023",
+                "998678693058719784",
+            ],
+            "enabled": true,
+            "mode": 1,
+        }))
+        .unwrap();
+
+        assert_eq!(onboarding.guild_id, GuildId::new(960007075288915998));
+        assert_eq!(onboarding.default_channel_ids.len(), 2);
+        assert!(onboarding.enabled);
+        assert_eq!(onboarding.mode, OnboardingMode::OnboardingAdvanced);
+
+        let prompt = &onboarding.prompts[0];
+        assert_eq!(prompt.kind, OnboardingPromptType::MultipleChoice);
+        assert!(prompt.in_onboarding);
+        assert!(matches!(
+            prompt.options[0].emoji,
+            Some(ReactionType::Custom { id, .. }) if id == EmojiId::new(1070002302032826408)
+        ));
+        assert_eq!(prompt.options[0].channel_ids, [ChannelId::new(962007075288916001)]);
+        assert_eq!(prompt.options[1].emoji, Some(ReactionType::Unicode("\u{1f9e0}".into())));
+        assert_eq!(prompt.options[1].role_ids, [RoleId::new(982014491980083211)]);
+        assert!(prompt.options[2].emoji.is_none());
+        assert!(prompt.options[2].description.is_none());
+    }
+}
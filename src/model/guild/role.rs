@@ -166,9 +166,7 @@ impl RoleId {
     #[cfg(feature = "cache")]
     #[deprecated = "Use Guild::roles. This performs a loop over the entire cache!"]
     pub fn to_role_cached(self, cache: impl AsRef<Cache>) -> Option<Role> {
-        for guild_entry in cache.as_ref().guilds.iter() {
-            let guild = guild_entry.value();
-
+        for guild in cache.as_ref().guilds.iter() {
             if !guild.roles.contains_key(&self) {
                 continue;
             }
@@ -247,6 +247,11 @@ pub struct StageInstanceId(#[serde(with = "snowflake")] NonZeroU64);
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Deserialize, Serialize)]
 pub struct ForumTagId(#[serde(with = "snowflake")] NonZeroU64);
 
+/// An identifier for a soundboard sound.
+#[repr(packed)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+pub struct SoundId(#[serde(with = "snowflake")] NonZeroU64);
+
 /// An identifier for an entitlement.
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Deserialize, Serialize)]
 pub struct EntitlementId(#[serde(with = "snowflake")] pub NonZeroU64);
@@ -278,6 +283,7 @@ id_u64! {
     RuleId;
     ForumTagId;
     EntitlementId;
+    SoundId;
 }
 
 /// An identifier for a Shard.
@@ -305,6 +311,29 @@ impl fmt::Display for ShardId {
     }
 }
 
+/// An identifier for an answer of a [`Poll`], unique within the poll.
+///
+/// This identifier is not a snowflake, but a small number, starting at 1 for the first answer.
+///
+/// [`Poll`]: super::channel::Poll
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Deserialize, Serialize)]
+pub struct AnswerId(pub u8);
+
+impl AnswerId {
+    /// Retrieves the value as a [`u8`].
+    #[must_use]
+    pub fn get(self) -> u8 {
+        self.0
+    }
+}
+
+impl fmt::Display for AnswerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 mod snowflake {
     use std::fmt;
     use std::num::NonZeroU64;
@@ -0,0 +1,94 @@
+use crate::model::prelude::*;
+
+/// A sound which can be played in voice channels through the soundboard.
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/soundboard#soundboard-sound-object).
+#[cfg_attr(feature = "typesize", derive(typesize::derive::TypeSize))]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct SoundboardSound {
+    /// The unique ID given to this sound.
+    pub sound_id: SoundId,
+    /// The name of the sound.
+    pub name: String,
+    /// The volume of the sound, from 0 to 1.
+    pub volume: f64,
+    /// The Id of the custom emoji of the sound, if any.
+    pub emoji_id: Option<EmojiId>,
+    /// The unicode emoji of the sound, if any.
+    pub emoji_name: Option<String>,
+    /// The Id of the guild the sound belongs to. This is [`None`] for default sounds.
+    pub guild_id: Option<GuildId>,
+    /// Whether the sound can be used. This may be false if the guild lost the boosts required for
+    /// the sound.
+    pub available: bool,
+    /// The user who created the sound.
+    ///
+    /// **Note**: This is only present if the current user has either the [Create Guild
+    /// Expressions] or the [Manage Guild Expressions] permission.
+    ///
+    /// [Create Guild Expressions]: Permissions::CREATE_GUILD_EXPRESSIONS
+    /// [Manage Guild Expressions]: Permissions::MANAGE_GUILD_EXPRESSIONS
+    pub user: Option<User>,
+}
+
+#[cfg(feature = "model")]
+impl SoundboardSound {
+    /// Returns the emoji of the sound, if any.
+    #[must_use]
+    pub fn emoji(&self) -> Option<ReactionType> {
+        match (self.emoji_id, &self.emoji_name) {
+            (Some(id), name) => Some(ReactionType::Custom {
+                animated: false,
+                id,
+                name: name.clone(),
+            }),
+            (None, Some(name)) => Some(ReactionType::Unicode(name.clone())),
+            (None, None) => None,
+        }
+    }
+
+    /// Retrieves the URL to the sound file.
+    #[inline]
+    #[must_use]
+    pub fn url(&self) -> String {
+        cdn!("/soundboard-sounds/{}", self.sound_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::{from_value, json};
+
+    #[test]
+    fn soundboard_sound_deserialize() {
+        let sound: SoundboardSound = from_value(json!({
+            "name": "quack",
+            "sound_id": "1",
+            "volume": 1.0,
+            "emoji_id": null,
+            "emoji_name": "\u{1f986}",
+            "guild_id": "2",
+            "available": true,
+        }))
+        .unwrap();
+
+        assert_eq!(sound.sound_id, SoundId::new(1));
+        assert_eq!(sound.guild_id, Some(GuildId::new(2)));
+        assert_eq!(sound.emoji_name.as_deref(), Some("\u{1f986}"));
+        assert!(sound.user.is_none());
+
+        let default: SoundboardSound = from_value(json!({
+            "name": "airhorn",
+            "sound_id": "3",
+            "volume": 0.5,
+            "emoji_id": null,
+            "emoji_name": null,
+            "available": true,
+        }))
+        .unwrap();
+        assert!(default.guild_id.is_none());
+        assert!(default.available);
+    }
+}
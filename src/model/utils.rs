@@ -301,6 +301,26 @@ pub mod stickers {
     pub use super::serialize_map_values as serialize;
 }
 
+/// Used with `#[serde(with = "soundboard_sounds")]`
+pub mod soundboard_sounds {
+    use std::collections::HashMap;
+
+    use serde::Deserializer;
+
+    use super::SequenceToMapVisitor;
+    use crate::model::id::SoundId;
+    use crate::model::soundboard::SoundboardSound;
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<SoundId, SoundboardSound>, D::Error> {
+        deserializer
+            .deserialize_seq(SequenceToMapVisitor::new(|sound: &SoundboardSound| sound.sound_id))
+    }
+
+    pub use super::serialize_map_values as serialize;
+}
+
 /// Used with `#[serde(with = "comma_separated_string")]`
 pub mod comma_separated_string {
     use serde::{Deserialize, Deserializer, Serializer};
@@ -44,10 +44,9 @@ impl ArgumentConvert for Guild {
         let lookup_by_id = || guilds.get(&s.parse().ok()?).map(|g| g.clone());
 
         let lookup_by_name = || {
-            guilds.iter().find_map(|m| {
-                let guild = m.value();
-                guild.name.eq_ignore_ascii_case(s).then(|| guild.clone())
-            })
+            guilds
+                .iter()
+                .find_map(|guild| guild.name.eq_ignore_ascii_case(s).then(|| guild.clone()))
         };
 
         lookup_by_id().or_else(lookup_by_name).ok_or(GuildParseError::NotFoundOrMalformed)
@@ -29,6 +29,28 @@ impl fmt::Display for MemberParseError {
     }
 }
 
+#[cfg(feature = "cache")]
+fn lookup_by_cache_index(ctx: impl CacheHttp, guild_id: GuildId, s: &str) -> Option<Member> {
+    let cache = ctx.cache()?;
+    let guild = cache.guild(guild_id)?;
+    let index = cache.member_indexes.get(&guild_id)?;
+
+    if let Some((name, discrim)) = crate::utils::parse_user_tag(s) {
+        let mut members = index.members_with_username(&guild.members, name)?;
+        if let Some(member) = members.find(|m| m.user.discriminator == discrim) {
+            return Some(member.clone());
+        }
+    }
+
+    // Exact username matches take precedence over nickname matches.
+    let member = index
+        .members_with_username(&guild.members, s)?
+        .next()
+        .or_else(|| index.members_with_nickname(&guild.members, s)?.next())
+        .cloned();
+    member
+}
+
 /// Look up a guild member by a string case-insensitively.
 ///
 /// Requires the cache feature to be enabled.
@@ -39,6 +61,11 @@ impl fmt::Display for MemberParseError {
 /// 3. [Lookup by name#discrim](`crate::utils::parse_user_tag`).
 /// 4. Lookup by name
 /// 5. Lookup by nickname
+///
+/// Lookups by name and nickname first search the cached members of the guild if
+/// [`Settings::index_member_names`] is enabled, before searching the guild's members via HTTP.
+///
+/// [`Settings::index_member_names`]: crate::cache::Settings::index_member_names
 #[async_trait::async_trait]
 impl ArgumentConvert for Member {
     type Err = MemberParseError;
@@ -60,6 +87,13 @@ impl ArgumentConvert for Member {
             }
         }
 
+        // Guild::members may be incomplete, so only use it if names are indexed, and fall back to
+        // searching via HTTP if the member wasn't found.
+        #[cfg(feature = "cache")]
+        if let Some(member) = lookup_by_cache_index(&ctx, guild_id, s) {
+            return Ok(member);
+        }
+
         // Following code is inspired by discord.py's MemberConvert::query_member_named
 
         // If string is a username+discriminator
@@ -33,19 +33,13 @@ fn lookup_by_global_cache(ctx: impl CacheHttp, s: &str) -> Option<User> {
 
     let lookup_by_name_and_discrim = || {
         let (name, discrim) = crate::utils::parse_user_tag(s)?;
-        users.iter().find_map(|m| {
-            let user = m.value();
+        users.iter().find_map(|user| {
             (user.discriminator == discrim && user.name.eq_ignore_ascii_case(name))
                 .then(|| user.clone())
         })
     };
 
-    let lookup_by_name = || {
-        users.iter().find_map(|m| {
-            let user = m.value();
-            (user.name == s).then(|| user.clone())
-        })
-    };
+    let lookup_by_name = || users.iter().find_map(|user| (user.name == s).then(|| user.clone()));
 
     lookup_by_id()
         .or_else(lookup_by_mention)